@@ -27,6 +27,7 @@ required_patterns=(
   '"PermissionProfileListParams"'
   '"thread/started"'
   '"turn/started"'
+  '"thread/tokenUsage/updated"'
   '"activePermissionProfile"'
   '"CommandExecutionRequestApprovalParams"'
   '"FileChangeRequestApprovalParams"'
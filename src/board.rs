@@ -1,12 +1,64 @@
+use crate::control_plane::atomic_write_string;
 use anyhow::{Context, anyhow, bail};
+use std::fmt;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+const ARCHIVE_DIVIDER: &str = "***";
+const SETTINGS_MARKER: &str = "%% kanban:settings";
+const COMPLETE_MARKER: &str = "**Complete**";
+
+/// An Obsidian Kanban board, parsed so that rendering an untouched board reproduces it byte for byte.
 #[derive(Debug, Clone)]
 pub struct BoardFile {
     pub path: PathBuf,
     pub preamble: Vec<String>,
     pub sections: Vec<BoardSection>,
+    /// The `***` divider and everything under it up to the settings block; archived cards
+    /// are not lanes, so dispatch never sees or moves them.
+    pub archive: Vec<BoardLine>,
+    /// The trailing `%% kanban:settings` block, kept verbatim.
+    pub settings: Vec<String>,
+    line_ending: &'static str,
+    trailing_newline: bool,
+    /// File contents as loaded, the base for merging with edits made on disk since.
+    loaded: String,
+    moves: Vec<BoardMove>,
+}
+
+#[derive(Debug, Clone)]
+struct BoardMove {
+    link: String,
+    /// Source lane, or `None` when the card was added by `add_card`.
+    from: Option<String>,
+    to: String,
+}
+
+/// A dispatcher card move dropped on save because the card changed on disk in the meantime.
+#[derive(Debug, Clone)]
+pub struct BoardMoveConflict {
+    pub link: String,
+    pub from: String,
+    pub to: String,
+    /// Where the card is in the latest file, or `None` when it was removed or archived.
+    pub found: Option<String>,
+}
+
+impl fmt::Display for BoardMoveConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.found {
+            Some(found) => write!(
+                f,
+                "card [[{}]] was moved to '{}' on disk, so its move from '{}' to '{}' was dropped",
+                self.link, found, self.from, self.to
+            ),
+            None => write!(
+                f,
+                "card [[{}]] was removed on disk, so its move from '{}' to '{}' was dropped",
+                self.link, self.from, self.to
+            ),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -22,10 +74,14 @@ pub enum BoardLine {
     Card(BoardCard),
 }
 
+/// A card linking a ticket note. Multi-line cards keep their indented continuation lines.
 #[derive(Debug, Clone)]
 pub struct BoardCard {
-    pub raw: String,
+    pub lines: Vec<String>,
     pub link: String,
+    pub checked: bool,
+    pub tags: Vec<String>,
+    pub due: Option<String>,
 }
 
 impl BoardFile {
@@ -33,49 +89,100 @@ impl BoardFile {
         let path = path.as_ref();
         let raw = fs::read_to_string(path)
             .with_context(|| format!("failed to read board '{}'", path.display()))?;
+        let board = Self::parse(path, &raw);
+        if board.sections.is_empty() {
+            bail!("board '{}' has no sections", path.display());
+        }
+        Ok(board)
+    }
+
+    fn parse(path: &Path, raw: &str) -> Self {
+        let line_ending = if raw.contains("\r\n") { "\r\n" } else { "\n" };
+        let trailing_newline = raw.ends_with('\n');
+        let body = raw.strip_suffix(line_ending).unwrap_or(raw);
+
         let mut preamble = Vec::new();
-        let mut sections = Vec::new();
-        let mut current_section: Option<BoardSection> = None;
+        let mut sections: Vec<BoardSection> = Vec::new();
+        let mut archive = Vec::new();
+        let mut settings = Vec::new();
+        let mut in_archive = false;
 
-        for line in raw.lines() {
-            if line.starts_with("## ") {
-                if let Some(section) = current_section.take() {
-                    sections.push(section);
-                }
-                let title = line.trim_start_matches("## ").trim().to_string();
-                current_section = Some(BoardSection {
-                    title,
+        for line in body.split(line_ending) {
+            if !settings.is_empty() || line.trim_start().starts_with(SETTINGS_MARKER) {
+                settings.push(line.to_string());
+            } else if in_archive {
+                push_board_line(&mut archive, line);
+            } else if !sections.is_empty() && line.trim() == ARCHIVE_DIVIDER {
+                in_archive = true;
+                archive.push(BoardLine::Raw(line.to_string()));
+            } else if line.starts_with("## ") {
+                sections.push(BoardSection {
+                    title: line.trim_start_matches("## ").trim().to_string(),
                     heading_line: line.to_string(),
                     lines: Vec::new(),
                 });
-                continue;
-            }
-
-            match current_section.as_mut() {
-                Some(section) => section.lines.push(parse_board_line(line)),
-                None => preamble.push(line.to_string()),
+            } else if let Some(section) = sections.last_mut() {
+                push_board_line(&mut section.lines, line);
+            } else {
+                preamble.push(line.to_string());
             }
         }
 
-        if let Some(section) = current_section.take() {
-            sections.push(section);
-        }
-
-        if sections.is_empty() {
-            bail!("board '{}' has no sections", path.display());
-        }
-
-        Ok(Self {
+        Self {
             path: path.to_path_buf(),
             preamble,
             sections,
-        })
+            archive,
+            settings,
+            line_ending,
+            trailing_newline,
+            loaded: raw.to_string(),
+            moves: Vec::new(),
+        }
     }
 
-    pub fn save(&self) -> anyhow::Result<()> {
+    /// Writes the board atomically. When the file changed on disk since it was loaded (an
+    /// Obsidian edit or sync), the card moves made since are replayed onto the latest
+    /// contents instead; moves whose card was meanwhile moved or removed are dropped and
+    /// returned as conflicts, leaving the on-disk edit in place.
+    pub fn save(&mut self) -> anyhow::Result<Vec<BoardMoveConflict>> {
+        let latest = fs::read_to_string(&self.path)
+            .with_context(|| format!("failed to read board '{}'", self.path.display()))?;
+        let mut conflicts = Vec::new();
+        if latest != self.loaded {
+            let mut merged = Self::parse(&self.path, &latest);
+            for board_move in &self.moves {
+                let found = merged.column_of(&board_move.link);
+                let Some(from) = &board_move.from else {
+                    if found.is_none() {
+                        merged.add_card(&board_move.link, &board_move.to)?;
+                    }
+                    continue;
+                };
+                match found.as_deref().map(normalize_column) {
+                    Some(column) if column == normalize_column(from) => {
+                        merged.move_card(&board_move.link, &board_move.to)?;
+                    }
+                    Some(column) if column == normalize_column(&board_move.to) => {}
+                    _ => conflicts.push(BoardMoveConflict {
+                        link: board_move.link.clone(),
+                        from: from.clone(),
+                        to: board_move.to.clone(),
+                        found,
+                    }),
+                }
+            }
+            *self = merged;
+        }
+
         let rendered = self.render();
-        fs::write(&self.path, rendered)
-            .with_context(|| format!("failed to write board '{}'", self.path.display()))
+        if rendered != latest {
+            atomic_write_string(&self.path, &rendered)
+                .with_context(|| format!("failed to write board '{}'", self.path.display()))?;
+        }
+        self.loaded = rendered;
+        self.moves.clear();
+        Ok(conflicts)
     }
 
     pub fn render(&self) -> String {
@@ -84,55 +191,101 @@ impl BoardFile {
 
         for section in &self.sections {
             out.push(section.heading_line.clone());
-            out.extend(section.lines.iter().map(render_board_line));
+            out.extend(section.lines.iter().flat_map(render_board_line));
         }
+        out.extend(self.archive.iter().flat_map(render_board_line));
+        out.extend(self.settings.iter().cloned());
 
-        let mut rendered = out.join("\n");
-        rendered.push('\n');
+        let mut rendered = out.join(self.line_ending);
+        if self.trailing_newline {
+            rendered.push_str(self.line_ending);
+        }
         rendered
     }
 
+    /// Cards on lanes with their lane title, in board order; archived cards are excluded.
+    pub fn cards(&self) -> impl Iterator<Item = (&str, &BoardCard)> {
+        self.sections.iter().flat_map(|section| {
+            section.lines.iter().filter_map(|line| match line {
+                BoardLine::Card(card) => Some((section.title.as_str(), card)),
+                BoardLine::Raw(_) => None,
+            })
+        })
+    }
+
+    pub fn card(&self, link: &str) -> Option<&BoardCard> {
+        self.cards()
+            .find(|(_, card)| card.link == link)
+            .map(|(_, card)| card)
+    }
+
+    fn column_of(&self, link: &str) -> Option<String> {
+        self.cards()
+            .find(|(_, card)| card.link == link)
+            .map(|(title, _)| title.to_string())
+    }
+
     pub fn card_positions(&self) -> Vec<(String, String)> {
-        let mut positions = Vec::new();
-        for section in &self.sections {
-            for line in &section.lines {
-                if let BoardLine::Card(card) = line {
-                    positions.push((card.link.clone(), section.title.clone()));
-                }
-            }
-        }
-        positions
+        self.cards()
+            .map(|(title, card)| (card.link.clone(), title.to_string()))
+            .collect()
     }
 
+    /// Moves a card after the last item of the destination lane, creating the lane when
+    /// missing. Entering or leaving a lane marked `**Complete**` updates the checkbox the
+    /// way the Kanban plugin does.
     pub fn move_card(&mut self, link: &str, destination_title: &str) -> anyhow::Result<bool> {
-        let mut moved_card: Option<BoardCard> = None;
+        let mut moved: Option<(BoardCard, String, bool)> = None;
 
         for section in &mut self.sections {
-            let mut index = 0;
-            while index < section.lines.len() {
-                let should_remove = matches!(
-                    section.lines.get(index),
-                    Some(BoardLine::Card(card)) if card.link == link
-                );
-                if should_remove {
-                    let line = section.lines.remove(index);
-                    if let BoardLine::Card(card) = line {
-                        moved_card = Some(card);
-                    }
-                    break;
-                } else {
-                    index += 1;
+            let position = section
+                .lines
+                .iter()
+                .position(|line| matches!(line, BoardLine::Card(card) if card.link == link));
+            if let Some(index) = position {
+                let source_complete = section.marks_complete();
+                if let BoardLine::Card(card) = section.lines.remove(index) {
+                    moved = Some((card, section.title.clone(), source_complete));
                 }
-            }
-            if moved_card.is_some() {
                 break;
             }
         }
 
-        let Some(card) = moved_card else {
+        let Some((card, source_title, source_complete)) = moved else {
             return Ok(false);
         };
+        self.moves.push(BoardMove {
+            link: link.to_string(),
+            from: Some(source_title),
+            to: destination_title.to_string(),
+        });
+        self.insert_card(card, destination_title, source_complete)?;
+        Ok(true)
+    }
 
+    /// Adds an unchecked card linking `link` at the end of the destination lane. Returns
+    /// `false` without touching the board when a lane already holds a card for the link.
+    pub fn add_card(&mut self, link: &str, destination_title: &str) -> anyhow::Result<bool> {
+        if self.card(link).is_some() {
+            return Ok(false);
+        }
+        let card = BoardCard::parse(vec![format!("- [ ] [[{}]]", link)])
+            .ok_or_else(|| anyhow!("'{}' is not a valid card link", link))?;
+        self.moves.push(BoardMove {
+            link: link.to_string(),
+            from: None,
+            to: destination_title.to_string(),
+        });
+        self.insert_card(card, destination_title, false)?;
+        Ok(true)
+    }
+
+    fn insert_card(
+        &mut self,
+        mut card: BoardCard,
+        destination_title: &str,
+        source_complete: bool,
+    ) -> anyhow::Result<()> {
         let destination_index = self
             .sections
             .iter()
@@ -143,7 +296,7 @@ impl BoardFile {
                 self.sections.push(BoardSection {
                     title: destination_title.to_string(),
                     heading_line: format!("## {}", destination_title),
-                    lines: vec![BoardLine::Raw(String::new())],
+                    lines: vec![BoardLine::Raw(String::new()), BoardLine::Raw(String::new())],
                 });
                 self.sections.len() - 1
             });
@@ -152,8 +305,62 @@ impl BoardFile {
             .sections
             .get_mut(destination_index)
             .ok_or_else(|| anyhow!("destination section '{}' is missing", destination_title))?;
-        section.lines.push(BoardLine::Card(card));
-        Ok(true)
+        let destination_complete = section.marks_complete();
+        if source_complete || destination_complete {
+            card.set_checked(destination_complete);
+        }
+        let insert_at = section
+            .lines
+            .iter()
+            .rposition(|line| !line.is_blank())
+            .map(|index| index + 1)
+            .unwrap_or_else(|| section.lines.len().min(1));
+        section.lines.insert(insert_at, BoardLine::Card(card));
+        Ok(())
+    }
+}
+
+impl BoardSection {
+    /// Lanes configured with "Mark cards in this list as complete" carry a `**Complete**` line.
+    pub fn marks_complete(&self) -> bool {
+        self.lines
+            .iter()
+            .any(|line| matches!(line, BoardLine::Raw(raw) if raw.trim() == COMPLETE_MARKER))
+    }
+}
+
+impl BoardLine {
+    fn is_blank(&self) -> bool {
+        matches!(self, BoardLine::Raw(raw) if raw.trim().is_empty())
+    }
+}
+
+impl BoardCard {
+    fn parse(lines: Vec<String>) -> Option<Self> {
+        let first = lines.first()?;
+        let checked = matches!(first.as_bytes().get(3), Some(b'x' | b'X'));
+        let link = lines.iter().find_map(|line| extract_ticket_link(line))?;
+        let mut tags = Vec::new();
+        let mut due = None;
+        for line in &lines {
+            collect_tags(line, &mut tags);
+            due = due.or_else(|| extract_card_date(line));
+        }
+        Some(Self {
+            lines,
+            link,
+            checked,
+            tags,
+            due,
+        })
+    }
+
+    fn set_checked(&mut self, checked: bool) {
+        let Some(first) = self.lines.first_mut() else {
+            return;
+        };
+        first.replace_range(3..4, if checked { "x" } else { " " });
+        self.checked = checked;
     }
 }
 
@@ -203,31 +410,200 @@ pub fn resolve_wiki_link(vault_path: &Path, link: &str) -> PathBuf {
     resolved
 }
 
-fn parse_board_line(line: &str) -> BoardLine {
-    if let Some(link) = extract_ticket_link(line) {
-        return BoardLine::Card(BoardCard {
-            raw: line.to_string(),
-            link,
+/// Appends a line, folding indented continuation lines into the card above them.
+fn push_board_line(lines: &mut Vec<BoardLine>, line: &str) {
+    let continuation = line.starts_with([' ', '\t']) && !line.trim().is_empty();
+    if continuation {
+        if let Some(BoardLine::Card(card)) = lines.last_mut() {
+            card.lines.push(line.to_string());
+            return;
+        }
+        // The ticket link may first appear on a continuation line of a card.
+        let start = lines.iter().rposition(|line| {
+            !matches!(line, BoardLine::Raw(raw) if raw.starts_with([' ', '\t']) && !raw.trim().is_empty())
         });
+        if let Some(start) = start
+            && matches!(&lines[start], BoardLine::Raw(raw) if is_card_start(raw))
+        {
+            let mut block = lines
+                .drain(start..)
+                .flat_map(|line| render_board_line(&line))
+                .collect::<Vec<_>>();
+            block.push(line.to_string());
+            match BoardCard::parse(block.clone()) {
+                Some(card) => lines.push(BoardLine::Card(card)),
+                None => lines.extend(block.into_iter().map(BoardLine::Raw)),
+            }
+            return;
+        }
     }
-    BoardLine::Raw(line.to_string())
+
+    if is_card_start(line)
+        && let Some(card) = BoardCard::parse(vec![line.to_string()])
+    {
+        lines.push(BoardLine::Card(card));
+        return;
+    }
+    lines.push(BoardLine::Raw(line.to_string()));
 }
 
-fn render_board_line(line: &BoardLine) -> String {
+fn render_board_line(line: &BoardLine) -> Vec<String> {
     match line {
-        BoardLine::Raw(raw) => raw.clone(),
-        BoardLine::Card(card) => card.raw.clone(),
+        BoardLine::Raw(raw) => vec![raw.clone()],
+        BoardLine::Card(card) => card.lines.clone(),
     }
 }
 
+/// Kanban cards are top-level checklist items; indented checkboxes belong to the card above.
+fn is_card_start(line: &str) -> bool {
+    let bytes = line.as_bytes();
+    bytes.starts_with(b"- [")
+        && matches!(bytes.get(3), Some(b' ' | b'x' | b'X'))
+        && bytes.get(4) == Some(&b']')
+        && matches!(bytes.get(5), None | Some(b' '))
+}
+
+/// First wiki link in the line, skipping `@[[date]]` links the plugin uses for due dates.
 fn extract_ticket_link(line: &str) -> Option<String> {
-    let trimmed = line.trim_start();
-    if !(trimmed.starts_with("- [ ] ") || trimmed.starts_with("- [x] ")) {
-        return None;
+    let mut rest = line;
+    let mut offset = 0;
+    while let Some(start) = rest.find("[[") {
+        let end = rest[start + 2..].find("]]")?;
+        let link = &rest[start + 2..start + 2 + end];
+        let is_date = line[..offset + start].ends_with('@');
+        if !is_date && !link.trim().is_empty() {
+            return Some(link.to_string());
+        }
+        offset += start + 2 + end + 2;
+        rest = &line[offset..];
+    }
+    None
+}
+
+fn collect_tags(line: &str, tags: &mut Vec<String>) {
+    for word in line.split_whitespace() {
+        let Some(tag) = word.strip_prefix('#') else {
+            continue;
+        };
+        let tag = tag.trim_end_matches([',', '.', ';', ':', ')']);
+        let valid = !tag.is_empty()
+            && tag
+                .chars()
+                .all(|ch| ch.is_alphanumeric() || matches!(ch, '-' | '_' | '/'))
+            && !tag.chars().all(|ch| ch.is_ascii_digit());
+        if valid && !tags.iter().any(|existing| existing == tag) {
+            tags.push(tag.to_string());
+        }
     }
+}
+
+/// The plugin's card date, written as `@{2026-03-02}` or `@[[2026-03-02]]`.
+fn extract_card_date(line: &str) -> Option<String> {
+    if let Some(start) = line.find("@{") {
+        let end = line[start + 2..].find('}')?;
+        return Some(line[start + 2..start + 2 + end].to_string());
+    }
+    let start = line.find("@[[")?;
+    let end = line[start + 3..].find("]]")?;
+    Some(line[start + 3..start + 3 + end].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BoardFile;
+    use crate::test_support::unique_temp_dir;
+    use std::fs;
+    use std::path::Path;
 
-    let start = trimmed.find("[[")?;
-    let end = trimmed[start + 2..].find("]]")?;
-    let link = &trimmed[start + 2..start + 2 + end];
-    Some(link.to_string())
+    const KANBAN_BOARD: &str = "---\n\nkanban-plugin: basic\n\n---\n\n## Ready for Codex\n\n- [ ] [[Tickets/parser]] #backend @{2026-03-02}\n\t  Keep the grammar stable.\n\t- [ ] Nested checklist item\n- [ ] Loose idea without a ticket\n- [ ] Split card\n\tSee [[Tickets/split]]\n\n\n## Done\n\n**Complete**\n- [x] [[Tickets/cli]]\n\n\n***\n\n## Archive\n\n- [x] [[Tickets/old]]\n\n%% kanban:settings\n```\n{\"kanban-plugin\":\"basic\"}\n```\n%%";
+
+    #[test]
+    fn parse_round_trips_plugin_format_and_reads_card_metadata() {
+        let board = BoardFile::parse(Path::new("Board.md"), KANBAN_BOARD);
+        assert_eq!(board.render(), KANBAN_BOARD);
+        assert_eq!(
+            board.card_positions(),
+            vec![
+                ("Tickets/parser".to_string(), "Ready for Codex".to_string()),
+                ("Tickets/split".to_string(), "Ready for Codex".to_string()),
+                ("Tickets/cli".to_string(), "Done".to_string()),
+            ]
+        );
+        let card = board.card("Tickets/parser").unwrap();
+        assert_eq!(card.lines.len(), 3);
+        assert_eq!(card.tags, vec!["backend".to_string()]);
+        assert_eq!(card.due.as_deref(), Some("2026-03-02"));
+        assert!(!card.checked);
+        assert!(board.archive.iter().any(
+            |line| matches!(line, super::BoardLine::Card(card) if card.link == "Tickets/old")
+        ));
+
+        let crlf = KANBAN_BOARD.replace('\n', "\r\n") + "\r\n";
+        assert_eq!(
+            BoardFile::parse(Path::new("Board.md"), &crlf).render(),
+            crlf
+        );
+    }
+
+    #[test]
+    fn move_card_keeps_lane_layout_archive_and_settings() {
+        let mut board = BoardFile::parse(Path::new("Board.md"), KANBAN_BOARD);
+        assert!(board.move_card("Tickets/parser", "Done").unwrap());
+        assert!(board.move_card("Tickets/cli", "Codex Working").unwrap());
+
+        let rendered = board.render();
+        assert!(rendered.contains(
+            "## Done\n\n**Complete**\n- [x] [[Tickets/parser]] #backend @{2026-03-02}\n\t  Keep the grammar stable.\n\t- [ ] Nested checklist item\n\n\n## Codex Working\n\n- [ ] [[Tickets/cli]]\n\n***"
+        ));
+        assert!(rendered.ends_with("```\n%%"));
+        assert!(board.card("Tickets/parser").unwrap().checked);
+        assert!(!board.move_card("Tickets/old", "Ready for Codex").unwrap());
+
+        assert!(board.add_card("Tickets/new", "Ready for Codex").unwrap());
+        assert!(!board.add_card("Tickets/cli", "Backlog").unwrap());
+        assert!(board.render().contains(
+            "- [ ] Split card\n\tSee [[Tickets/split]]\n- [ ] [[Tickets/new]]\n\n\n## Done"
+        ));
+    }
+
+    #[test]
+    fn save_merges_moves_onto_edits_made_on_disk() {
+        let root = unique_temp_dir("jarvisctl-board-merge");
+        fs::create_dir_all(&root).unwrap();
+        let path = root.join("Board.md");
+        fs::write(&path, KANBAN_BOARD).unwrap();
+
+        let mut board = BoardFile::load(&path).unwrap();
+        board.move_card("Tickets/parser", "Codex Working").unwrap();
+        board.move_card("Tickets/split", "Codex Working").unwrap();
+        fs::write(
+            &path,
+            KANBAN_BOARD
+                .replace("- [ ] Loose idea", "- [ ] Edited idea")
+                .replace("- [ ] Split card\n\tSee [[Tickets/split]]\n", "")
+                .replace(
+                    "**Complete**\n",
+                    "**Complete**\n- [x] Split card\n\tSee [[Tickets/split]]\n",
+                ),
+        )
+        .unwrap();
+
+        let conflicts = board.save().unwrap();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].link, "Tickets/split");
+        assert_eq!(conflicts[0].found.as_deref(), Some("Done"));
+
+        let saved = BoardFile::load(&path).unwrap();
+        assert!(saved.render().contains("- [ ] Edited idea"));
+        assert_eq!(
+            saved.card_positions(),
+            vec![
+                ("Tickets/split".to_string(), "Done".to_string()),
+                ("Tickets/cli".to_string(), "Done".to_string()),
+                ("Tickets/parser".to_string(), "Codex Working".to_string()),
+            ]
+        );
+
+        let _ = fs::remove_dir_all(root);
+    }
 }
@@ -1,6 +1,8 @@
 use crate::SessionBackend;
 use crate::codex_app::{CodexAppLaunchManifest, CodexAppProtocolConfig, spawn_codex_app_session};
-use crate::native::{NativeSessionMetadata, RuntimeContextMetadata};
+use crate::native::{
+    AgentRestartPolicy, NativeSessionMetadata, NativeSessionOptions, RuntimeContextMetadata,
+};
 use crate::ticket::{TicketNote, slugify};
 use anyhow::{Context, ensure};
 use clap::ValueEnum;
@@ -40,6 +42,7 @@ pub struct CodexLaunchOptions {
     pub context_overlay: RuntimeContextMetadata,
     pub extra_runtime_args: Vec<String>,
     pub startup_delay_ms: u64,
+    pub restart_policy: AgentRestartPolicy,
     pub command: Vec<String>,
 }
 
@@ -140,6 +143,10 @@ pub fn launch_codex_ticket(options: CodexLaunchOptions) -> anyhow::Result<CodexL
                     transcript_path: resume_transcript_path,
                     ..prepared.runtime_context.clone()
                 }),
+                NativeSessionOptions {
+                    restart_policy: options.restart_policy,
+                    ..NativeSessionOptions::default()
+                },
             )?;
 
             if options.startup_delay_ms > 0 {
@@ -359,6 +366,9 @@ pub fn prepare_codex_ticket_launch(
             recent_events: Vec::new(),
             server_requests: Vec::new(),
             subagents: Vec::new(),
+            turn_token_usage: None,
+            token_usage: None,
+            token_budget: ticket.frontmatter.codex_goal_token_budget,
         },
         options.context_overlay.clone(),
     );
@@ -419,6 +429,7 @@ fn codex_app_protocol_config(ticket: &TicketNote) -> CodexAppProtocolConfig {
             .collect(),
         goal: trim_opt(frontmatter.codex_goal.as_deref()),
         goal_token_budget: frontmatter.codex_goal_token_budget,
+        request_policy: frontmatter.codex_request_policy.clone(),
         memory_mode: trim_opt(frontmatter.codex_memory_mode.as_deref()),
         enabled_features: frontmatter
             .codex_enable_features
@@ -828,6 +839,9 @@ fn merge_runtime_context(
     if !overlay.subagents.is_empty() {
         base.subagents = overlay.subagents;
     }
+    if overlay.token_budget.is_some() {
+        base.token_budget = overlay.token_budget;
+    }
     base
 }
 
@@ -1260,11 +1274,11 @@ mod tests {
         HistoricalLaunchRecord, RuntimeContextMetadata, build_base_command, build_codex_prompt,
         canonical_path_string, discover_latest_launch_session_id_in_dir, merge_runtime_environment,
     };
+    use crate::test_support::unique_temp_dir;
     use crate::ticket::TicketNote;
     use std::collections::BTreeMap;
     use std::fs;
     use std::path::Path;
-    use std::time::{SystemTime, UNIX_EPOCH};
 
     #[test]
     fn build_base_command_inserts_resume_id_when_missing() {
@@ -1377,12 +1391,4 @@ repo_path: /tmp/repo
             Some("planner")
         );
     }
-
-    fn unique_temp_dir(prefix: &str) -> std::path::PathBuf {
-        let nanos = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_nanos();
-        std::env::temp_dir().join(format!("{}-{}", prefix, nanos))
-    }
 }
@@ -1,12 +1,21 @@
+use crate::events::{
+    RuntimeEvent, RuntimeEventBus, RuntimeEventKind, diff_session_events, pump_subscription,
+};
 use crate::native::{
     NativeAgentMetadata, NativeSessionMetadata, RuntimeContextMetadata, RuntimeFeedEntry,
-    RuntimeServerRequest, RuntimeSubagentAction, RuntimeSubagentMetadata,
+    RuntimeServerRequest, RuntimeSubagentAction, RuntimeSubagentMetadata, RuntimeTokenUsage,
+    compact_token_count,
 };
 use crate::operator_request::{
     OperatorRequestResolveOptions, expire_operator_request, resolve_operator_request,
     upsert_server_operator_request,
 };
+use crate::request_policy::{
+    RequestPolicyAction, RequestPolicyAuditEntry, RequestPolicyRule, RequestPolicyScope,
+    append_request_policy_audit, evaluate_request_policy, policy_response, server_request_facts,
+};
 use crate::tui::view_agent;
+use crate::usage::{TokenUsageRecord, append_usage_record, budget_label};
 use anyhow::{Context, anyhow, bail, ensure};
 use base64::Engine;
 use base64::engine::general_purpose::STANDARD as BASE64;
@@ -25,6 +34,7 @@ use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex, mpsc};
 use std::thread;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::warn;
 
 const SESSION_DIR_NAME: &str = "sessions";
 const MANIFEST_DIR_NAME: &str = "manifests";
@@ -112,6 +122,8 @@ pub struct CodexAppProtocolConfig {
     pub thread_config: BTreeMap<String, Value>,
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub turn_config: BTreeMap<String, Value>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub request_policy: Vec<RequestPolicyRule>,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, ValueEnum, Default)]
@@ -153,6 +165,12 @@ enum ClientMessage {
         error: Option<String>,
     },
     Interrupt,
+    Subscribe {
+        #[serde(default)]
+        kinds: Vec<RuntimeEventKind>,
+        #[serde(default)]
+        follow: bool,
+    },
     KillSession,
 }
 
@@ -168,6 +186,7 @@ enum ServerMessage {
     Attached { namespace: String, agent: String },
     Output { data_base64: String },
     Exited { agent: String },
+    Event(RuntimeEvent),
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -187,6 +206,7 @@ struct AppSessionState {
     seen_log_items: HashSet<String>,
     active_agent_messages: BTreeMap<String, String>,
     active_command_outputs: BTreeMap<String, String>,
+    turn_token_baseline: RuntimeTokenUsage,
 }
 
 struct CodexAppSession {
@@ -202,6 +222,7 @@ struct CodexAppSession {
     log: Mutex<VecDeque<u8>>,
     log_file: Mutex<File>,
     subscribers: Mutex<Vec<mpsc::Sender<Vec<u8>>>>,
+    events: RuntimeEventBus,
     shutdown_requested: AtomicBool,
 }
 
@@ -232,12 +253,17 @@ impl CodexAppSession {
     where
         F: FnOnce(&mut AppSessionState),
     {
-        let raw = {
+        let (raw, events) = {
             let mut state = self.state.lock().unwrap();
+            let before = state.metadata.clone();
             mutator(&mut state);
-            serde_json::to_string_pretty(&state.metadata)
-                .context("failed to encode codex app session metadata")?
+            (
+                serde_json::to_string_pretty(&state.metadata)
+                    .context("failed to encode codex app session metadata")?,
+                diff_session_events(&before, &state.metadata),
+            )
         };
+        self.events.publish(events);
         fs::write(self.metadata_path(), raw).with_context(|| {
             format!(
                 "failed to write codex app session metadata '{}'",
@@ -356,6 +382,18 @@ impl CodexAppSession {
             .and_then(Value::as_str)
             .unwrap_or("unknown");
         let params = value.get("params").cloned().unwrap_or(Value::Null);
+        let escalation = match self.apply_request_policy(&id, &request_id, method, &params) {
+            Ok(Some(RequestPolicyAction::Escalate)) => {
+                Some("Escalated by request policy; waiting for operator response".to_string())
+            }
+            Ok(Some(_)) => return Ok(()),
+            Ok(None) => None,
+            // A policy that cannot be applied must not leave the turn waiting on an answer
+            // nobody will send, so hand the request to the operator instead.
+            Err(error) => Some(format!(
+                "Request policy failed ({error:#}); waiting for operator response"
+            )),
+        };
         let (tx, rx) = mpsc::channel();
         self.pending_server_requests
             .lock()
@@ -365,7 +403,7 @@ impl CodexAppSession {
             &request_id,
             method,
             "pending",
-            Some("Waiting for operator response".to_string()),
+            Some(escalation.unwrap_or_else(|| "Waiting for operator response".to_string())),
             Some(params.clone()),
             None,
             None,
@@ -469,6 +507,125 @@ impl CodexAppSession {
         }
     }
 
+    /// Answers a server request from the ticket and Namespace approval rules when one matches.
+    /// Returns the action taken; escalations are audited and left for the operator.
+    fn apply_request_policy(
+        &self,
+        id: &Value,
+        request_id: &str,
+        method: &str,
+        params: &Value,
+    ) -> anyhow::Result<Option<RequestPolicyAction>> {
+        let context = self.metadata().context.unwrap_or_default();
+        let mut rule_sets = Vec::<(String, Vec<RequestPolicyRule>)>::new();
+        if !self.protocol.request_policy.is_empty() {
+            rule_sets.push(("ticket".to_string(), self.protocol.request_policy.clone()));
+        }
+        if let Some(control_namespace) = context.control_namespace.as_deref() {
+            let rules = crate::control_plane::namespace_request_policy(control_namespace)
+                .unwrap_or_default();
+            if !rules.is_empty() {
+                rule_sets.push((format!("namespace/{}", control_namespace), rules));
+            }
+        }
+        if rule_sets.is_empty() {
+            return Ok(None);
+        }
+
+        let facts = server_request_facts(method, params);
+        let repo = self.metadata().working_directory.map(PathBuf::from);
+        let writable_roots = self
+            .protocol
+            .permission_additional_writable_roots
+            .iter()
+            .map(PathBuf::from)
+            .collect::<Vec<_>>();
+        let scope = RequestPolicyScope {
+            repo: repo.as_deref(),
+            writable_roots: &writable_roots,
+        };
+        let Some(decision) = evaluate_request_policy(&rule_sets, &facts, &scope) else {
+            return Ok(None);
+        };
+        let summary = format!(
+            "{} rule {}: {}",
+            decision.source,
+            decision.rule + 1,
+            decision.summary
+        );
+        let audit = append_request_policy_audit(&RequestPolicyAuditEntry {
+            timestamp_epoch_ms: now_epoch_ms(),
+            namespace: self.namespace.clone(),
+            request_id: request_id.to_string(),
+            method: method.to_string(),
+            action: decision.action,
+            source: decision.source.clone(),
+            rule: decision.rule + 1,
+            summary: decision.summary.clone(),
+            command: facts.command.clone(),
+            cwd: facts.cwd.as_ref().map(|cwd| cwd.display().to_string()),
+            paths: facts
+                .paths
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect(),
+        });
+        if let Err(error) = audit {
+            warn!("failed to record request policy audit entry: {error:#}");
+            let _ = self.append_text_line(format!("[policy] audit write failed: {error:#}"));
+        }
+        if decision.action == RequestPolicyAction::Escalate {
+            self.append_text_line(format!("[policy] escalated {} ({})", method, summary))?;
+            return Ok(Some(decision.action));
+        }
+
+        let approved = decision.action == RequestPolicyAction::Approve;
+        let response = policy_response(decision.action, &facts, params);
+        let (status, request_status) = if approved {
+            ("resolved", "approved")
+        } else {
+            ("denied", "denied")
+        };
+        self.record_server_request(
+            request_id,
+            method,
+            status,
+            Some(format!(
+                "{} by policy ({})",
+                if approved { "Approved" } else { "Denied" },
+                summary
+            )),
+            Some(params.clone()),
+            Some(response.clone()),
+            (!approved).then(|| summary.clone()),
+        )?;
+        if let Ok(operator_request) =
+            upsert_server_operator_request(&self.namespace, request_id, method, params.clone())
+        {
+            let _ = resolve_operator_request(
+                &operator_request.id,
+                OperatorRequestResolveOptions {
+                    status: request_status.to_string(),
+                    response: Some(response.clone()),
+                    error: (!approved).then(|| summary.clone()),
+                    decided_by: Some("policy".to_string()),
+                    decision: Some(summary.clone()),
+                },
+            );
+        }
+        self.append_text_line(format!(
+            "[policy] {} {} ({})",
+            if approved { "approved" } else { "denied" },
+            facts.command.as_deref().unwrap_or(method),
+            summary
+        ))?;
+        self.send_json(&json!({
+            "id": id,
+            "result": response,
+        }))?;
+        Ok(Some(decision.action))
+    }
+
     fn record_server_request(
         &self,
         request_id: &str,
@@ -626,6 +783,11 @@ impl CodexAppSession {
             }
             "turn/started" => {
                 if let Some(turn) = params.get("turn") {
+                    self.mutate_state(|state| {
+                        let context = state.metadata.context.get_or_insert_with(Default::default);
+                        state.turn_token_baseline = context.token_usage.unwrap_or_default();
+                        context.turn_token_usage = None;
+                    })?;
                     self.apply_turn(turn)?;
                     self.upsert_runtime_event(
                         format!(
@@ -668,6 +830,21 @@ impl CodexAppSession {
                         None,
                     )?;
                     self.append_text_line(format!("[turn] {}", status))?;
+                    self.record_turn_usage()?;
+                }
+            }
+            "thread/tokenUsage/updated" => {
+                if let Some(total) = params
+                    .get("tokenUsage")
+                    .and_then(|usage| usage.get("total"))
+                    .and_then(token_usage_from_value)
+                {
+                    self.mutate_state(|state| {
+                        let baseline = state.turn_token_baseline;
+                        let context = state.metadata.context.get_or_insert_with(Default::default);
+                        context.token_usage = Some(total);
+                        context.turn_token_usage = Some(total.since(&baseline));
+                    })?;
                 }
             }
             "item/started" => {
@@ -791,6 +968,51 @@ impl CodexAppSession {
         Ok(())
     }
 
+    /// Logs the finished turn's token counts and appends them to the usage ledger.
+    fn record_turn_usage(&self) -> anyhow::Result<()> {
+        let context = self.metadata().context.unwrap_or_default();
+        let Some(usage) = context.turn_token_usage.filter(|usage| !usage.is_empty()) else {
+            return Ok(());
+        };
+        let total = context.token_usage.unwrap_or(usage);
+        let mut line = format!(
+            "[tokens] turn {} | session total={}",
+            usage.summary(),
+            compact_token_count(total.total_tokens)
+        );
+        if context.token_budget.is_some() {
+            line.push_str(&format!(
+                " budget {}",
+                budget_label(total.total_tokens, context.token_budget)
+            ));
+        }
+        self.append_text_line(line)?;
+        if let Some(budget) = context
+            .token_budget
+            .filter(|budget| total.total_tokens > *budget)
+        {
+            self.upsert_runtime_event(
+                "token-budget".to_string(),
+                "tokens",
+                "Token budget exceeded",
+                Some(format!("{} of {} tokens", total.total_tokens, budget)),
+                Some("over-budget".to_string()),
+                Some("jarvisctl".to_string()),
+            )?;
+        }
+        append_usage_record(&TokenUsageRecord {
+            timestamp_epoch_ms: now_epoch_ms(),
+            namespace: self.namespace.clone(),
+            ticket: context.task_note.clone(),
+            task_id: context.task_id.clone(),
+            thread_id: context.thread_id.clone(),
+            turn_id: context.turn_id.clone(),
+            turn_status: context.turn_status.clone(),
+            usage,
+            budget: context.token_budget,
+        })
+    }
+
     fn mark_item_started(&self, item_id: &str, header: &str) -> anyhow::Result<bool> {
         let should_write = {
             let mut state = self.state.lock().unwrap();
@@ -1696,6 +1918,10 @@ pub fn serve_codex_app_session(manifest_path: PathBuf) -> anyhow::Result<()> {
             codex_environments: manifest.protocol.environments.clone(),
             memory_mode: manifest.protocol.memory_mode.clone(),
             goal_objective: manifest.protocol.goal.clone(),
+            token_budget: manifest
+                .context
+                .token_budget
+                .or(manifest.protocol.goal_token_budget),
             recent_events: vec![RuntimeFeedEntry {
                 id: format!("session:{}:launch", manifest.namespace),
                 kind: "session".to_string(),
@@ -1712,7 +1938,11 @@ pub fn serve_codex_app_session(manifest_path: PathBuf) -> anyhow::Result<()> {
             pid: child_pid,
             running: true,
             exit_code: None,
+            restarts: 0,
+            last_exit_code: None,
+            idle: false,
         }],
+        clients: Vec::new(),
     };
     let session = Arc::new(CodexAppSession {
         namespace: manifest.namespace.clone(),
@@ -1722,6 +1952,7 @@ pub fn serve_codex_app_session(manifest_path: PathBuf) -> anyhow::Result<()> {
             seen_log_items: HashSet::new(),
             active_agent_messages: BTreeMap::new(),
             active_command_outputs: BTreeMap::new(),
+            turn_token_baseline: RuntimeTokenUsage::default(),
         }),
         writer: Mutex::new(stdin),
         protocol: manifest.protocol.clone(),
@@ -1732,6 +1963,7 @@ pub fn serve_codex_app_session(manifest_path: PathBuf) -> anyhow::Result<()> {
         log: Mutex::new(VecDeque::new()),
         log_file: Mutex::new(log_file),
         subscribers: Mutex::new(Vec::new()),
+        events: RuntimeEventBus::default(),
         shutdown_requested: AtomicBool::new(false),
     });
     session.mutate_state(|_| {})?;
@@ -1895,6 +2127,7 @@ pub fn serve_codex_app_session(manifest_path: PathBuf) -> anyhow::Result<()> {
         }
     }
 
+    session.events.close(Duration::from_millis(500));
     let _ = fs::remove_file(&socket_path);
     let _ = fs::remove_file(session.metadata_path());
     let _ = fs::remove_file(events_path);
@@ -2046,6 +2279,47 @@ pub fn tell_codex_app_with_mode(
     }
 }
 
+pub fn stream_codex_app_events(
+    namespace: &str,
+    kinds: &[RuntimeEventKind],
+    follow: bool,
+    mut sink: impl FnMut(RuntimeEvent) -> anyhow::Result<()>,
+) -> anyhow::Result<()> {
+    let socket_path = socket_path_for(namespace)?;
+    let mut stream = UnixStream::connect(&socket_path)
+        .with_context(|| format!("failed to connect '{}'", socket_path.display()))?;
+    send_message(
+        &mut stream,
+        &ClientMessage::Subscribe {
+            kinds: kinds.to_vec(),
+            follow,
+        },
+    )?;
+    let mut reader = BufReader::new(stream);
+    loop {
+        let message = match read_server_message(&mut reader) {
+            Ok(message) => message,
+            Err(error)
+                if error
+                    .to_string()
+                    .contains("codex app server connection closed") =>
+            {
+                return Ok(());
+            }
+            Err(error) => return Err(error),
+        };
+        match message {
+            ServerMessage::Event(event) => sink(event)?,
+            ServerMessage::Error { message } => bail!(message),
+            other => bail!(
+                "unexpected event stream message from codex app session '{}': {:?}",
+                namespace,
+                other
+            ),
+        }
+    }
+}
+
 pub fn interrupt_codex_app(namespace: &str) -> anyhow::Result<()> {
     match request(namespace, &ClientMessage::Interrupt)? {
         ServerMessage::Ok => Ok(()),
@@ -2142,7 +2416,10 @@ pub fn list_codex_app_permission_profiles(
     }
 }
 
-pub fn attach_codex_app(namespace: &str) -> anyhow::Result<()> {
+/// Streams the session output into a local viewer. App-server sessions only take input
+/// through `tell` and `interrupt`, so the viewer never forwards keystrokes and every attach
+/// is effectively read-only; `read_only` marks the view so the operator can tell.
+pub fn attach_codex_app(namespace: &str, read_only: bool) -> anyhow::Result<()> {
     let socket_path = socket_path_for(namespace)?;
     let mut stream = UnixStream::connect(&socket_path)
         .with_context(|| format!("failed to connect '{}'", socket_path.display()))?;
@@ -2205,12 +2482,18 @@ pub fn attach_codex_app(namespace: &str) -> anyhow::Result<()> {
                 | ServerMessage::Metadata(..)
                 | ServerMessage::ThreadHistory(..)
                 | ServerMessage::ThreadSearch(..)
-                | ServerMessage::PermissionProfiles(..) => {}
+                | ServerMessage::PermissionProfiles(..)
+                | ServerMessage::Event(..) => {}
             }
         }
     });
 
-    view_agent(&format!("{}:agent0", namespace), output)
+    let title = if read_only {
+        format!("{}:agent0 (read-only)", namespace)
+    } else {
+        format!("{}:agent0", namespace)
+    };
+    view_agent(&title, output)
 }
 
 fn handle_client(stream: UnixStream, session: Arc<CodexAppSession>) -> anyhow::Result<()> {
@@ -2263,6 +2546,12 @@ fn handle_client_stream(
                 },
             );
         }
+        ClientMessage::Subscribe { kinds, follow } => {
+            let subscription = session.events.subscribe(kinds);
+            pump_subscription(subscription, follow, |event| {
+                send_server_message(writer, &ServerMessage::Event(event))
+            })?;
+        }
         other => {
             let response = match handle_client_message(&session, other) {
                 Ok(response) => response,
@@ -2302,6 +2591,9 @@ fn handle_client_message(
         ClientMessage::Attach { .. } => {
             bail!("attach is not supported over the filesystem control queue")
         }
+        ClientMessage::Subscribe { .. } => {
+            bail!("event subscriptions are not supported over the filesystem control queue")
+        }
         ClientMessage::SendText { text, mode } => {
             session.send_operator_message(&text, mode)?;
             Ok(ServerMessage::Ok)
@@ -2489,7 +2781,8 @@ pub fn attach_codex_app_tcp(host: &str, port: u16, namespace: &str) -> anyhow::R
                 | ServerMessage::Metadata(..)
                 | ServerMessage::ThreadHistory(..)
                 | ServerMessage::ThreadSearch(..)
-                | ServerMessage::PermissionProfiles(..) => {}
+                | ServerMessage::PermissionProfiles(..)
+                | ServerMessage::Event(..) => {}
             }
         }
     });
@@ -2704,6 +2997,17 @@ fn thread_status_from_value(value: &Value) -> Option<String> {
     }
 }
 
+fn token_usage_from_value(value: &Value) -> Option<RuntimeTokenUsage> {
+    let count = |key: &str| value.get(key).and_then(Value::as_u64).unwrap_or_default();
+    value.is_object().then(|| RuntimeTokenUsage {
+        input_tokens: count("inputTokens"),
+        cached_input_tokens: count("cachedInputTokens"),
+        output_tokens: count("outputTokens"),
+        reasoning_output_tokens: count("reasoningOutputTokens"),
+        total_tokens: count("totalTokens"),
+    })
+}
+
 fn thread_id_from_value(thread: &Value) -> Option<String> {
     thread
         .get("id")
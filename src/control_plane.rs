@@ -7,10 +7,13 @@ use crate::codex_app::{
     CodexAppInputMode, collect_codex_app_sessions, delete_codex_app_session,
     tell_codex_app_with_mode,
 };
+use crate::events::{RuntimeEvent, RuntimeEventKind};
 use crate::native::{
-    NativeSessionMetadata, RuntimeContextMetadata, collect_native_sessions, delete_native_session,
+    AgentRestartPolicy, NativeSessionMetadata, RuntimeContextMetadata, collect_native_sessions,
+    delete_native_session,
 };
 use crate::operator_request::OperatorRequestRecord;
+use crate::request_policy::{RequestPolicyRule, validate_request_policy};
 use crate::ticket::slugify;
 use anyhow::{Context, anyhow, bail, ensure};
 use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
@@ -24,17 +27,31 @@ use sha2::{Digest, Sha256};
 use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::env;
 use std::fs;
-use std::io::{self, Write};
+use std::io::{self, BufRead, BufReader, Write};
 use std::path::{Path, PathBuf};
 use std::process::Command as ProcessCommand;
 use std::process::Stdio;
+use std::sync::mpsc;
+use std::thread;
 use std::time::{Duration, Instant};
 use tracing::error;
 
+mod capsules;
+mod deletion;
+mod dry_run;
 mod kubernetes;
 mod reporting;
+mod secrets;
 mod storage;
 
+use capsules::*;
+pub use capsules::{
+    capsule_identity, open_visit_capsule, protect_visit_capsule, rotate_capsule_key,
+};
+pub use deletion::{DeleteCascade, DeleteOptions, delete_declared_resources, delete_resource};
+pub use dry_run::{
+    diff_manifests, dry_run_apply, render_apply_dry_run_output, render_manifest_diff_output,
+};
 use kubernetes::*;
 pub use kubernetes::{apply_kubernetes_resources, render_kubernetes_resources};
 use reporting::*;
@@ -42,6 +59,12 @@ pub use reporting::{
     render_describe_output, render_get_output, render_rollout_history_output,
     render_rollout_status_output, render_worker_validation_output, wait_for_rollout_status_output,
 };
+use secrets::*;
+pub use secrets::{
+    render_secret_key_rotation_output, render_secret_reveal_output, reveal_secret,
+    rotate_secret_key,
+};
+pub(crate) use storage::atomic_write_string;
 use storage::*;
 
 const API_VERSION: &str = "jarvisctl.io/v1alpha1";
@@ -49,6 +72,8 @@ const DEFAULT_KUBERNETES_RUNTIME_IMAGE: &str = "node:25-bookworm-slim";
 const DEFAULT_KUBERNETES_RUNTIME_CONTROL_PORT: u16 = 47832;
 const HEARTBEAT_SERVICE_NAME: &str = "jarvisctl-heartbeat.service";
 const HEARTBEAT_TIMER_NAME: &str = "jarvisctl-heartbeat.timer";
+const CLUSTER_EVENT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+const CLUSTER_EVENT_RECONNECT_DELAY: Duration = Duration::from_secs(5);
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
 pub enum ControlPlaneOutput {
@@ -141,6 +166,20 @@ pub struct NodeSpec {
     pub taints: Vec<String>,
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub capabilities: BTreeMap<String, String>,
+    /// X25519 public key visit capsules for this node are encrypted to.
+    #[serde(
+        default,
+        rename = "capsulePublicKey",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub capsule_public_key: Option<String>,
+    /// Ed25519 public key this node signs the capsules it sends with.
+    #[serde(
+        default,
+        rename = "capsuleSigningKey",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub capsule_signing_key: Option<String>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -598,6 +637,8 @@ pub struct NodeReconcileResult {
 #[derive(Debug, Clone, Serialize)]
 pub struct CapsuleKeyRotationResult {
     pub key_path: String,
+    pub encryption_public_key: String,
+    pub signing_public_key: String,
     pub synced_nodes: Vec<String>,
     pub failures: Vec<NodeFanoutFailure>,
 }
@@ -796,6 +837,8 @@ pub struct NamespaceSpec {
     pub default_driver: Option<CodexRuntimeDriver>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub default_startup_delay_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub request_policy: Vec<RequestPolicyRule>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -818,6 +861,20 @@ pub struct SecretSpec {
         skip_serializing_if = "BTreeMap::is_empty"
     )]
     pub string_data: BTreeMap<String, String>,
+    /// Values sealed at rest; `save_manifest` moves `stringData` here on every write.
+    #[serde(
+        default,
+        rename = "sealedData",
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub sealed_data: BTreeMap<String, SealedSecretValue>,
+    /// Values resolved from files, the environment, commands, or the keyring on each use.
+    #[serde(
+        default,
+        rename = "valueFrom",
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub value_from: BTreeMap<String, SecretValueSource>,
     #[serde(
         default,
         rename = "accessPolicy",
@@ -826,6 +883,90 @@ pub struct SecretSpec {
     pub access_policy: ResourceAccessPolicy,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SealedSecretValue {
+    #[serde(rename = "keyId")]
+    pub key_id: String,
+    pub nonce: String,
+    pub ciphertext: String,
+    /// Truncated SHA-256 of the plaintext so `describe` can show when a value changed.
+    pub digest: String,
+}
+
+/// Reads a Secret value at resolve time instead of storing it; set exactly one field.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct SecretValueSource {
+    /// File owned by the current user and not readable by group or others.
+    #[serde(default, rename = "fromFile", skip_serializing_if = "Option::is_none")]
+    pub from_file: Option<String>,
+    #[serde(default, rename = "fromEnv", skip_serializing_if = "Option::is_none")]
+    pub from_env: Option<String>,
+    /// Command whose stdout is the value, e.g. `pass show openai/api-key`.
+    #[serde(
+        default,
+        rename = "fromCommand",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub from_command: Option<String>,
+    /// Secret Service item attributes, e.g. `{service: openai, account: worker}`.
+    #[serde(
+        default,
+        rename = "fromKeyring",
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub from_keyring: BTreeMap<String, String>,
+}
+
+enum SecretSourceKind<'a> {
+    File(&'a str),
+    Env(&'a str),
+    Command(&'a str),
+    Keyring(&'a BTreeMap<String, String>),
+}
+
+fn non_empty_source(value: &Option<String>) -> Option<&str> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+impl SecretValueSource {
+    fn kind(&self) -> anyhow::Result<SecretSourceKind<'_>> {
+        let mut kinds = Vec::new();
+        if let Some(path) = non_empty_source(&self.from_file) {
+            kinds.push(SecretSourceKind::File(path));
+        }
+        if let Some(name) = non_empty_source(&self.from_env) {
+            kinds.push(SecretSourceKind::Env(name));
+        }
+        if let Some(command) = non_empty_source(&self.from_command) {
+            kinds.push(SecretSourceKind::Command(command));
+        }
+        if !self.from_keyring.is_empty() {
+            kinds.push(SecretSourceKind::Keyring(&self.from_keyring));
+        }
+        ensure!(
+            kinds.len() == 1,
+            "set exactly one of fromFile, fromEnv, fromCommand, or fromKeyring"
+        );
+        Ok(kinds.remove(0))
+    }
+}
+
+impl SecretSpec {
+    pub fn keys(&self) -> Vec<String> {
+        self.string_data
+            .keys()
+            .chain(self.sealed_data.keys())
+            .chain(self.value_from.keys())
+            .cloned()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct VolumeSpec {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
@@ -1115,6 +1256,12 @@ pub struct DeploymentTemplateSpec {
     pub labels: BTreeMap<String, String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub kubernetes: Option<KubernetesRuntimeSpec>,
+    #[serde(
+        default,
+        rename = "restartPolicy",
+        skip_serializing_if = "AgentRestartPolicy::is_never"
+    )]
+    pub restart_policy: AgentRestartPolicy,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -1725,6 +1872,10 @@ struct ConfigMapStatus {
 #[derive(Debug, Clone, Serialize)]
 struct SecretStatus {
     keys: Vec<String>,
+    digests: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    sources: BTreeMap<String, String>,
+    sealed: bool,
     access_policy: ResourceAccessPolicyStatus,
 }
 
@@ -1823,6 +1974,18 @@ pub fn register_node(options: NodeRegisterOptions) -> anyhow::Result<Vec<String>
     if options.local {
         capabilities.extend(probe_local_capabilities());
     }
+    let (capsule_public_key, capsule_signing_key) = if options.local {
+        let (public_key, signing_key) = local_capsule_public_keys()?;
+        (Some(public_key), Some(signing_key))
+    } else {
+        match load_manifest(ResourceKind::Node, &metadata.name, None) {
+            Ok(ResourceManifest::Node(existing)) => (
+                existing.spec.capsule_public_key,
+                existing.spec.capsule_signing_key,
+            ),
+            _ => (None, None),
+        }
+    };
 
     let manifest = ResourceEnvelope {
         api_version: API_VERSION.to_string(),
@@ -1838,6 +2001,8 @@ pub fn register_node(options: NodeRegisterOptions) -> anyhow::Result<Vec<String>
             cordoned: false,
             taints,
             capabilities,
+            capsule_public_key,
+            capsule_signing_key,
         },
     };
     validate_node(&manifest)?;
@@ -1964,7 +2129,7 @@ fn run_node_visit_direct(options: NodeVisitOptions) -> anyhow::Result<NodeVisitR
         .clone()
         .unwrap_or_else(|| format!("visit-{}-{}", slugify(&node.metadata.name), now_epoch_ms()));
     let auth_files = local_codex_auth_files()?;
-    sync_capsule_key_to_remote_node(&node)?;
+    let node = exchange_capsule_identity(&node)?;
     append_auth_audit_event(
         "auth_lease_create_start",
         &node.metadata.name,
@@ -2002,7 +2167,7 @@ fn run_node_visit_direct(options: NodeVisitOptions) -> anyhow::Result<NodeVisitR
         failure_class: None,
         retryable: None,
     })?;
-    let visit_result = run_remote_codex_exec_visit(&target, &options, &namespace);
+    let visit_result = run_remote_codex_exec_visit(&target, &node, &options, &namespace);
     let finished_at_epoch_ms = now_epoch_ms();
     append_auth_audit_event(
         "auth_lease_restore_start",
@@ -3320,6 +3485,7 @@ fn start_local_node_session_once(
         },
         extra_runtime_args: Vec::new(),
         startup_delay_ms: options.startup_delay_ms,
+        restart_policy: AgentRestartPolicy::default(),
         command: options.command.clone(),
     });
 
@@ -4690,48 +4856,6 @@ pub fn reconcile_nodes(max_age_days: u64) -> anyhow::Result<NodeReconcileResult>
     })
 }
 
-pub fn rotate_capsule_key(sync: bool) -> anyhow::Result<CapsuleKeyRotationResult> {
-    let path = jarvis_capsule_key_path()?;
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent)?;
-    }
-    let rng = rand::SystemRandom::new();
-    let mut key = [0_u8; 32];
-    rand::SecureRandom::fill(&rng, &mut key)
-        .map_err(|_| anyhow!("failed to generate capsule key"))?;
-    atomic_write_bytes(&path, &key)?;
-    #[cfg(unix)]
-    {
-        use std::os::unix::fs::PermissionsExt;
-        fs::set_permissions(&path, fs::Permissions::from_mode(0o600))?;
-    }
-
-    let mut synced_nodes = Vec::new();
-    let mut failures = Vec::new();
-    if sync {
-        for manifest in load_manifests_by_kind(ResourceKind::Node, None)? {
-            let ResourceManifest::Node(node) = manifest else {
-                continue;
-            };
-            if node_is_local(&node) {
-                continue;
-            }
-            match sync_capsule_key_to_remote_node(&node) {
-                Ok(()) => synced_nodes.push(node.metadata.name),
-                Err(error) => failures.push(NodeFanoutFailure {
-                    node: node.metadata.name,
-                    error: error.to_string(),
-                }),
-            }
-        }
-    }
-    Ok(CapsuleKeyRotationResult {
-        key_path: path.display().to_string(),
-        synced_nodes,
-        failures,
-    })
-}
-
 pub fn migrate_session_to_node(
     namespace: &str,
     to_node: &str,
@@ -5055,21 +5179,26 @@ fn run_remote_sudo_command(
     })
 }
 
-pub fn attach_cluster_runtime_session(namespace: &str, agent: &str) -> anyhow::Result<bool> {
+pub fn attach_cluster_runtime_session(
+    namespace: &str,
+    agent: &str,
+    read_only: bool,
+) -> anyhow::Result<bool> {
     let Some(node) = remote_node_for_runtime_session(namespace)? else {
         return Ok(false);
     };
-    run_remote_runtime_command_interactive(
-        &node,
-        vec![
-            "jarvisctl".to_string(),
-            "attach".to_string(),
-            "--namespace".to_string(),
-            namespace.to_string(),
-            "--agent".to_string(),
-            agent.to_string(),
-        ],
-    )?;
+    let mut command = vec![
+        "jarvisctl".to_string(),
+        "attach".to_string(),
+        "--namespace".to_string(),
+        namespace.to_string(),
+        "--agent".to_string(),
+        agent.to_string(),
+    ];
+    if read_only {
+        command.push("--read-only".to_string());
+    }
+    run_remote_runtime_command_interactive(&node, command)?;
     Ok(true)
 }
 
@@ -6264,6 +6393,7 @@ fn auth_lease_name(runtime_namespace: &str) -> String {
 
 fn run_remote_codex_exec_visit(
     target: &str,
+    recipient: &ResourceEnvelope<NodeSpec>,
     options: &NodeVisitOptions,
     namespace: &str,
 ) -> anyhow::Result<NodeVisitResult> {
@@ -6329,7 +6459,7 @@ fn run_remote_codex_exec_visit(
         .with_context(|| format!("failed to start remote Codex visit on '{target}'"))?;
 
     {
-        let protected_prompt = protect_visit_capsule(&options.prompt)?;
+        let protected_prompt = protect_visit_capsule(&options.prompt, recipient)?;
         let stdin = child.stdin.as_mut().context("failed to open visit stdin")?;
         stdin
             .write_all(protected_prompt.as_bytes())
@@ -6389,7 +6519,7 @@ fn run_node_relay_visit(options: NodeVisitOptions) -> anyhow::Result<NodeVisitRe
     );
     let target = node_ssh_target(&from_node.spec)
         .ok_or_else(|| anyhow!("Node '{}' has no SSH target", from_node.metadata.name))?;
-    sync_capsule_key_to_remote_node(&from_node)?;
+    let from_node = exchange_capsule_identity(&from_node)?;
     let namespace = options.namespace.clone().unwrap_or_else(|| {
         format!(
             "visit-{}-to-{}-{}",
@@ -6412,7 +6542,7 @@ fn run_node_relay_visit(options: NodeVisitOptions) -> anyhow::Result<NodeVisitRe
         failure_class: None,
         retryable: None,
     })?;
-    let mut result = run_remote_jarvis_visit(&target, &options, &namespace)?;
+    let mut result = run_remote_jarvis_visit(&target, &from_node, &options, &namespace)?;
     let finished_at_epoch_ms = now_epoch_ms();
     result.from_node = Some(from_node.metadata.name.clone());
     result.archive_path = Some(
@@ -6450,6 +6580,7 @@ fn run_node_relay_visit(options: NodeVisitOptions) -> anyhow::Result<NodeVisitRe
 
 fn run_remote_jarvis_visit(
     target: &str,
+    recipient: &ResourceEnvelope<NodeSpec>,
     options: &NodeVisitOptions,
     namespace: &str,
 ) -> anyhow::Result<NodeVisitResult> {
@@ -6526,7 +6657,7 @@ fn run_remote_jarvis_visit(
         .spawn()
         .with_context(|| format!("failed to start relay visit on '{target}'"))?;
     {
-        let protected_prompt = protect_visit_capsule(&options.prompt)?;
+        let protected_prompt = protect_visit_capsule(&options.prompt, recipient)?;
         let stdin = child
             .stdin
             .as_mut()
@@ -6710,156 +6841,6 @@ fn jarvis_codex_dir() -> anyhow::Result<PathBuf> {
     Ok(PathBuf::from(home).join(".jarvis").join("codex"))
 }
 
-fn jarvis_capsule_key_path() -> anyhow::Result<PathBuf> {
-    Ok(jarvis_codex_dir()?.join("capsule.key"))
-}
-
-fn load_or_create_capsule_key() -> anyhow::Result<[u8; 32]> {
-    let path = jarvis_capsule_key_path()?;
-    if path.exists() {
-        let raw = fs::read(&path)
-            .with_context(|| format!("failed to read capsule key '{}'", path.display()))?;
-        ensure!(
-            raw.len() == 32,
-            "capsule key '{}' must be 32 bytes",
-            path.display()
-        );
-        let mut key = [0_u8; 32];
-        key.copy_from_slice(&raw);
-        return Ok(key);
-    }
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent)?;
-    }
-    let rng = rand::SystemRandom::new();
-    let mut key = [0_u8; 32];
-    rand::SecureRandom::fill(&rng, &mut key)
-        .map_err(|_| anyhow!("failed to generate capsule key"))?;
-    fs::write(&path, key)
-        .with_context(|| format!("failed to write capsule key '{}'", path.display()))?;
-    #[cfg(unix)]
-    {
-        use std::os::unix::fs::PermissionsExt;
-        fs::set_permissions(&path, fs::Permissions::from_mode(0o600))?;
-    }
-    Ok(key)
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-struct ProtectedCapsule {
-    version: u32,
-    algorithm: String,
-    nonce: String,
-    ciphertext: String,
-}
-
-pub fn protect_visit_capsule(prompt: &str) -> anyhow::Result<String> {
-    let key = load_or_create_capsule_key()?;
-    let unbound = aead::UnboundKey::new(&aead::CHACHA20_POLY1305, &key)
-        .map_err(|_| anyhow!("failed to initialize capsule key"))?;
-    let sealing_key = aead::LessSafeKey::new(unbound);
-    let rng = rand::SystemRandom::new();
-    let mut nonce_bytes = [0_u8; 12];
-    rand::SecureRandom::fill(&rng, &mut nonce_bytes)
-        .map_err(|_| anyhow!("failed to generate nonce"))?;
-    let nonce = aead::Nonce::assume_unique_for_key(nonce_bytes);
-    let mut in_out = prompt.as_bytes().to_vec();
-    sealing_key
-        .seal_in_place_append_tag(
-            nonce,
-            aead::Aad::from(b"jarvisctl-visit-capsule-v1"),
-            &mut in_out,
-        )
-        .map_err(|_| anyhow!("failed to encrypt visit capsule"))?;
-    let envelope = ProtectedCapsule {
-        version: 1,
-        algorithm: "CHACHA20-POLY1305".to_string(),
-        nonce: BASE64.encode(nonce_bytes),
-        ciphertext: BASE64.encode(in_out),
-    };
-    serde_json::to_string(&envelope).context("failed to encode protected capsule")
-}
-
-pub fn open_visit_capsule(raw: &str) -> anyhow::Result<String> {
-    let envelope: ProtectedCapsule =
-        serde_json::from_str(raw).context("failed to parse protected capsule")?;
-    ensure!(
-        envelope.version == 1,
-        "unsupported capsule version {}",
-        envelope.version
-    );
-    ensure!(
-        envelope.algorithm == "CHACHA20-POLY1305",
-        "unsupported capsule algorithm {}",
-        envelope.algorithm
-    );
-    let key = load_or_create_capsule_key()?;
-    let nonce_raw = BASE64
-        .decode(envelope.nonce.as_bytes())
-        .context("failed to decode capsule nonce")?;
-    ensure!(nonce_raw.len() == 12, "capsule nonce must be 12 bytes");
-    let mut nonce_bytes = [0_u8; 12];
-    nonce_bytes.copy_from_slice(&nonce_raw);
-    let nonce = aead::Nonce::assume_unique_for_key(nonce_bytes);
-    let mut in_out = BASE64
-        .decode(envelope.ciphertext.as_bytes())
-        .context("failed to decode capsule ciphertext")?;
-    let unbound = aead::UnboundKey::new(&aead::CHACHA20_POLY1305, &key)
-        .map_err(|_| anyhow!("failed to initialize capsule key"))?;
-    let opening_key = aead::LessSafeKey::new(unbound);
-    let plaintext = opening_key
-        .open_in_place(
-            nonce,
-            aead::Aad::from(b"jarvisctl-visit-capsule-v1"),
-            &mut in_out,
-        )
-        .map_err(|_| anyhow!("failed to authenticate or decrypt capsule"))?;
-    String::from_utf8(plaintext.to_vec()).context("capsule plaintext was not UTF-8")
-}
-
-fn sync_capsule_key_to_remote_node(node: &ResourceEnvelope<NodeSpec>) -> anyhow::Result<()> {
-    if node_is_local(node) {
-        let _ = load_or_create_capsule_key()?;
-        return Ok(());
-    }
-    let target = node_ssh_target(&node.spec)
-        .ok_or_else(|| anyhow!("Node '{}' has no SSH target", node.metadata.name))?;
-    let key_path = jarvis_capsule_key_path()?;
-    let _ = load_or_create_capsule_key()?;
-    let remote = format!("{target}:~/.jarvis/codex/capsule.key");
-    run_shell_probe(
-        Some(&target),
-        "set -eu; mkdir -p \"$HOME/.jarvis/codex\"; chmod 700 \"$HOME/.jarvis\" \"$HOME/.jarvis/codex\" 2>/dev/null || true",
-        "capsule key prepare",
-    )?;
-    let status = ProcessCommand::new("scp")
-        .args([
-            "-q",
-            key_path
-                .to_str()
-                .ok_or_else(|| anyhow!("capsule key path is not valid UTF-8"))?,
-            &remote,
-        ])
-        .status()
-        .with_context(|| {
-            format!(
-                "failed to copy capsule key to Node '{}'",
-                node.metadata.name
-            )
-        })?;
-    ensure!(
-        status.success(),
-        "capsule key copy to Node '{}' failed with {status}",
-        node.metadata.name
-    );
-    run_shell_probe(
-        Some(&target),
-        "chmod 600 \"$HOME/.jarvis/codex/capsule.key\"",
-        "capsule key permissions",
-    )?;
-    Ok(())
-}
-
 fn append_auth_audit_event(
     event: &str,
     node: &str,
@@ -8860,11 +8841,7 @@ fn resolve_worker_api_key(worker: &ResourceEnvelope<WorkerSpec>) -> anyhow::Resu
                 reference.name
             );
         };
-        return secret
-            .spec
-            .string_data
-            .get(&reference.key)
-            .cloned()
+        return secret_value(&secret, &reference.key)?
             .filter(|value| !value.trim().is_empty())
             .ok_or_else(|| {
                 anyhow!(
@@ -9446,8 +9423,20 @@ fn parse_manifest_documents(raw: &str) -> anyhow::Result<Vec<ResourceManifest>>
 }
 
 fn resolve_manifest_relative_paths(manifests: &mut [ResourceManifest], base_dir: &Path) {
-    let _ = manifests;
-    let _ = base_dir;
+    let base_dir = fs::canonicalize(base_dir).unwrap_or_else(|_| base_dir.to_path_buf());
+    for manifest in manifests {
+        let ResourceManifest::Secret(secret) = manifest else {
+            continue;
+        };
+        for source in secret.spec.value_from.values_mut() {
+            if let Some(path) = source.from_file.as_mut()
+                && !path.starts_with('~')
+                && Path::new(path.as_str()).is_relative()
+            {
+                *path = base_dir.join(path.as_str()).display().to_string();
+            }
+        }
+    }
 }
 
 fn parse_manifest_value(value: Value) -> anyhow::Result<ResourceManifest> {
@@ -9469,6 +9458,12 @@ fn parse_manifest_value(value: Value) -> anyhow::Result<ResourceManifest> {
             let mut manifest: ResourceEnvelope<NamespaceSpec> =
                 serde_yaml::from_value(value).context("failed to decode Namespace manifest")?;
             normalize_metadata(&mut manifest.metadata, true)?;
+            validate_request_policy(&manifest.spec.request_policy).with_context(|| {
+                format!(
+                    "Namespace '{}' has an invalid spec.request_policy",
+                    manifest.metadata.name
+                )
+            })?;
             manifest.api_version = API_VERSION.to_string();
             manifest.kind = "Namespace".to_string();
             Ok(ResourceManifest::Namespace(manifest))
@@ -9534,6 +9529,7 @@ fn parse_manifest_value(value: Value) -> anyhow::Result<ResourceManifest> {
             let mut manifest: ResourceEnvelope<SecretSpec> =
                 serde_yaml::from_value(value).context("failed to decode Secret manifest")?;
             normalize_metadata(&mut manifest.metadata, false)?;
+            validate_secret(&manifest)?;
             manifest.api_version = API_VERSION.to_string();
             manifest.kind = "Secret".to_string();
             Ok(ResourceManifest::Secret(manifest))
@@ -9661,6 +9657,12 @@ fn validate_deployment(manifest: &ResourceEnvelope<DeploymentSpec>) -> anyhow::R
             }
         }
     }
+    validate_template_restart_policy(
+        &manifest.spec.template,
+        manifest.spec.driver,
+        "Deployment",
+        &manifest.metadata.name,
+    )?;
     validate_template_bindings(
         &manifest.spec.template,
         "Deployment",
@@ -9695,6 +9697,12 @@ fn validate_replica_set(manifest: &ResourceEnvelope<ReplicaSetSpec>) -> anyhow::
         "ReplicaSet '{}' must set spec.template.task_note",
         manifest.metadata.name
     );
+    validate_template_restart_policy(
+        &manifest.spec.template,
+        manifest.spec.driver,
+        "ReplicaSet",
+        &manifest.metadata.name,
+    )?;
     validate_template_bindings(
         &manifest.spec.template,
         "ReplicaSet",
@@ -9745,6 +9753,23 @@ fn validate_volume(manifest: &ResourceEnvelope<VolumeSpec>) -> anyhow::Result<()
     Ok(())
 }
 
+/// Only the native PTY supervisor restarts agents, so a restart policy on an app-server
+/// template would be accepted and then silently ignored.
+fn validate_template_restart_policy(
+    template: &DeploymentTemplateSpec,
+    driver: Option<CodexRuntimeDriver>,
+    kind: &str,
+    name: &str,
+) -> anyhow::Result<()> {
+    ensure!(
+        template.restart_policy.is_never() || driver == Some(CodexRuntimeDriver::CliPty),
+        "{} '{}' sets spec.template.restartPolicy, which requires spec.driver: cli_pty",
+        kind,
+        name
+    );
+    Ok(())
+}
+
 fn validate_template_bindings(
     template: &DeploymentTemplateSpec,
     kind: &str,
@@ -9833,45 +9858,24 @@ fn reconcile_deployment(manifest: &ResourceEnvelope<DeploymentSpec>) -> anyhow::
     let desired_hash = deployment_template_hash(manifest, &namespace_defaults)?;
     let mut replica_sets = load_replica_sets_for_deployment(&control_namespace, &deployment_name)?;
     replica_sets.sort_by_key(|replica_set| replica_set.spec.revision);
-    let target_replica_set_name = if let Some(replica_set) = replica_sets
-        .iter()
-        .find(|replica_set| replica_set.spec.template_hash == desired_hash)
-    {
-        replica_set.metadata.name.clone()
-    } else if replica_sets.is_empty() {
-        let revision = next_deployment_revision(&replica_sets);
-        let replica_set = create_replica_set_manifest(
-            manifest,
-            &namespace_defaults,
-            revision,
-            manifest.spec.replicas,
-            &desired_hash,
-        );
-        save_manifest(&ResourceManifest::ReplicaSet(replica_set.clone()))?;
-        let name = replica_set.metadata.name.clone();
-        replica_sets.push(replica_set);
-        replica_sets.sort_by_key(|replica_set| replica_set.spec.revision);
-        name
-    } else if manifest.spec.paused {
-        current_deployment_replica_set(&replica_sets)
-            .map(|replica_set| replica_set.metadata.name.clone())
-            .ok_or_else(|| {
-                anyhow!(
-                    "deployment '{}/{}' has no active ReplicaSet while paused",
-                    control_namespace,
-                    deployment_name
-                )
-            })?
-    } else {
-        let revision = next_deployment_revision(&replica_sets);
-        let replica_set =
-            create_replica_set_manifest(manifest, &namespace_defaults, revision, 0, &desired_hash);
-        save_manifest(&ResourceManifest::ReplicaSet(replica_set.clone()))?;
-        let name = replica_set.metadata.name.clone();
-        replica_sets.push(replica_set);
-        replica_sets.sort_by_key(|replica_set| replica_set.spec.revision);
-        name
-    };
+    let target_replica_set_name =
+        match deployment_rollout_target(manifest, &replica_sets, &desired_hash)? {
+            RolloutTarget::Existing(name) => name,
+            RolloutTarget::Create { revision, replicas } => {
+                let replica_set = create_replica_set_manifest(
+                    manifest,
+                    &namespace_defaults,
+                    revision,
+                    replicas,
+                    &desired_hash,
+                );
+                save_manifest(&ResourceManifest::ReplicaSet(replica_set.clone()))?;
+                let name = replica_set.metadata.name.clone();
+                replica_sets.push(replica_set);
+                replica_sets.sort_by_key(|replica_set| replica_set.spec.revision);
+                name
+            }
+        };
 
     let current_sessions = collect_runtime_sessions()?;
     if !manifest.spec.paused {
@@ -9911,6 +9915,47 @@ fn reconcile_deployment(manifest: &ResourceEnvelope<DeploymentSpec>) -> anyhow::
     ))
 }
 
+/// ReplicaSet a Deployment rolls toward: the revision already carrying the desired template
+/// hash, or a new revision created with the given initial replicas.
+enum RolloutTarget {
+    Existing(String),
+    Create { revision: u64, replicas: usize },
+}
+
+fn deployment_rollout_target(
+    manifest: &ResourceEnvelope<DeploymentSpec>,
+    replica_sets: &[ResourceEnvelope<ReplicaSetSpec>],
+    desired_hash: &str,
+) -> anyhow::Result<RolloutTarget> {
+    if let Some(replica_set) = replica_sets
+        .iter()
+        .find(|replica_set| replica_set.spec.template_hash == desired_hash)
+    {
+        return Ok(RolloutTarget::Existing(replica_set.metadata.name.clone()));
+    }
+    if replica_sets.is_empty() {
+        return Ok(RolloutTarget::Create {
+            revision: next_deployment_revision(replica_sets),
+            replicas: manifest.spec.replicas,
+        });
+    }
+    if manifest.spec.paused {
+        return current_deployment_replica_set(replica_sets)
+            .map(|replica_set| RolloutTarget::Existing(replica_set.metadata.name.clone()))
+            .ok_or_else(|| {
+                anyhow!(
+                    "deployment '{}/{}' has no active ReplicaSet while paused",
+                    manifest.namespace_key(),
+                    manifest.metadata.name
+                )
+            });
+    }
+    Ok(RolloutTarget::Create {
+        revision: next_deployment_revision(replica_sets),
+        replicas: 0,
+    })
+}
+
 fn load_replica_sets_for_deployment(
     control_namespace: &str,
     deployment_name: &str,
@@ -10025,20 +10070,36 @@ fn prune_deployment_replica_sets(
     revision_history_limit: usize,
     replica_sets: &mut Vec<ResourceEnvelope<ReplicaSetSpec>>,
 ) -> anyhow::Result<()> {
-    replica_sets.sort_by_key(|replica_set| replica_set.spec.revision);
-    let mut inactive_names = replica_sets
+    for removed_name in replica_sets_to_prune(
+        active_replica_set_name,
+        revision_history_limit,
+        replica_sets,
+    ) {
+        delete_replica_set_resources(control_namespace, deployment_name, &removed_name)?;
+        replica_sets.retain(|replica_set| replica_set.metadata.name != removed_name);
+    }
+    Ok(())
+}
+
+/// Oldest scaled-down ReplicaSets beyond the Deployment's revision history limit.
+fn replica_sets_to_prune(
+    active_replica_set_name: &str,
+    revision_history_limit: usize,
+    replica_sets: &[ResourceEnvelope<ReplicaSetSpec>],
+) -> Vec<String> {
+    let mut inactive = replica_sets
         .iter()
         .filter(|replica_set| {
             replica_set.metadata.name != active_replica_set_name && replica_set.spec.replicas == 0
         })
-        .map(|replica_set| replica_set.metadata.name.clone())
         .collect::<Vec<_>>();
-    while inactive_names.len() > revision_history_limit {
-        let removed_name = inactive_names.remove(0);
-        delete_replica_set_resources(control_namespace, deployment_name, &removed_name)?;
-        replica_sets.retain(|replica_set| replica_set.metadata.name != removed_name);
-    }
-    Ok(())
+    inactive.sort_by_key(|replica_set| replica_set.spec.revision);
+    let excess = inactive.len().saturating_sub(revision_history_limit);
+    inactive
+        .into_iter()
+        .take(excess)
+        .map(|replica_set| replica_set.metadata.name.clone())
+        .collect()
 }
 
 fn delete_replica_set_resources(
@@ -10046,14 +10107,7 @@ fn delete_replica_set_resources(
     deployment_name: &str,
     replica_set_name: &str,
 ) -> anyhow::Result<()> {
-    for session in collect_runtime_sessions()?.into_iter().filter(|session| {
-        let Some(context) = session.context.as_ref() else {
-            return false;
-        };
-        context.control_namespace.as_deref() == Some(control_namespace)
-            && context.deployment.as_deref() == Some(deployment_name)
-            && context.labels.get("jarvisctl.io/replicaset") == Some(&replica_set_name.to_string())
-    }) {
+    for session in replica_set_sessions(control_namespace, deployment_name, replica_set_name)? {
         let _ = delete_runtime_session(&session);
     }
     delete_manifest_only(
@@ -10063,13 +10117,52 @@ fn delete_replica_set_resources(
     )
 }
 
+fn replica_set_sessions(
+    control_namespace: &str,
+    deployment_name: &str,
+    replica_set_name: &str,
+) -> anyhow::Result<Vec<NativeSessionMetadata>> {
+    Ok(collect_runtime_sessions()?
+        .into_iter()
+        .filter(|session| {
+            let Some(context) = session.context.as_ref() else {
+                return false;
+            };
+            context.control_namespace.as_deref() == Some(control_namespace)
+                && context.deployment.as_deref() == Some(deployment_name)
+                && context.labels.get("jarvisctl.io/replicaset")
+                    == Some(&replica_set_name.to_string())
+        })
+        .collect())
+}
+
 fn apply_deployment_strategy(
     manifest: &ResourceEnvelope<DeploymentSpec>,
     target_replica_set_name: &str,
     replica_sets: &mut [ResourceEnvelope<ReplicaSetSpec>],
     sessions: &[NativeSessionMetadata],
 ) -> anyhow::Result<()> {
-    let strategy = effective_deployment_strategy(manifest);
+    let before = replica_sets
+        .iter()
+        .map(|replica_set| (replica_set.metadata.name.clone(), replica_set.spec.replicas))
+        .collect::<BTreeMap<_, _>>();
+    plan_deployment_strategy(manifest, target_replica_set_name, replica_sets, sessions)?;
+    for replica_set in replica_sets.iter() {
+        if before.get(&replica_set.metadata.name) != Some(&replica_set.spec.replicas) {
+            save_manifest(&ResourceManifest::ReplicaSet(replica_set.clone()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Scale ReplicaSet replica counts toward the target in memory; callers decide whether to persist.
+fn plan_deployment_strategy(
+    manifest: &ResourceEnvelope<DeploymentSpec>,
+    target_replica_set_name: &str,
+    replica_sets: &mut [ResourceEnvelope<ReplicaSetSpec>],
+    sessions: &[NativeSessionMetadata],
+) -> anyhow::Result<()> {
+    let strategy = effective_deployment_strategy(manifest);
     match strategy.strategy_type {
         DeploymentStrategyType::Recreate => {
             scale_recreate_deployment(manifest, target_replica_set_name, replica_sets, sessions)
@@ -10097,10 +10190,7 @@ fn scale_recreate_deployment(
         if replica_set.metadata.name == target_replica_set_name {
             continue;
         }
-        if replica_set.spec.replicas != 0 {
-            replica_set.spec.replicas = 0;
-            save_manifest(&ResourceManifest::ReplicaSet(replica_set.clone()))?;
-        }
+        replica_set.spec.replicas = 0;
         if !replica_set_live_session_names(replica_set, sessions).is_empty() {
             old_live_sessions = true;
         }
@@ -10115,10 +10205,7 @@ fn scale_recreate_deployment(
     } else {
         manifest.spec.replicas
     };
-    if target_replica_set.spec.replicas != desired_replicas {
-        target_replica_set.spec.replicas = desired_replicas;
-        save_manifest(&ResourceManifest::ReplicaSet(target_replica_set.clone()))?;
-    }
+    target_replica_set.spec.replicas = desired_replicas;
     Ok(())
 }
 
@@ -10132,11 +10219,7 @@ fn scale_rolling_update_deployment(
     let desired = manifest.spec.replicas;
     if desired == 0 {
         for replica_set in replica_sets.iter_mut() {
-            if replica_set.spec.replicas == 0 {
-                continue;
-            }
             replica_set.spec.replicas = 0;
-            save_manifest(&ResourceManifest::ReplicaSet(replica_set.clone()))?;
         }
         return Ok(());
     }
@@ -10175,7 +10258,6 @@ fn scale_rolling_update_deployment(
             if add > 0 {
                 target_replica_set.spec.replicas += add;
                 total_spec += add;
-                save_manifest(&ResourceManifest::ReplicaSet(target_replica_set.clone()))?;
             }
         }
     }
@@ -10206,7 +10288,6 @@ fn scale_rolling_update_deployment(
         replica_set.spec.replicas -= remove;
         total_spec = total_spec.saturating_sub(remove);
         total_ready = total_ready.saturating_sub(removed_ready);
-        save_manifest(&ResourceManifest::ReplicaSet(replica_set.clone()))?;
     }
 
     if let Some(target_replica_set) = replica_sets
@@ -10219,7 +10300,6 @@ fn scale_rolling_update_deployment(
             let add = can_add.min(desired.saturating_sub(current_target));
             if add > 0 {
                 target_replica_set.spec.replicas += add;
-                save_manifest(&ResourceManifest::ReplicaSet(target_replica_set.clone()))?;
             }
         }
     }
@@ -10439,6 +10519,7 @@ fn launch_replica_set_replica(
         context_overlay,
         extra_runtime_args: volume_runtime_args(&volumes),
         startup_delay_ms,
+        restart_policy: replica_set.spec.template.restart_policy,
         command: replica_set.spec.template.command.clone(),
     })?;
     Ok(())
@@ -11175,6 +11256,11 @@ fn delete_manifest_only(
     Ok(())
 }
 
+/// Approval rules declared on a control-plane Namespace, read fresh for each server request.
+pub fn namespace_request_policy(control_namespace: &str) -> anyhow::Result<Vec<RequestPolicyRule>> {
+    Ok(load_namespace_defaults(control_namespace)?.request_policy)
+}
+
 fn load_namespace_defaults(control_namespace: &str) -> anyhow::Result<NamespaceSpec> {
     match load_manifest(ResourceKind::Namespace, control_namespace, None) {
         Ok(ResourceManifest::Namespace(namespace)) => Ok(namespace.spec),
@@ -11319,14 +11405,12 @@ fn load_secret_values(
             name
         );
         let data = if let Some(prefix) = env_binding_prefix(reference) {
-            secret
-                .spec
-                .string_data
+            secret_values(&secret)?
                 .into_iter()
                 .map(|(key, value)| (format!("{}{}", prefix, key), value))
                 .collect::<BTreeMap<_, _>>()
         } else {
-            secret.spec.string_data
+            secret_values(&secret)?
         };
         let key = env_binding_prefix(reference)
             .map(|prefix| format!("{}:{}", name, prefix))
@@ -11956,6 +12040,207 @@ fn collect_remote_runtime_sessions() -> anyhow::Result<Vec<NativeSessionMetadata
     Ok(sessions)
 }
 
+/// Multiplexes runtime events from every local session and, unless `local_only`, from every
+/// registered remote node. Remote nodes run `jarvisctl events --cluster --local-only` over SSH.
+pub fn stream_cluster_events(
+    kinds: &[RuntimeEventKind],
+    follow: bool,
+    local_only: bool,
+    mut sink: impl FnMut(RuntimeEvent) -> anyhow::Result<()>,
+) -> anyhow::Result<()> {
+    let (sender, receiver) = mpsc::channel();
+    let local_node = local_node_name().ok();
+    let local_kinds = kinds.to_vec();
+    let local_sender = sender.clone();
+    thread::spawn(move || {
+        watch_local_session_events(local_node, &local_kinds, follow, local_sender)
+    });
+    if !local_only {
+        for node in remote_nodes()? {
+            let Some(target) = node_ssh_target(&node.spec) else {
+                continue;
+            };
+            let kinds = kinds.to_vec();
+            let sender = sender.clone();
+            thread::spawn(move || {
+                watch_remote_node_events(&node.metadata.name, &target, &kinds, follow, sender)
+            });
+        }
+    }
+    drop(sender);
+
+    if follow {
+        for event in receiver {
+            sink(event)?;
+        }
+        return Ok(());
+    }
+    let mut events = receiver.into_iter().collect::<Vec<_>>();
+    events.sort_by_key(|event| event.timestamp_epoch_ms);
+    for event in events {
+        sink(event)?;
+    }
+    Ok(())
+}
+
+fn watch_local_session_events(
+    node: Option<String>,
+    kinds: &[RuntimeEventKind],
+    follow: bool,
+    sender: mpsc::Sender<RuntimeEvent>,
+) {
+    let wants = |kind| kinds.is_empty() || kinds.contains(&kind);
+    let tagged = move |event: RuntimeEvent| tag_event_node(event, node.as_deref());
+    let mut watching = BTreeSet::new();
+    let mut initial_scan = true;
+    loop {
+        let sessions = crate::runtime::collect_runtime_sessions().unwrap_or_default();
+        let diff = diff_runtime_sessions(&watching, &sessions, initial_scan);
+        for event in diff.events {
+            if wants(event.kind) && sender.send(tagged(event)).is_err() {
+                return;
+            }
+        }
+        for session in diff.started {
+            let namespace = session.namespace.clone();
+            let kinds = kinds.to_vec();
+            let sender = sender.clone();
+            let tagged = tagged.clone();
+            let stream = move || {
+                let _ =
+                    crate::runtime::stream_runtime_events(&namespace, &kinds, follow, |event| {
+                        sender.send(tagged(event)).map_err(anyhow::Error::from)
+                    });
+            };
+            if follow {
+                thread::spawn(stream);
+            } else {
+                stream();
+            }
+        }
+        watching = diff.current;
+        if !follow {
+            return;
+        }
+        initial_scan = false;
+        thread::sleep(CLUSTER_EVENT_POLL_INTERVAL);
+    }
+}
+
+/// What changed between two scans of the local runtime sessions.
+struct RuntimeSessionDiff<'a> {
+    /// Sessions that need an event stream, including every session on the initial scan.
+    started: Vec<&'a NativeSessionMetadata>,
+    /// SessionStarted/SessionExited events in scan order; the initial scan emits none.
+    events: Vec<RuntimeEvent>,
+    current: BTreeSet<(String, u128)>,
+}
+
+/// Sessions are keyed by namespace and creation time, so a namespace that was closed and
+/// relaunched between scans reports an exit followed by a start.
+fn diff_runtime_sessions<'a>(
+    watching: &BTreeSet<(String, u128)>,
+    sessions: &'a [NativeSessionMetadata],
+    initial_scan: bool,
+) -> RuntimeSessionDiff<'a> {
+    let current = sessions
+        .iter()
+        .map(|session| (session.namespace.clone(), session.created_at_epoch_ms))
+        .collect::<BTreeSet<_>>();
+    let mut events = watching
+        .difference(&current)
+        .map(|(namespace, _)| RuntimeEvent::new(RuntimeEventKind::SessionExited, namespace))
+        .collect::<Vec<_>>();
+    let started = sessions
+        .iter()
+        .filter(|session| {
+            !watching.contains(&(session.namespace.clone(), session.created_at_epoch_ms))
+        })
+        .collect::<Vec<_>>();
+    if !initial_scan {
+        events.extend(started.iter().map(|session| RuntimeEvent {
+            detail: Some(session.backend.clone()),
+            ..RuntimeEvent::new(RuntimeEventKind::SessionStarted, &session.namespace)
+        }));
+    }
+    RuntimeSessionDiff {
+        started,
+        events,
+        current,
+    }
+}
+
+/// Stamps the node an event was collected from, keeping the event's own node when the
+/// collector does not know its name.
+fn tag_event_node(event: RuntimeEvent, node: Option<&str>) -> RuntimeEvent {
+    RuntimeEvent {
+        node: node.map(ToOwned::to_owned).or(event.node),
+        ..event
+    }
+}
+
+fn watch_remote_node_events(
+    node: &str,
+    target: &str,
+    kinds: &[RuntimeEventKind],
+    follow: bool,
+    sender: mpsc::Sender<RuntimeEvent>,
+) {
+    let mut remote_command = "jarvisctl events --cluster --local-only --json".to_string();
+    if follow {
+        remote_command.push_str(" --follow");
+    }
+    for kind in kinds {
+        if let Some(value) = kind.to_possible_value() {
+            remote_command.push_str(&format!(" --kind {}", value.get_name()));
+        }
+    }
+    loop {
+        let mut command = if follow {
+            ProcessCommand::new("ssh")
+        } else {
+            let mut command = ProcessCommand::new("timeout");
+            command.args(["--kill-after=5s", "25s", "ssh"]);
+            command
+        };
+        command
+            .args([
+                "-o",
+                "BatchMode=yes",
+                "-o",
+                "ConnectTimeout=5",
+                "-o",
+                "ServerAliveInterval=15",
+                target,
+                &remote_command,
+            ])
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null());
+        if let Ok(mut child) = command.spawn() {
+            let stdout = child.stdout.take().map(BufReader::new);
+            for line in stdout.into_iter().flat_map(|stdout| stdout.lines()) {
+                let Ok(line) = line else {
+                    break;
+                };
+                let Ok(event) = serde_json::from_str::<RuntimeEvent>(&line) else {
+                    continue;
+                };
+                if sender.send(tag_event_node(event, Some(node))).is_err() {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return;
+                }
+            }
+            let _ = child.wait();
+        }
+        if !follow {
+            return;
+        }
+        thread::sleep(CLUSTER_EVENT_RECONNECT_DELAY);
+    }
+}
+
 fn remote_nodes() -> anyhow::Result<Vec<ResourceEnvelope<NodeSpec>>> {
     Ok(load_manifests_by_kind(ResourceKind::Node, None)?
         .into_iter()
@@ -12444,7 +12729,11 @@ mod tests {
                     pid: 42,
                     running: true,
                     exit_code: None,
+                    restarts: 0,
+                    last_exit_code: None,
+                    idle: false,
                 }],
+                clients: Vec::new(),
             },
         );
         save_manifest(&ResourceManifest::Service(ResourceEnvelope {
@@ -12877,6 +13166,401 @@ mod tests {
         );
     }
 
+    #[test]
+    fn apply_dry_run_plans_rollout_without_persisting() {
+        let _home_guard = home_env_lock().lock().unwrap();
+        let temp_home = TempHomeGuard::new("jarvisctl-apply-dry-run");
+
+        let deployment = ResourceEnvelope {
+            api_version: API_VERSION.to_string(),
+            kind: "Deployment".to_string(),
+            metadata: ResourceMetadata {
+                name: "sender".to_string(),
+                namespace: Some("mesh".to_string()),
+                labels: BTreeMap::new(),
+                annotations: BTreeMap::new(),
+            },
+            spec: DeploymentSpec {
+                replicas: 2,
+                agents: 1,
+                revision_history_limit: default_revision_history_limit(),
+                paused: false,
+                progress_deadline_seconds: default_progress_deadline_seconds(),
+                restart_token: None,
+                strategy: Some(DeploymentStrategy::default()),
+                driver: Some(CodexRuntimeDriver::AppServer),
+                startup_delay_ms: Some(0),
+                template: DeploymentTemplateSpec {
+                    task_note: "/tmp/sender.md".to_string(),
+                    ..DeploymentTemplateSpec::default()
+                },
+            },
+        };
+        let desired_hash =
+            deployment_template_hash(&deployment, &NamespaceSpec::default()).unwrap();
+        let replica_set = create_replica_set_manifest(
+            &deployment,
+            &NamespaceSpec::default(),
+            1,
+            2,
+            &desired_hash,
+        );
+        save_manifest(&ResourceManifest::Deployment(deployment.clone())).unwrap();
+        save_manifest(&ResourceManifest::ReplicaSet(replica_set.clone())).unwrap();
+
+        let mut bumped = deployment.clone();
+        bumped.spec.restart_token = Some("typo".to_string());
+        let manifest_path = temp_home.root.join("sender.yaml");
+        write_text_file(
+            &manifest_path,
+            &serde_yaml::to_string(&ResourceManifest::Deployment(bumped)).unwrap(),
+        );
+
+        let report = dry_run_apply(std::slice::from_ref(&manifest_path), None).unwrap();
+        assert_eq!(report.resources.len(), 1);
+        assert_eq!(
+            report.resources[0].action,
+            dry_run::ManifestDiffAction::Configured
+        );
+        assert_eq!(report.resources[0].changes[0].path, "spec.restartToken");
+
+        let plan = &report.deployments[0];
+        assert!(plan.new_revision);
+        assert_eq!(plan.target_revision, 2);
+        let replicas = plan
+            .replica_sets
+            .iter()
+            .map(|replica_set| {
+                (
+                    replica_set.revision,
+                    replica_set.replicas_before,
+                    replica_set.replicas_after,
+                    replica_set.start.len(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(replicas, vec![(1, 2, 0, 0), (2, 0, 2, 2)]);
+
+        let ResourceManifest::Deployment(stored) =
+            load_manifest(ResourceKind::Deployment, "sender", Some("mesh")).unwrap()
+        else {
+            panic!("stored manifest is not a Deployment");
+        };
+        assert_eq!(stored.spec.restart_token, None);
+        assert_eq!(
+            load_replica_sets_for_deployment("mesh", "sender")
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn cluster_events_diff_sessions_and_tag_nodes() {
+        let session = |namespace: &str, created_at_epoch_ms: u128| NativeSessionMetadata {
+            namespace: namespace.to_string(),
+            backend: "native".to_string(),
+            created_at_epoch_ms,
+            working_directory: None,
+            shell_command: "codex".to_string(),
+            context: None,
+            agents: Vec::new(),
+            clients: Vec::new(),
+        };
+        let summarize = |diff: &RuntimeSessionDiff<'_>| {
+            (
+                diff.started
+                    .iter()
+                    .map(|session| session.namespace.clone())
+                    .collect::<Vec<_>>(),
+                diff.events
+                    .iter()
+                    .map(|event| (event.kind, event.namespace.clone()))
+                    .collect::<Vec<_>>(),
+            )
+        };
+
+        let first = vec![session("alpha", 1), session("beta", 1)];
+        let initial = diff_runtime_sessions(&BTreeSet::new(), &first, true);
+        assert_eq!(
+            summarize(&initial),
+            (vec!["alpha".to_string(), "beta".to_string()], Vec::new())
+        );
+
+        let second = vec![session("alpha", 1), session("beta", 2), session("gamma", 3)];
+        let next = diff_runtime_sessions(&initial.current, &second, false);
+        assert_eq!(
+            summarize(&next),
+            (
+                vec!["beta".to_string(), "gamma".to_string()],
+                vec![
+                    (RuntimeEventKind::SessionExited, "beta".to_string()),
+                    (RuntimeEventKind::SessionStarted, "beta".to_string()),
+                    (RuntimeEventKind::SessionStarted, "gamma".to_string()),
+                ]
+            )
+        );
+        assert_eq!(next.events[1].detail.as_deref(), Some("native"));
+
+        let closed = diff_runtime_sessions(&next.current, &second[..1], false);
+        assert_eq!(
+            summarize(&closed),
+            (
+                Vec::new(),
+                vec![
+                    (RuntimeEventKind::SessionExited, "beta".to_string()),
+                    (RuntimeEventKind::SessionExited, "gamma".to_string()),
+                ]
+            )
+        );
+
+        let remote = RuntimeEvent {
+            node: Some("archiebald".to_string()),
+            ..RuntimeEvent::new(RuntimeEventKind::SessionStarted, "alpha")
+        };
+        assert_eq!(
+            tag_event_node(remote.clone(), Some("relay"))
+                .node
+                .as_deref(),
+            Some("relay")
+        );
+        assert_eq!(
+            tag_event_node(remote, None).node.as_deref(),
+            Some("archiebald")
+        );
+    }
+
+    #[test]
+    fn deployment_restart_policy_requires_cli_pty_driver() {
+        let manifest = |driver: &str| {
+            format!(
+                r#"apiVersion: jarvisctl.io/v1alpha1
+kind: Deployment
+metadata:
+  name: planner
+  namespace: mesh
+spec:
+  replicas: 1
+  agents: 1
+{driver}  template:
+    task_note: /tmp/planner.md
+    restartPolicy:
+      policy: on-failure
+"#
+            )
+        };
+
+        for driver in ["", "  driver: app_server\n"] {
+            let error = parse_manifest_documents(&manifest(driver))
+                .unwrap_err()
+                .to_string();
+            assert!(error.contains("requires spec.driver: cli_pty"), "{error}");
+        }
+        let parsed = parse_manifest_documents(&manifest("  driver: cli_pty\n")).unwrap();
+        let ResourceManifest::Deployment(deployment) = &parsed[0] else {
+            panic!("expected a Deployment");
+        };
+        assert!(!deployment.spec.template.restart_policy.is_never());
+    }
+
+    #[test]
+    fn delete_cascades_to_replica_sets_and_guards_namespaces() {
+        let _home_guard = home_env_lock().lock().unwrap();
+        let temp_home = TempHomeGuard::new("jarvisctl-delete-cascade");
+
+        let manifests = parse_manifest_documents(
+            r#"apiVersion: jarvisctl.io/v1alpha1
+kind: Namespace
+metadata:
+  name: mesh
+spec: {}
+---
+apiVersion: jarvisctl.io/v1alpha1
+kind: Deployment
+metadata:
+  name: planner
+  namespace: mesh
+spec:
+  replicas: 0
+  agents: 1
+  template:
+    task_note: /tmp/planner.md
+---
+apiVersion: jarvisctl.io/v1alpha1
+kind: Deployment
+metadata:
+  name: sender
+  namespace: mesh
+spec:
+  replicas: 0
+  agents: 1
+  template:
+    task_note: /tmp/sender.md
+"#,
+        )
+        .unwrap();
+        for manifest in &manifests {
+            save_manifest(manifest).unwrap();
+            if let ResourceManifest::Deployment(deployment) = manifest {
+                let hash = deployment_template_hash(deployment, &NamespaceSpec::default()).unwrap();
+                let replica_set =
+                    create_replica_set_manifest(deployment, &NamespaceSpec::default(), 1, 0, &hash);
+                save_manifest(&ResourceManifest::ReplicaSet(replica_set)).unwrap();
+            }
+        }
+
+        let orphan = DeleteOptions {
+            cascade: DeleteCascade::Orphan,
+            force: false,
+        };
+        delete_resource("deployment/planner", Some("mesh"), orphan).unwrap();
+        assert_eq!(
+            load_replica_sets_for_deployment("mesh", "planner")
+                .unwrap()
+                .len(),
+            1
+        );
+
+        delete_resource("deployments/sender", Some("mesh"), DeleteOptions::default()).unwrap();
+        assert!(
+            load_replica_sets_for_deployment("mesh", "sender")
+                .unwrap()
+                .is_empty()
+        );
+        assert!(load_manifest(ResourceKind::Deployment, "sender", Some("mesh")).is_err());
+
+        // A codex-app runtime for a "stuck" ReplicaSet whose control socket refuses to stop.
+        let stuck = parse_manifest_documents(
+            "apiVersion: jarvisctl.io/v1alpha1\nkind: Deployment\nmetadata:\n  name: stuck\n  namespace: mesh\nspec:\n  replicas: 0\n  agents: 1\n  template:\n    task_note: /tmp/stuck.md\n",
+        )
+        .unwrap();
+        let ResourceManifest::Deployment(stuck) = &stuck[0] else {
+            panic!("expected a Deployment");
+        };
+        save_manifest(&ResourceManifest::Deployment(stuck.clone())).unwrap();
+        let hash = deployment_template_hash(stuck, &NamespaceSpec::default()).unwrap();
+        let stuck_rs = create_replica_set_manifest(stuck, &NamespaceSpec::default(), 1, 0, &hash);
+        save_manifest(&ResourceManifest::ReplicaSet(stuck_rs.clone())).unwrap();
+        let session_dir = temp_home
+            .root
+            .join(".jarvis/codex-app/sessions/mesh-stuck-0");
+        fs::create_dir_all(&session_dir).unwrap();
+        let session = NativeSessionMetadata {
+            namespace: "mesh-stuck-0".to_string(),
+            backend: "codex-app".to_string(),
+            created_at_epoch_ms: 1,
+            working_directory: None,
+            shell_command: "app-server".to_string(),
+            context: Some(RuntimeContextMetadata {
+                control_namespace: Some("mesh".to_string()),
+                deployment: Some("stuck".to_string()),
+                labels: BTreeMap::from([(
+                    "jarvisctl.io/replicaset".to_string(),
+                    stuck_rs.metadata.name.clone(),
+                )]),
+                ..RuntimeContextMetadata::default()
+            }),
+            agents: vec![NativeAgentMetadata {
+                name: "agent0".to_string(),
+                pid: 1,
+                running: true,
+                exit_code: None,
+                restarts: 0,
+                last_exit_code: None,
+                idle: false,
+            }],
+            clients: Vec::new(),
+        };
+        fs::write(
+            session_dir.join("metadata.json"),
+            serde_json::to_string(&session).unwrap(),
+        )
+        .unwrap();
+        let listener =
+            std::os::unix::net::UnixListener::bind(session_dir.join("control.sock")).unwrap();
+        thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let mut request = String::new();
+                let _ = BufReader::new(&stream).read_line(&mut request);
+                let _ = writeln!(
+                    stream,
+                    r#"{{"type":"error","payload":{{"message":"runtime is busy"}}}}"#
+                );
+            }
+        });
+
+        let foreground = DeleteOptions {
+            cascade: DeleteCascade::Foreground,
+            force: false,
+        };
+        let kept = delete_resource("deployment/stuck", Some("mesh"), foreground)
+            .unwrap_err()
+            .to_string();
+        assert!(kept.contains("Deployment mesh/stuck was kept"), "{kept}");
+        assert!(load_manifest(ResourceKind::Deployment, "stuck", Some("mesh")).is_ok());
+        assert_eq!(
+            load_replica_sets_for_deployment("mesh", "stuck")
+                .unwrap()
+                .len(),
+            1
+        );
+
+        let reported = delete_resource("deployment/stuck", Some("mesh"), DeleteOptions::default())
+            .unwrap()
+            .join("\n");
+        assert!(reported.contains("failed to clean up"), "{reported}");
+        assert!(load_manifest(ResourceKind::Deployment, "stuck", Some("mesh")).is_err());
+        assert_eq!(
+            load_replica_sets_for_deployment("mesh", "stuck")
+                .unwrap()
+                .len(),
+            1
+        );
+        fs::remove_dir_all(&session_dir).unwrap();
+
+        let declared = temp_home.root.join("declared.yaml");
+        fs::write(
+            &declared,
+            "apiVersion: jarvisctl.io/v1alpha1\nkind: Namespace\nmetadata:\n  name: mesh\nspec: {}\n---\napiVersion: jarvisctl.io/v1alpha1\nkind: ConfigMap\nmetadata:\n  name: settings\n  namespace: mesh\nspec:\n  data:\n    MODE: test\n",
+        )
+        .unwrap();
+        save_manifest(
+            &parse_manifest_documents(&fs::read_to_string(&declared).unwrap()).unwrap()[1],
+        )
+        .unwrap();
+        let partial =
+            delete_declared_resources(&[declared], None, DeleteOptions::default()).unwrap_err();
+        assert!(
+            format!("{partial:#}").contains("already done: deleted ConfigMap mesh/settings"),
+            "{partial:#}"
+        );
+        assert!(load_manifest(ResourceKind::ConfigMap, "settings", Some("mesh")).is_err());
+
+        let blocked = delete_resource("namespace/mesh", None, DeleteOptions::default())
+            .unwrap_err()
+            .to_string();
+        assert!(
+            blocked.contains("ReplicaSet mesh/planner-rs-0001"),
+            "{blocked}"
+        );
+        delete_resource(
+            "namespace/mesh",
+            None,
+            DeleteOptions {
+                cascade: DeleteCascade::Orphan,
+                force: true,
+            },
+        )
+        .unwrap();
+        assert!(load_manifest(ResourceKind::Namespace, "mesh", None).is_err());
+        assert!(
+            load_replica_sets_for_deployment("mesh", "planner")
+                .unwrap()
+                .is_empty()
+        );
+        assert!(delete_resource("deployment/missing", Some("mesh"), orphan).is_err());
+    }
+
     #[test]
     fn kubernetes_compiler_renders_codex_runtime_deployment_and_service() {
         let _home_guard = home_env_lock().lock().unwrap();
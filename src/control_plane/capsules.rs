@@ -0,0 +1,718 @@
+use super::*;
+use ring::signature::KeyPair as _;
+use ring::{hkdf, signature};
+use x25519_dalek::{PublicKey as X25519PublicKey, StaticSecret};
+
+const CAPSULE_V2_ALGORITHM: &str = "X25519-HKDF-SHA256-CHACHA20-POLY1305+ED25519";
+const CAPSULE_V2_CONTEXT: &[u8] = b"jarvisctl-visit-capsule-v2";
+/// Capsules are opened as soon as they land on the recipient, so a short lifetime is enough
+/// and keeps the replay cache small.
+const CAPSULE_TTL_MS: u64 = 15 * 60 * 1000;
+const CAPSULE_MAX_TTL_MS: u64 = 60 * 60 * 1000;
+const CAPSULE_CLOCK_SKEW_MS: u64 = 5 * 60 * 1000;
+
+/// This node's capsule keypairs: X25519 to receive capsules, Ed25519 to sign the ones it sends.
+#[derive(Debug, Serialize, Deserialize)]
+struct CapsuleIdentity {
+    #[serde(rename = "encryptionSecretKey")]
+    encryption_secret_key: String,
+    #[serde(rename = "signingKeyPkcs8")]
+    signing_key_pkcs8: String,
+    #[serde(rename = "createdAtEpochMs")]
+    created_at_epoch_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapsuleIdentityPublic {
+    pub node: String,
+    pub encryption_public_key: String,
+    pub signing_public_key: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub trusted: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CapsuleHeader {
+    version: u32,
+    algorithm: String,
+    id: String,
+    sender: String,
+    #[serde(rename = "senderKey")]
+    sender_key: String,
+    recipient: String,
+    #[serde(rename = "recipientKey")]
+    recipient_key: String,
+    #[serde(rename = "ephemeralKey")]
+    ephemeral_key: String,
+    nonce: String,
+    #[serde(rename = "createdAtEpochMs")]
+    created_at_epoch_ms: u64,
+    #[serde(rename = "expiresAtEpochMs")]
+    expires_at_epoch_ms: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SignedCapsule {
+    header: CapsuleHeader,
+    ciphertext: String,
+    signature: String,
+}
+
+/// Pre-v2 envelope sealed with the shared `capsule.key`; only opened while that key is still
+/// present so operators can finish migrating every node.
+#[derive(Debug, Serialize, Deserialize)]
+struct LegacyProtectedCapsule {
+    version: u32,
+    algorithm: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+fn capsule_identity_path() -> anyhow::Result<PathBuf> {
+    Ok(jarvis_codex_dir()?.join("capsule-identity.json"))
+}
+
+fn capsule_trust_path() -> anyhow::Result<PathBuf> {
+    Ok(jarvis_codex_dir()?.join("capsule-trust.json"))
+}
+
+fn capsule_replay_cache_path() -> anyhow::Result<PathBuf> {
+    Ok(jarvis_codex_dir()?.join("capsule-replay.json"))
+}
+
+fn legacy_capsule_key_path() -> anyhow::Result<PathBuf> {
+    Ok(jarvis_codex_dir()?.join("capsule.key"))
+}
+
+fn write_private_file(path: &Path, raw: &[u8]) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create '{}'", parent.display()))?;
+    }
+    atomic_write_private_bytes(path, raw)
+}
+
+fn generate_capsule_identity() -> anyhow::Result<CapsuleIdentity> {
+    let rng = rand::SystemRandom::new();
+    let mut secret = [0_u8; 32];
+    rand::SecureRandom::fill(&rng, &mut secret)
+        .map_err(|_| anyhow!("failed to generate capsule encryption key"))?;
+    let pkcs8 = signature::Ed25519KeyPair::generate_pkcs8(&rng)
+        .map_err(|_| anyhow!("failed to generate capsule signing key"))?;
+    Ok(CapsuleIdentity {
+        encryption_secret_key: BASE64.encode(secret),
+        signing_key_pkcs8: BASE64.encode(pkcs8.as_ref()),
+        created_at_epoch_ms: now_epoch_ms() as u64,
+    })
+}
+
+fn save_capsule_identity(identity: &CapsuleIdentity) -> anyhow::Result<PathBuf> {
+    let path = capsule_identity_path()?;
+    write_private_file(&path, &serde_json::to_vec_pretty(identity)?)?;
+    Ok(path)
+}
+
+fn load_or_create_capsule_identity() -> anyhow::Result<CapsuleIdentity> {
+    let path = capsule_identity_path()?;
+    if path.exists() {
+        let raw = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read capsule identity '{}'", path.display()))?;
+        return serde_json::from_str(&raw)
+            .with_context(|| format!("failed to parse capsule identity '{}'", path.display()));
+    }
+    let identity = generate_capsule_identity()?;
+    save_capsule_identity(&identity)?;
+    Ok(identity)
+}
+
+impl CapsuleIdentity {
+    fn encryption_secret(&self) -> anyhow::Result<StaticSecret> {
+        Ok(StaticSecret::from(decode_key32(
+            &self.encryption_secret_key,
+            "capsule encryption key",
+        )?))
+    }
+
+    fn signing_key(&self) -> anyhow::Result<signature::Ed25519KeyPair> {
+        let pkcs8 = BASE64
+            .decode(self.signing_key_pkcs8.as_bytes())
+            .context("failed to decode capsule signing key")?;
+        signature::Ed25519KeyPair::from_pkcs8(&pkcs8)
+            .map_err(|_| anyhow!("capsule signing key is not a valid Ed25519 PKCS#8 key"))
+    }
+
+    fn encryption_public_key(&self) -> anyhow::Result<String> {
+        Ok(BASE64.encode(X25519PublicKey::from(&self.encryption_secret()?).as_bytes()))
+    }
+
+    fn signing_public_key(&self) -> anyhow::Result<String> {
+        Ok(BASE64.encode(self.signing_key()?.public_key().as_ref()))
+    }
+}
+
+fn decode_key32(raw: &str, label: &str) -> anyhow::Result<[u8; 32]> {
+    let bytes = BASE64
+        .decode(raw.trim().as_bytes())
+        .with_context(|| format!("failed to decode {label}"))?;
+    bytes
+        .try_into()
+        .map_err(|_| anyhow!("{label} must be 32 bytes"))
+}
+
+fn load_capsule_trust() -> anyhow::Result<BTreeMap<String, String>> {
+    let path = capsule_trust_path()?;
+    if !path.exists() {
+        return Ok(BTreeMap::new());
+    }
+    let raw = fs::read_to_string(&path)
+        .with_context(|| format!("failed to read capsule trust '{}'", path.display()))?;
+    serde_json::from_str(&raw)
+        .with_context(|| format!("failed to parse capsule trust '{}'", path.display()))
+}
+
+pub(super) fn local_capsule_public_keys() -> anyhow::Result<(String, String)> {
+    let identity = load_or_create_capsule_identity()?;
+    Ok((
+        identity.encryption_public_key()?,
+        identity.signing_public_key()?,
+    ))
+}
+
+/// Print this node's public capsule keys, first recording any `NAME=SIGNING_KEY` senders the
+/// caller vouches for. Runs over SSH during identity exchange, so SSH access is what authorizes
+/// adding a trusted sender.
+pub fn capsule_identity(trust: &[String]) -> anyhow::Result<CapsuleIdentityPublic> {
+    let identity = load_or_create_capsule_identity()?;
+    let mut trusted = Vec::new();
+    if !trust.is_empty() {
+        let mut store = load_capsule_trust()?;
+        for entry in trust {
+            let (name, key) = entry
+                .split_once('=')
+                .ok_or_else(|| anyhow!("trusted sender '{entry}' must be NAME=SIGNING_KEY"))?;
+            let name = name.trim();
+            ensure!(!name.is_empty(), "trusted sender '{entry}' has no name");
+            decode_key32(key, "trusted sender signing key")?;
+            store.insert(name.to_string(), key.trim().to_string());
+            trusted.push(name.to_string());
+        }
+        write_private_file(&capsule_trust_path()?, &serde_json::to_vec_pretty(&store)?)?;
+    }
+    Ok(CapsuleIdentityPublic {
+        node: local_node_name()?,
+        encryption_public_key: identity.encryption_public_key()?,
+        signing_public_key: identity.signing_public_key()?,
+        trusted,
+    })
+}
+
+/// Swap keys with a remote node over SSH: it learns our signing key and we record its public
+/// keys on the Node manifest. Returns the Node as updated.
+pub(super) fn exchange_capsule_identity(
+    node: &ResourceEnvelope<NodeSpec>,
+) -> anyhow::Result<ResourceEnvelope<NodeSpec>> {
+    let identity = load_or_create_capsule_identity()?;
+    let target = node_ssh_target(&node.spec)
+        .ok_or_else(|| anyhow!("Node '{}' has no SSH target", node.metadata.name))?;
+    let script = shell_words::join([
+        "jarvisctl".to_string(),
+        "capsule-identity".to_string(),
+        "--trust".to_string(),
+        format!("{}={}", local_node_name()?, identity.signing_public_key()?),
+        "--output".to_string(),
+        "json".to_string(),
+    ]);
+    let raw = run_shell_probe(Some(&target), &script, "capsule identity exchange").with_context(
+        || {
+            format!(
+                "failed to exchange capsule keys with Node '{}'; it may need a newer jarvisctl",
+                node.metadata.name
+            )
+        },
+    )?;
+    let remote: CapsuleIdentityPublic =
+        serde_json::from_str(raw.trim()).context("failed to parse remote capsule identity")?;
+    decode_key32(
+        &remote.encryption_public_key,
+        "remote capsule encryption key",
+    )?;
+    decode_key32(&remote.signing_public_key, "remote capsule signing key")?;
+
+    let mut node = node.clone();
+    let changed = node.spec.capsule_public_key.as_deref()
+        != Some(remote.encryption_public_key.as_str())
+        || node.spec.capsule_signing_key.as_deref() != Some(remote.signing_public_key.as_str());
+    if changed {
+        let previous = node.spec.capsule_public_key.is_some();
+        node.spec.capsule_public_key = Some(remote.encryption_public_key);
+        node.spec.capsule_signing_key = Some(remote.signing_public_key);
+        save_manifest(&ResourceManifest::Node(node.clone()))?;
+        append_auth_audit_event(
+            "capsule_identity_update",
+            &node.metadata.name,
+            "",
+            if previous { "rotated" } else { "recorded" },
+            "",
+        )?;
+    }
+    Ok(node)
+}
+
+pub fn rotate_capsule_key(sync: bool) -> anyhow::Result<CapsuleKeyRotationResult> {
+    let identity = generate_capsule_identity()?;
+    let path = save_capsule_identity(&identity)?;
+    let encryption_public_key = identity.encryption_public_key()?;
+    let signing_public_key = identity.signing_public_key()?;
+
+    let mut synced_nodes = Vec::new();
+    let mut failures = Vec::new();
+    for manifest in load_manifests_by_kind(ResourceKind::Node, None)? {
+        let ResourceManifest::Node(mut node) = manifest else {
+            continue;
+        };
+        if node_is_local(&node) {
+            node.spec.capsule_public_key = Some(encryption_public_key.clone());
+            node.spec.capsule_signing_key = Some(signing_public_key.clone());
+            save_manifest(&ResourceManifest::Node(node))?;
+            continue;
+        }
+        if !sync {
+            continue;
+        }
+        match exchange_capsule_identity(&node) {
+            Ok(_) => synced_nodes.push(node.metadata.name),
+            Err(error) => failures.push(NodeFanoutFailure {
+                node: node.metadata.name,
+                error: error.to_string(),
+            }),
+        }
+    }
+    Ok(CapsuleKeyRotationResult {
+        key_path: path.display().to_string(),
+        encryption_public_key,
+        signing_public_key,
+        synced_nodes,
+        failures,
+    })
+}
+
+/// Encrypt a prompt to `recipient`'s X25519 key with a fresh ephemeral key and sign the whole
+/// envelope with this node's Ed25519 key.
+pub fn protect_visit_capsule(
+    prompt: &str,
+    recipient: &ResourceEnvelope<NodeSpec>,
+) -> anyhow::Result<String> {
+    let recipient_key = recipient.spec.capsule_public_key.as_deref().ok_or_else(|| {
+        anyhow!(
+            "Node '{}' has no capsulePublicKey; run `jarvisctl node rotate-capsule-key` or visit it to exchange keys",
+            recipient.metadata.name
+        )
+    })?;
+    seal_capsule(
+        prompt,
+        &load_or_create_capsule_identity()?,
+        &local_node_name()?,
+        &recipient.metadata.name,
+        recipient_key,
+        now_epoch_ms() as u64,
+    )
+}
+
+fn seal_capsule(
+    prompt: &str,
+    identity: &CapsuleIdentity,
+    sender: &str,
+    recipient: &str,
+    recipient_key: &str,
+    now_ms: u64,
+) -> anyhow::Result<String> {
+    let recipient_public =
+        X25519PublicKey::from(decode_key32(recipient_key, "recipient capsule key")?);
+    let rng = rand::SystemRandom::new();
+    let mut ephemeral_raw = [0_u8; 32];
+    let mut nonce_bytes = [0_u8; 12];
+    let mut id_bytes = [0_u8; 16];
+    for buffer in [
+        &mut ephemeral_raw[..],
+        &mut nonce_bytes[..],
+        &mut id_bytes[..],
+    ] {
+        rand::SecureRandom::fill(&rng, buffer)
+            .map_err(|_| anyhow!("failed to generate capsule randomness"))?;
+    }
+    let ephemeral = StaticSecret::from(ephemeral_raw);
+    let ephemeral_public = X25519PublicKey::from(&ephemeral);
+    let header = CapsuleHeader {
+        version: 2,
+        algorithm: CAPSULE_V2_ALGORITHM.to_string(),
+        id: id_bytes.iter().map(|byte| format!("{byte:02x}")).collect(),
+        sender: sender.to_string(),
+        sender_key: identity.signing_public_key()?,
+        recipient: recipient.to_string(),
+        recipient_key: recipient_key.trim().to_string(),
+        ephemeral_key: BASE64.encode(ephemeral_public.as_bytes()),
+        nonce: BASE64.encode(nonce_bytes),
+        created_at_epoch_ms: now_ms,
+        expires_at_epoch_ms: now_ms + CAPSULE_TTL_MS,
+    };
+    let header_bytes = serde_json::to_vec(&header)?;
+    let key = capsule_content_key(
+        &ephemeral.diffie_hellman(&recipient_public),
+        &ephemeral_public,
+        &recipient_public,
+    )?;
+    let mut in_out = prompt.as_bytes().to_vec();
+    key.seal_in_place_append_tag(
+        aead::Nonce::assume_unique_for_key(nonce_bytes),
+        aead::Aad::from(&header_bytes),
+        &mut in_out,
+    )
+    .map_err(|_| anyhow!("failed to encrypt visit capsule"))?;
+    let signature = identity
+        .signing_key()?
+        .sign(&capsule_signed_bytes(&header_bytes, &in_out));
+    let envelope = SignedCapsule {
+        header,
+        ciphertext: BASE64.encode(in_out),
+        signature: BASE64.encode(signature.as_ref()),
+    };
+    serde_json::to_string(&envelope).context("failed to encode protected capsule")
+}
+
+fn capsule_content_key(
+    shared: &x25519_dalek::SharedSecret,
+    ephemeral_public: &X25519PublicKey,
+    recipient_public: &X25519PublicKey,
+) -> anyhow::Result<aead::LessSafeKey> {
+    ensure!(
+        shared.was_contributory(),
+        "capsule key agreement produced a non-contributory secret"
+    );
+    let mut salt = ephemeral_public.as_bytes().to_vec();
+    salt.extend_from_slice(recipient_public.as_bytes());
+    let prk = hkdf::Salt::new(hkdf::HKDF_SHA256, &salt).extract(shared.as_bytes());
+    let okm = prk
+        .expand(&[CAPSULE_V2_CONTEXT], &aead::CHACHA20_POLY1305)
+        .map_err(|_| anyhow!("failed to derive capsule key"))?;
+    Ok(aead::LessSafeKey::new(aead::UnboundKey::from(okm)))
+}
+
+fn capsule_signed_bytes(header_bytes: &[u8], ciphertext: &[u8]) -> Vec<u8> {
+    let mut signed = CAPSULE_V2_CONTEXT.to_vec();
+    signed.push(0);
+    signed.extend_from_slice(header_bytes);
+    signed.extend_from_slice(ciphertext);
+    signed
+}
+
+pub fn open_visit_capsule(raw: &str) -> anyhow::Result<String> {
+    let value: serde_json::Value =
+        serde_json::from_str(raw).context("failed to parse protected capsule")?;
+    if value.get("header").is_none() {
+        return open_legacy_capsule(value);
+    }
+    let envelope: SignedCapsule =
+        serde_json::from_value(value).context("failed to parse protected capsule")?;
+    let identity = load_or_create_capsule_identity()?;
+    let trusted = trusted_sender_key(&envelope.header.sender)?;
+    let plaintext = open_signed_capsule(
+        &envelope,
+        &identity,
+        trusted.as_deref(),
+        now_epoch_ms() as u64,
+    )?;
+    record_capsule_id(&envelope.header)?;
+    Ok(plaintext)
+}
+
+fn trusted_sender_key(sender: &str) -> anyhow::Result<Option<String>> {
+    if let Some(key) = load_capsule_trust()?.remove(sender) {
+        return Ok(Some(key));
+    }
+    Ok(load_manifest(ResourceKind::Node, sender, None)
+        .ok()
+        .and_then(|manifest| match manifest {
+            ResourceManifest::Node(node) => node.spec.capsule_signing_key,
+            _ => None,
+        }))
+}
+
+fn open_signed_capsule(
+    envelope: &SignedCapsule,
+    identity: &CapsuleIdentity,
+    trusted_sender_key: Option<&str>,
+    now_ms: u64,
+) -> anyhow::Result<String> {
+    let header = &envelope.header;
+    ensure!(
+        header.version == 2,
+        "unsupported capsule version {}",
+        header.version
+    );
+    ensure!(
+        header.algorithm == CAPSULE_V2_ALGORITHM,
+        "unsupported capsule algorithm {}",
+        header.algorithm
+    );
+    let trusted_sender_key = trusted_sender_key.ok_or_else(|| {
+        anyhow!(
+            "capsule sender '{}' is not trusted on this node",
+            header.sender
+        )
+    })?;
+    ensure!(
+        trusted_sender_key.trim() == header.sender_key,
+        "capsule sender '{}' signed with an unexpected key",
+        header.sender
+    );
+    let header_bytes = serde_json::to_vec(header)?;
+    let ciphertext = BASE64
+        .decode(envelope.ciphertext.as_bytes())
+        .context("failed to decode capsule ciphertext")?;
+    let signature_bytes = BASE64
+        .decode(envelope.signature.as_bytes())
+        .context("failed to decode capsule signature")?;
+    signature::UnparsedPublicKey::new(
+        &signature::ED25519,
+        decode_key32(&header.sender_key, "capsule sender key")?,
+    )
+    .verify(
+        &capsule_signed_bytes(&header_bytes, &ciphertext),
+        &signature_bytes,
+    )
+    .map_err(|_| anyhow!("capsule signature from '{}' is invalid", header.sender))?;
+
+    ensure!(
+        header.recipient_key == identity.encryption_public_key()?,
+        "capsule is addressed to '{}', not this node",
+        header.recipient
+    );
+    ensure!(
+        header.expires_at_epoch_ms > header.created_at_epoch_ms
+            && header.expires_at_epoch_ms - header.created_at_epoch_ms <= CAPSULE_MAX_TTL_MS,
+        "capsule lifetime is invalid"
+    );
+    ensure!(
+        header.created_at_epoch_ms <= now_ms + CAPSULE_CLOCK_SKEW_MS,
+        "capsule was created in the future"
+    );
+    ensure!(now_ms < header.expires_at_epoch_ms, "capsule has expired");
+
+    let nonce: [u8; 12] = BASE64
+        .decode(header.nonce.as_bytes())
+        .context("failed to decode capsule nonce")?
+        .try_into()
+        .map_err(|_| anyhow!("capsule nonce must be 12 bytes"))?;
+    let ephemeral_public = X25519PublicKey::from(decode_key32(
+        &header.ephemeral_key,
+        "capsule ephemeral key",
+    )?);
+    let secret = identity.encryption_secret()?;
+    let key = capsule_content_key(
+        &secret.diffie_hellman(&ephemeral_public),
+        &ephemeral_public,
+        &X25519PublicKey::from(&secret),
+    )?;
+    let mut in_out = ciphertext;
+    let plaintext = key
+        .open_in_place(
+            aead::Nonce::assume_unique_for_key(nonce),
+            aead::Aad::from(&header_bytes),
+            &mut in_out,
+        )
+        .map_err(|_| anyhow!("failed to authenticate or decrypt capsule"))?;
+    String::from_utf8(plaintext.to_vec()).context("capsule plaintext was not UTF-8")
+}
+
+/// Remember opened capsule ids until they expire so a captured capsule cannot be replayed.
+fn record_capsule_id(header: &CapsuleHeader) -> anyhow::Result<()> {
+    record_capsule_id_at(&capsule_replay_cache_path()?, header, now_epoch_ms() as u64)
+}
+
+fn record_capsule_id_at(path: &Path, header: &CapsuleHeader, now_ms: u64) -> anyhow::Result<()> {
+    // Hold the lock across the read-check-write so two concurrent opens of the
+    // same capsule cannot both see it as unseen.
+    let _lock = lock_capsule_replay_cache(path)?;
+    let mut seen: BTreeMap<String, u64> = if path.exists() {
+        // A cache that cannot be read must not be treated as empty, or every capsule it
+        // recorded could be replayed.
+        serde_json::from_str(&fs::read_to_string(path)?)
+            .with_context(|| format!("failed to parse capsule replay cache '{}'", path.display()))?
+    } else {
+        BTreeMap::new()
+    };
+    seen.retain(|_, expires_at| *expires_at > now_ms);
+    ensure!(
+        !seen.contains_key(&header.id),
+        "capsule '{}' from '{}' was already opened; refusing replay",
+        header.id,
+        header.sender
+    );
+    seen.insert(header.id.clone(), header.expires_at_epoch_ms);
+    write_private_file(path, &serde_json::to_vec(&seen)?)
+}
+
+/// Take an exclusive advisory lock on a sibling `.lock` file; released when the handle drops.
+fn lock_capsule_replay_cache(path: &Path) -> anyhow::Result<fs::File> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create '{}'", parent.display()))?;
+    }
+    let lock_path = path.with_extension("json.lock");
+    let mut options = fs::OpenOptions::new();
+    options.create(true).truncate(false).write(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let file = options
+        .open(&lock_path)
+        .with_context(|| format!("failed to open '{}'", lock_path.display()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::io::AsRawFd;
+        loop {
+            if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX) } == 0 {
+                break;
+            }
+            let err = io::Error::last_os_error();
+            if err.kind() != io::ErrorKind::Interrupted {
+                return Err(err)
+                    .with_context(|| format!("failed to lock '{}'", lock_path.display()));
+            }
+        }
+    }
+    Ok(file)
+}
+
+fn open_legacy_capsule(value: serde_json::Value) -> anyhow::Result<String> {
+    let envelope: LegacyProtectedCapsule =
+        serde_json::from_value(value).context("failed to parse protected capsule")?;
+    ensure!(
+        envelope.version == 1,
+        "unsupported capsule version {}",
+        envelope.version
+    );
+    ensure!(
+        envelope.algorithm == "CHACHA20-POLY1305",
+        "unsupported capsule algorithm {}",
+        envelope.algorithm
+    );
+    let path = legacy_capsule_key_path()?;
+    ensure!(
+        path.exists(),
+        "refusing unsigned v1 capsule; the shared capsule key has been retired on this node"
+    );
+    let key = fs::read(&path)
+        .with_context(|| format!("failed to read capsule key '{}'", path.display()))?;
+    ensure!(
+        key.len() == 32,
+        "capsule key '{}' must be 32 bytes",
+        path.display()
+    );
+    tracing::warn!(
+        "opening unsigned v1 visit capsule; delete '{}' once every node sends v2",
+        path.display()
+    );
+    let nonce: [u8; 12] = BASE64
+        .decode(envelope.nonce.as_bytes())
+        .context("failed to decode capsule nonce")?
+        .try_into()
+        .map_err(|_| anyhow!("capsule nonce must be 12 bytes"))?;
+    let mut in_out = BASE64
+        .decode(envelope.ciphertext.as_bytes())
+        .context("failed to decode capsule ciphertext")?;
+    let unbound = aead::UnboundKey::new(&aead::CHACHA20_POLY1305, &key)
+        .map_err(|_| anyhow!("failed to initialize capsule key"))?;
+    let plaintext = aead::LessSafeKey::new(unbound)
+        .open_in_place(
+            aead::Nonce::assume_unique_for_key(nonce),
+            aead::Aad::from(b"jarvisctl-visit-capsule-v1"),
+            &mut in_out,
+        )
+        .map_err(|_| anyhow!("failed to authenticate or decrypt capsule"))?;
+    String::from_utf8(plaintext.to_vec()).context("capsule plaintext was not UTF-8")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signed_capsule_opens_only_for_recipient_and_trusted_sender() {
+        let sender = generate_capsule_identity().unwrap();
+        let recipient = generate_capsule_identity().unwrap();
+        let stranger = generate_capsule_identity().unwrap();
+        let now_ms = 1_800_000_000_000;
+        let raw = seal_capsule(
+            "look around",
+            &sender,
+            "archiebald",
+            "archiechokie",
+            &recipient.encryption_public_key().unwrap(),
+            now_ms,
+        )
+        .unwrap();
+        let envelope: SignedCapsule = serde_json::from_str(&raw).unwrap();
+        let sender_key = sender.signing_public_key().unwrap();
+
+        assert_eq!(
+            open_signed_capsule(&envelope, &recipient, Some(&sender_key), now_ms + 1).unwrap(),
+            "look around"
+        );
+        assert!(open_signed_capsule(&envelope, &stranger, Some(&sender_key), now_ms).is_err());
+        assert!(open_signed_capsule(&envelope, &recipient, None, now_ms).is_err());
+        let other_key = stranger.signing_public_key().unwrap();
+        assert!(open_signed_capsule(&envelope, &recipient, Some(&other_key), now_ms).is_err());
+        assert!(
+            open_signed_capsule(
+                &envelope,
+                &recipient,
+                Some(&sender_key),
+                now_ms + CAPSULE_TTL_MS
+            )
+            .is_err()
+        );
+
+        let mut tampered: SignedCapsule = serde_json::from_str(&raw).unwrap();
+        tampered.header.expires_at_epoch_ms += 1;
+        assert!(open_signed_capsule(&tampered, &recipient, Some(&sender_key), now_ms).is_err());
+    }
+
+    #[test]
+    fn concurrent_opens_of_one_capsule_record_it_only_once() {
+        let sender = generate_capsule_identity().unwrap();
+        let recipient = generate_capsule_identity().unwrap();
+        let now_ms = 1_800_000_000_000;
+        let raw = seal_capsule(
+            "once",
+            &sender,
+            "archiebald",
+            "archiechokie",
+            &recipient.encryption_public_key().unwrap(),
+            now_ms,
+        )
+        .unwrap();
+        let envelope: SignedCapsule = serde_json::from_str(&raw).unwrap();
+        let dir = crate::test_support::unique_temp_dir("capsule-replay");
+        let path = dir.join("capsule-replay.json");
+
+        let accepted = thread::scope(|scope| {
+            let handles: Vec<_> = (0..8)
+                .map(|_| scope.spawn(|| record_capsule_id_at(&path, &envelope.header, now_ms)))
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .filter(Result::is_ok)
+                .count()
+        });
+        assert_eq!(accepted, 1);
+        assert!(record_capsule_id_at(&path, &envelope.header, now_ms + 1).is_err());
+
+        fs::write(&path, "not json").unwrap();
+        assert!(record_capsule_id_at(&path, &envelope.header, now_ms + 1).is_err());
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,244 @@
+use super::*;
+
+const NAMESPACED_KINDS: [ResourceKind; 8] = [
+    ResourceKind::Deployment,
+    ResourceKind::ReplicaSet,
+    ResourceKind::Service,
+    ResourceKind::Worker,
+    ResourceKind::NetworkPolicy,
+    ResourceKind::ConfigMap,
+    ResourceKind::Secret,
+    ResourceKind::Volume,
+];
+
+/// What happens to a resource's generated ReplicaSets and runtime namespaces when it is deleted.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub enum DeleteCascade {
+    /// Tear down dependents first and keep the resource if that fails
+    Foreground,
+    /// Delete the resource, then tear down dependents on a best-effort basis
+    #[default]
+    Background,
+    /// Delete only the resource and leave dependents running
+    Orphan,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DeleteOptions {
+    pub cascade: DeleteCascade,
+    /// Delete a Namespace together with everything it still contains.
+    pub force: bool,
+}
+
+/// Delete one `KIND/NAME` resource from the control plane.
+pub fn delete_resource(
+    reference: &str,
+    namespace: Option<&str>,
+    options: DeleteOptions,
+) -> anyhow::Result<Vec<String>> {
+    let (kind, name) = reference
+        .split_once('/')
+        .filter(|(kind, name)| !kind.trim().is_empty() && !name.trim().is_empty())
+        .ok_or_else(|| {
+            anyhow!("resource '{reference}' must be KIND/NAME, e.g. deployment/planner")
+        })?;
+    let kind_arg = ControlPlaneResourceKindArg::from_str(kind.trim(), true)
+        .map_err(|_| anyhow!("unknown resource kind '{}'", kind.trim()))?;
+    let kind = parse_specific_kind(kind_arg)?;
+    let namespace = (!matches!(kind, ResourceKind::Node | ResourceKind::Namespace))
+        .then(|| normalize_namespaced_resource_namespace(namespace));
+    let manifest =
+        load_manifest(kind, name.trim(), namespace.as_deref()).with_context(|| match namespace
+            .as_deref()
+        {
+            Some(namespace) => format!(
+                "{} {}/{} not found",
+                kind.display_name(),
+                namespace,
+                name.trim()
+            ),
+            None => format!("{} {} not found", kind.display_name(), name.trim()),
+        })?;
+    delete_manifest(&manifest, options)
+}
+
+/// Delete every resource a manifest file (or kustomization) declares. Namespaces go last so
+/// their contents from the same file are already gone when the emptiness check runs.
+pub fn delete_declared_resources(
+    files: &[PathBuf],
+    kustomize: Option<&Path>,
+    options: DeleteOptions,
+) -> anyhow::Result<Vec<String>> {
+    let mut manifests = load_source_manifests(files, kustomize)?;
+    manifests.sort_by_key(|manifest| matches!(manifest, ResourceManifest::Namespace(_)));
+    let mut messages = Vec::new();
+    for declared in &manifests {
+        let path = manifest_path(declared.kind(), declared.name(), declared.namespace())?;
+        if !path.exists() {
+            messages.push(format!("skipped {} (not found)", manifest_ref(declared)));
+            continue;
+        }
+        let deleted =
+            load_manifest_from_path(&path).and_then(|stored| delete_manifest(&stored, options));
+        match deleted {
+            Ok(deleted) => messages.extend(deleted),
+            // Keep what already happened visible; those deletes are not rolled back.
+            Err(error) if !messages.is_empty() => {
+                return Err(error.context(format!(
+                    "failed to delete {}; already done: {}",
+                    manifest_ref(declared),
+                    messages.join("; ")
+                )));
+            }
+            Err(error) => return Err(error),
+        }
+    }
+    Ok(messages)
+}
+
+fn delete_manifest(
+    manifest: &ResourceManifest,
+    options: DeleteOptions,
+) -> anyhow::Result<Vec<String>> {
+    match manifest {
+        ResourceManifest::Deployment(deployment) => {
+            Ok(vec![delete_deployment(deployment, options.cascade)?])
+        }
+        ResourceManifest::ReplicaSet(replica_set) => {
+            let namespace = replica_set.namespace_key();
+            if options.cascade == DeleteCascade::Orphan {
+                delete_manifest_only(
+                    ResourceKind::ReplicaSet,
+                    &replica_set.metadata.name,
+                    Some(namespace),
+                )?;
+                return Ok(vec![format!(
+                    "deleted {} (orphaned its runtimes)",
+                    manifest_ref(manifest)
+                )]);
+            }
+            delete_replica_set_and_runtimes(
+                namespace,
+                &replica_set.spec.deployment_name,
+                &replica_set.metadata.name,
+            )?;
+            Ok(vec![format!("deleted {}", manifest_ref(manifest))])
+        }
+        ResourceManifest::Namespace(namespace) => {
+            delete_namespace(&namespace.metadata.name, options)
+        }
+        _ => {
+            delete_manifest_only(manifest.kind(), manifest.name(), manifest.namespace())?;
+            Ok(vec![format!("deleted {}", manifest_ref(manifest))])
+        }
+    }
+}
+
+fn delete_deployment(
+    deployment: &ResourceEnvelope<DeploymentSpec>,
+    cascade: DeleteCascade,
+) -> anyhow::Result<String> {
+    let namespace = deployment.namespace_key();
+    let name = &deployment.metadata.name;
+    let replica_sets = load_replica_sets_for_deployment(namespace, name)?;
+    let delete_owner = || delete_manifest_only(ResourceKind::Deployment, name, Some(namespace));
+    match cascade {
+        DeleteCascade::Orphan => {
+            delete_owner()?;
+            Ok(format!(
+                "deleted Deployment {namespace}/{name} (orphaned {} ReplicaSet(s))",
+                replica_sets.len()
+            ))
+        }
+        DeleteCascade::Foreground => {
+            for replica_set in &replica_sets {
+                delete_replica_set_and_runtimes(namespace, name, &replica_set.metadata.name)
+                    .with_context(|| {
+                        format!(
+                            "failed to delete ReplicaSet {namespace}/{}; Deployment {namespace}/{name} was kept",
+                            replica_set.metadata.name
+                        )
+                    })?;
+            }
+            delete_owner()?;
+            Ok(format!(
+                "deleted Deployment {namespace}/{name} and {} ReplicaSet(s)",
+                replica_sets.len()
+            ))
+        }
+        DeleteCascade::Background => {
+            delete_owner()?;
+            let mut failures = Vec::new();
+            for replica_set in &replica_sets {
+                if let Err(error) =
+                    delete_replica_set_and_runtimes(namespace, name, &replica_set.metadata.name)
+                {
+                    failures.push(format!("{}: {error:#}", replica_set.metadata.name));
+                }
+            }
+            Ok(if failures.is_empty() {
+                format!(
+                    "deleted Deployment {namespace}/{name} and {} ReplicaSet(s)",
+                    replica_sets.len()
+                )
+            } else {
+                format!(
+                    "deleted Deployment {namespace}/{name}; failed to clean up {}",
+                    failures.join(", ")
+                )
+            })
+        }
+    }
+}
+
+/// Stops a ReplicaSet's runtimes and then removes it. Unlike rollout pruning, a runtime that
+/// will not stop fails the delete and the ReplicaSet is kept so it can be retried.
+fn delete_replica_set_and_runtimes(
+    namespace: &str,
+    deployment_name: &str,
+    replica_set_name: &str,
+) -> anyhow::Result<()> {
+    for session in replica_set_sessions(namespace, deployment_name, replica_set_name)? {
+        delete_runtime_session(&session)
+            .with_context(|| format!("failed to stop runtime {}", session.namespace))?;
+    }
+    delete_manifest_only(ResourceKind::ReplicaSet, replica_set_name, Some(namespace))
+}
+
+fn delete_namespace(name: &str, options: DeleteOptions) -> anyhow::Result<Vec<String>> {
+    let contents = namespace_contents(name)?;
+    if !contents.is_empty() && !options.force {
+        bail!(
+            "Namespace {} still contains {}; delete them first or pass --force",
+            name,
+            contents
+                .iter()
+                .map(manifest_ref)
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+    let mut messages = Vec::new();
+    // Deployments first so their ReplicaSets are torn down through the owner. With
+    // `--cascade orphan` the ReplicaSet manifests still go; only their runtimes are left running.
+    for manifest in contents
+        .iter()
+        .filter(|manifest| matches!(manifest, ResourceManifest::Deployment(_)))
+    {
+        messages.extend(delete_manifest(manifest, options)?);
+    }
+    for manifest in namespace_contents(name)? {
+        messages.extend(delete_manifest(&manifest, options)?);
+    }
+    delete_manifest_only(ResourceKind::Namespace, name, None)?;
+    messages.push(format!("deleted Namespace {name}"));
+    Ok(messages)
+}
+
+fn namespace_contents(namespace: &str) -> anyhow::Result<Vec<ResourceManifest>> {
+    let mut contents = Vec::new();
+    for kind in NAMESPACED_KINDS {
+        contents.extend(load_manifests_by_kind(kind, Some(namespace))?);
+    }
+    Ok(contents)
+}
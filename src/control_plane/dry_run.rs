@@ -0,0 +1,512 @@
+use super::*;
+use serde_json::Value as JsonValue;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ApplyDryRunReport {
+    pub resources: Vec<ManifestDiff>,
+    pub deployments: Vec<DeploymentDryRun>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ManifestDiff {
+    pub resource: String,
+    pub action: ManifestDiffAction,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub changes: Vec<FieldChange>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ManifestDiffAction {
+    Created,
+    Configured,
+    Unchanged,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldChange {
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<JsonValue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<JsonValue>,
+}
+
+/// What `reconcile_deployment` would do for one Deployment if the batch were applied.
+#[derive(Debug, Clone, Serialize)]
+pub struct DeploymentDryRun {
+    pub namespace: String,
+    pub name: String,
+    pub paused: bool,
+    pub template_hash: String,
+    pub target_replica_set: String,
+    pub target_revision: u64,
+    pub new_revision: bool,
+    pub replica_sets: Vec<ReplicaSetDryRun>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub pruned_replica_sets: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplicaSetDryRun {
+    pub name: String,
+    pub revision: u64,
+    pub replicas_before: usize,
+    pub replicas_after: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub start: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub stop: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub node: Option<String>,
+}
+
+impl ReplicaSetDryRun {
+    fn is_noop(&self) -> bool {
+        self.replicas_before == self.replicas_after && self.start.is_empty() && self.stop.is_empty()
+    }
+}
+
+/// Parse and validate manifests, diff them against the stored copies, and plan every Deployment
+/// reconcile without saving manifests or touching runtimes.
+pub fn dry_run_apply(
+    files: &[PathBuf],
+    kustomize: Option<&Path>,
+) -> anyhow::Result<ApplyDryRunReport> {
+    let manifests = load_source_manifests(files, kustomize)?;
+    let resources = manifests
+        .iter()
+        .map(diff_manifest)
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    // `apply` reconciles every stored Deployment, so plan those too with the batch on top.
+    let mut deployments = BTreeMap::new();
+    for manifest in load_manifests_by_kind(ResourceKind::Deployment, None)?
+        .into_iter()
+        .chain(manifests.iter().cloned())
+    {
+        if let ResourceManifest::Deployment(deployment) = manifest {
+            deployments.insert(
+                (
+                    deployment.namespace_key().to_string(),
+                    deployment.metadata.name.clone(),
+                ),
+                deployment,
+            );
+        }
+    }
+    let applied_namespaces = manifests
+        .iter()
+        .filter_map(|manifest| match manifest {
+            ResourceManifest::Namespace(namespace) => {
+                Some((namespace.metadata.name.clone(), namespace.spec.clone()))
+            }
+            _ => None,
+        })
+        .collect::<BTreeMap<_, _>>();
+
+    let sessions = if deployments.is_empty() {
+        Vec::new()
+    } else {
+        collect_runtime_sessions()?
+    };
+    let mut plans = Vec::new();
+    for deployment in deployments.values() {
+        let namespace_defaults = match applied_namespaces.get(deployment.namespace_key()) {
+            Some(spec) => spec.clone(),
+            None => load_namespace_defaults(deployment.namespace_key())?,
+        };
+        plans.push(plan_deployment(deployment, &namespace_defaults, &sessions)?);
+    }
+    Ok(ApplyDryRunReport {
+        resources,
+        deployments: plans,
+    })
+}
+
+pub fn diff_manifests(
+    files: &[PathBuf],
+    kustomize: Option<&Path>,
+) -> anyhow::Result<Vec<ManifestDiff>> {
+    load_source_manifests(files, kustomize)?
+        .iter()
+        .map(diff_manifest)
+        .collect()
+}
+
+fn diff_manifest(manifest: &ResourceManifest) -> anyhow::Result<ManifestDiff> {
+    let after = comparable_manifest_value(manifest)?;
+    let path = manifest_path(manifest.kind(), manifest.name(), manifest.namespace())?;
+    let mut changes = Vec::new();
+    let action = if path.exists() {
+        let stored = load_manifest_from_path(&path)?;
+        diff_json_values(
+            "",
+            Some(&comparable_manifest_value(&stored)?),
+            Some(&after),
+            &mut changes,
+        );
+        if changes.is_empty() {
+            ManifestDiffAction::Unchanged
+        } else {
+            ManifestDiffAction::Configured
+        }
+    } else {
+        diff_json_values("", None, Some(&after), &mut changes);
+        ManifestDiffAction::Created
+    };
+    Ok(ManifestDiff {
+        resource: manifest_ref(manifest),
+        action,
+        changes,
+    })
+}
+
+/// Secret values are compared by digest so neither side's plaintext or ciphertext is printed.
+fn comparable_manifest_value(manifest: &ResourceManifest) -> anyhow::Result<JsonValue> {
+    let mut value = serde_json::to_value(manifest).context("failed to encode manifest")?;
+    if let ResourceManifest::Secret(secret) = manifest
+        && let Some(spec) = value.get_mut("spec").and_then(JsonValue::as_object_mut)
+    {
+        spec.remove("stringData");
+        spec.remove("sealedData");
+        spec.insert("digests".to_string(), json!(secret_digests(&secret.spec)));
+    }
+    Ok(value)
+}
+
+fn diff_json_values(
+    path: &str,
+    before: Option<&JsonValue>,
+    after: Option<&JsonValue>,
+    changes: &mut Vec<FieldChange>,
+) {
+    let child_path = |key: &str| {
+        if path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{path}.{key}")
+        }
+    };
+    match (before, after) {
+        (None, None) => {}
+        (Some(before), Some(after)) if before == after => {}
+        (Some(JsonValue::Object(before)), Some(JsonValue::Object(after))) => {
+            let keys = before.keys().chain(after.keys()).collect::<BTreeSet<_>>();
+            for key in keys {
+                diff_json_values(&child_path(key), before.get(key), after.get(key), changes);
+            }
+        }
+        (Some(JsonValue::Object(before)), None) => {
+            for (key, value) in before {
+                diff_json_values(&child_path(key), Some(value), None, changes);
+            }
+        }
+        (None, Some(JsonValue::Object(after))) => {
+            for (key, value) in after {
+                diff_json_values(&child_path(key), None, Some(value), changes);
+            }
+        }
+        (Some(JsonValue::Array(before)), Some(JsonValue::Array(after)))
+            if before.len() == after.len() =>
+        {
+            for (index, (before, after)) in before.iter().zip(after).enumerate() {
+                diff_json_values(
+                    &format!("{path}[{index}]"),
+                    Some(before),
+                    Some(after),
+                    changes,
+                );
+            }
+        }
+        (before, after) => changes.push(FieldChange {
+            path: path.to_string(),
+            before: before.cloned(),
+            after: after.cloned(),
+        }),
+    }
+}
+
+/// Mirror `reconcile_deployment` on in-memory copies of the Deployment's ReplicaSets.
+fn plan_deployment(
+    manifest: &ResourceEnvelope<DeploymentSpec>,
+    namespace_defaults: &NamespaceSpec,
+    sessions: &[NativeSessionMetadata],
+) -> anyhow::Result<DeploymentDryRun> {
+    let control_namespace = manifest.namespace_key().to_string();
+    let deployment_name = manifest.metadata.name.clone();
+    let desired_hash = deployment_template_hash(manifest, namespace_defaults)?;
+    let mut replica_sets = load_replica_sets_for_deployment(&control_namespace, &deployment_name)?;
+    replica_sets.sort_by_key(|replica_set| replica_set.spec.revision);
+    let replicas_before = replica_sets
+        .iter()
+        .map(|replica_set| (replica_set.metadata.name.clone(), replica_set.spec.replicas))
+        .collect::<BTreeMap<_, _>>();
+
+    let (target_replica_set, new_revision) =
+        match deployment_rollout_target(manifest, &replica_sets, &desired_hash)? {
+            RolloutTarget::Existing(name) => (name, false),
+            RolloutTarget::Create { revision, replicas } => {
+                let replica_set = create_replica_set_manifest(
+                    manifest,
+                    namespace_defaults,
+                    revision,
+                    replicas,
+                    &desired_hash,
+                );
+                let name = replica_set.metadata.name.clone();
+                replica_sets.push(replica_set);
+                replica_sets.sort_by_key(|replica_set| replica_set.spec.revision);
+                (name, true)
+            }
+        };
+    if !manifest.spec.paused {
+        plan_deployment_strategy(manifest, &target_replica_set, &mut replica_sets, sessions)?;
+    }
+    let pruned_replica_sets = replica_sets_to_prune(
+        &target_replica_set,
+        manifest.spec.revision_history_limit,
+        &replica_sets,
+    );
+
+    let mut warnings = Vec::new();
+    let mut planned = Vec::new();
+    let mut target_revision = 0;
+    for replica_set in &replica_sets {
+        if replica_set.metadata.name == target_replica_set {
+            target_revision = replica_set.spec.revision;
+        }
+        let replicas_after = if pruned_replica_sets.contains(&replica_set.metadata.name) {
+            0
+        } else {
+            replica_set.spec.replicas
+        };
+        let desired = replica_set_runtime_namespaces(
+            &control_namespace,
+            &deployment_name,
+            replica_set.spec.revision,
+            replicas_after,
+        );
+        let managed = replica_set_live_session_names(replica_set, sessions)
+            .into_iter()
+            .collect::<HashSet<_>>();
+        let running = sessions
+            .iter()
+            .filter(|session| managed.contains(&session.namespace))
+            .filter(|session| session.agents.iter().any(|agent| agent.running))
+            .map(|session| session.namespace.clone())
+            .collect::<HashSet<_>>();
+        let mut stop = managed
+            .iter()
+            .filter(|namespace| !desired.contains(namespace) || !running.contains(*namespace))
+            .cloned()
+            .collect::<Vec<_>>();
+        stop.sort();
+        let start = desired
+            .into_iter()
+            .filter(|namespace| !running.contains(namespace))
+            .collect::<Vec<_>>();
+        let node = if start.is_empty() {
+            None
+        } else {
+            match select_node_for_template(&replica_set.spec.template) {
+                Ok(Some(node)) => Some(node.metadata.name),
+                Ok(None) => Some("local".to_string()),
+                Err(error) => {
+                    warnings.push(format!("{}: {error:#}", replica_set.metadata.name));
+                    None
+                }
+            }
+        };
+        planned.push(ReplicaSetDryRun {
+            name: replica_set.metadata.name.clone(),
+            revision: replica_set.spec.revision,
+            replicas_before: replicas_before
+                .get(&replica_set.metadata.name)
+                .copied()
+                .unwrap_or(0),
+            replicas_after,
+            start,
+            stop,
+            node,
+        });
+    }
+
+    Ok(DeploymentDryRun {
+        namespace: control_namespace,
+        name: deployment_name,
+        paused: manifest.spec.paused,
+        template_hash: desired_hash,
+        target_replica_set,
+        target_revision,
+        new_revision,
+        replica_sets: planned,
+        pruned_replica_sets,
+        warnings,
+    })
+}
+
+pub fn render_apply_dry_run_output(
+    report: &ApplyDryRunReport,
+    output: ControlPlaneOutput,
+) -> anyhow::Result<String> {
+    match output {
+        ControlPlaneOutput::Json => {
+            serde_json::to_string_pretty(report).context("failed to encode apply dry run")
+        }
+        ControlPlaneOutput::Yaml => {
+            serde_yaml::to_string(report).context("failed to encode apply dry run")
+        }
+        ControlPlaneOutput::Table => {
+            let mut lines = vec!["RESOURCE\tACTION\tCHANGES".to_string()];
+            for resource in &report.resources {
+                lines.push(format!(
+                    "{}\t{}\t{}",
+                    resource.resource,
+                    manifest_diff_action_label(resource.action),
+                    resource.changes.len()
+                ));
+            }
+            if report.deployments.is_empty() {
+                return Ok(lines.join("\n"));
+            }
+            lines.push(String::new());
+            lines.push("DEPLOYMENT\tREPLICASET\tREVISION\tREPLICAS\tSTART\tSTOP\tNODE".to_string());
+            for deployment in &report.deployments {
+                let scope = format!("{}/{}", deployment.namespace, deployment.name);
+                let mut rows = deployment
+                    .replica_sets
+                    .iter()
+                    .filter(|replica_set| {
+                        !replica_set.is_noop()
+                            || deployment.pruned_replica_sets.contains(&replica_set.name)
+                            || (deployment.new_revision
+                                && replica_set.name == deployment.target_replica_set)
+                    })
+                    .peekable();
+                if rows.peek().is_none() {
+                    lines.push(format!(
+                        "{scope}\t{}\t{}\tunchanged\t0\t0\t-",
+                        deployment.target_replica_set, deployment.target_revision
+                    ));
+                }
+                for replica_set in rows {
+                    let revision = if deployment.new_revision
+                        && replica_set.name == deployment.target_replica_set
+                    {
+                        format!("{} (new)", replica_set.revision)
+                    } else {
+                        replica_set.revision.to_string()
+                    };
+                    let replicas = if deployment.pruned_replica_sets.contains(&replica_set.name) {
+                        format!("{}->pruned", replica_set.replicas_before)
+                    } else {
+                        format!(
+                            "{}->{}",
+                            replica_set.replicas_before, replica_set.replicas_after
+                        )
+                    };
+                    lines.push(format!(
+                        "{scope}\t{}\t{revision}\t{replicas}\t{}\t{}\t{}",
+                        replica_set.name,
+                        replica_set.start.len(),
+                        replica_set.stop.len(),
+                        replica_set.node.as_deref().unwrap_or("-")
+                    ));
+                }
+                for warning in &deployment.warnings {
+                    lines.push(format!("warning: {scope}: {warning}"));
+                }
+            }
+            Ok(lines.join("\n"))
+        }
+    }
+}
+
+pub fn render_manifest_diff_output(
+    diffs: &[ManifestDiff],
+    output: ControlPlaneOutput,
+) -> anyhow::Result<String> {
+    match output {
+        ControlPlaneOutput::Json => {
+            serde_json::to_string_pretty(diffs).context("failed to encode manifest diff")
+        }
+        ControlPlaneOutput::Yaml => {
+            serde_yaml::to_string(diffs).context("failed to encode manifest diff")
+        }
+        ControlPlaneOutput::Table => {
+            let mut lines = Vec::new();
+            for diff in diffs
+                .iter()
+                .filter(|diff| diff.action != ManifestDiffAction::Unchanged)
+            {
+                lines.push(format!(
+                    "--- {} ({})",
+                    diff.resource,
+                    manifest_diff_action_label(diff.action)
+                ));
+                for change in &diff.changes {
+                    lines.push(match (&change.before, &change.after) {
+                        (Some(before), Some(after)) => {
+                            format!("~ {}: {before} -> {after}", change.path)
+                        }
+                        (None, Some(after)) => format!("+ {}: {after}", change.path),
+                        (Some(before), None) => format!("- {}: {before}", change.path),
+                        (None, None) => continue,
+                    });
+                }
+            }
+            if lines.is_empty() {
+                lines.push("no differences".to_string());
+            }
+            Ok(lines.join("\n"))
+        }
+    }
+}
+
+fn manifest_diff_action_label(action: ManifestDiffAction) -> &'static str {
+    match action {
+        ManifestDiffAction::Created => "created",
+        ManifestDiffAction::Configured => "configured",
+        ManifestDiffAction::Unchanged => "unchanged",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_json_values_reports_field_paths() {
+        let before = json!({
+            "spec": {"replicas": 2, "template": {"labels": {"tier": "gold"}, "args": ["a", "b"]}},
+        });
+        let after = json!({
+            "spec": {"replicas": 3, "template": {"model": "gpt-5", "args": ["a", "c"]}},
+        });
+        let mut changes = Vec::new();
+        diff_json_values("", Some(&before), Some(&after), &mut changes);
+        let rendered = changes
+            .iter()
+            .map(|change| {
+                format!(
+                    "{} {:?} {:?}",
+                    change.path,
+                    change.before.as_ref().map(ToString::to_string),
+                    change.after.as_ref().map(ToString::to_string)
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rendered,
+            vec![
+                r#"spec.replicas Some("2") Some("3")"#,
+                r#"spec.template.args[1] Some("\"b\"") Some("\"c\"")"#,
+                r#"spec.template.labels.tier Some("\"gold\"") None"#,
+                r#"spec.template.model None Some("\"gpt-5\"")"#,
+            ]
+        );
+    }
+}
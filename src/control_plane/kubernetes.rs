@@ -126,7 +126,7 @@ pub(crate) fn compile_kubernetes_manifests(
                 rendered.push(kubernetes_config_map_value(config_map));
             }
             ResourceManifest::Secret(secret) => {
-                rendered.push(kubernetes_secret_value(secret));
+                rendered.push(kubernetes_secret_value(secret)?);
             }
             ResourceManifest::NetworkPolicy(policy) => {
                 rendered.push(kubernetes_network_policy_value(policy));
@@ -409,8 +409,19 @@ fn kubernetes_config_map_value(manifest: &ResourceEnvelope<ConfigMapSpec>) -> se
     })
 }
 
-fn kubernetes_secret_value(manifest: &ResourceEnvelope<SecretSpec>) -> serde_json::Value {
-    json!({
+/// Kubernetes only sees `stringData`, so sealed and `valueFrom` keys are resolved here rather
+/// than dropped from the rendered Secret.
+fn kubernetes_secret_value(
+    manifest: &ResourceEnvelope<SecretSpec>,
+) -> anyhow::Result<serde_json::Value> {
+    let string_data = secret_values(manifest).with_context(|| {
+        format!(
+            "failed to resolve Secret '{}/{}' for Kubernetes",
+            manifest.namespace_key(),
+            manifest.metadata.name
+        )
+    })?;
+    Ok(json!({
         "apiVersion": "v1",
         "kind": "Secret",
         "type": "Opaque",
@@ -421,8 +432,8 @@ fn kubernetes_secret_value(manifest: &ResourceEnvelope<SecretSpec>) -> serde_jso
             BTreeMap::new(),
             None,
         ),
-        "stringData": manifest.spec.string_data,
-    })
+        "stringData": string_data,
+    }))
 }
 
 fn kubernetes_network_policy_value(
@@ -558,6 +569,7 @@ fn kubernetes_deployment_values(
         },
         extra_runtime_args: Vec::new(),
         startup_delay_ms: manifest.spec.startup_delay_ms.unwrap_or(1500),
+        restart_policy: template.restart_policy,
         command: template.command.clone(),
     })?;
     let launch_manifest = codex_app_manifest_from_prepared(&prepared);
@@ -47,8 +47,13 @@ pub fn render_describe_output(
         };
     }
     let kind = parse_specific_kind(kind_arg)?;
-    let manifest = load_manifest(kind, name, namespace)?;
+    let mut manifest = load_manifest(kind, name, namespace)?;
     let status = describe_status(&manifest)?;
+    if let ResourceManifest::Secret(secret) = &mut manifest {
+        // Key names and digests are in the status; values never leave `secret reveal`.
+        secret.spec.string_data.clear();
+        secret.spec.sealed_data.clear();
+    }
     let envelope = DescribeEnvelope {
         manifest: serde_json::to_value(&manifest).context("failed to encode manifest")?,
         status,
@@ -542,19 +547,13 @@ pub(crate) fn resource_summary(manifest: &ResourceManifest) -> anyhow::Result<Re
             kind: "Secret".to_string(),
             namespace: secret.metadata.namespace.clone(),
             name: secret.metadata.name.clone(),
-            status: format!("{} keys", secret.spec.string_data.len()),
+            status: format!("{} keys", secret.spec.keys().len()),
             detail: if secret.spec.access_policy.is_empty() {
-                secret
-                    .spec
-                    .string_data
-                    .keys()
-                    .cloned()
-                    .collect::<Vec<_>>()
-                    .join(", ")
+                secret.spec.keys().join(", ")
             } else {
                 format!(
                     "{} keys; scoped to {}",
-                    secret.spec.string_data.len(),
+                    secret.spec.keys().len(),
                     access_policy_summary(&secret.spec.access_policy)
                 )
             },
@@ -1175,7 +1174,10 @@ pub(crate) fn describe_status(manifest: &ResourceManifest) -> anyhow::Result<ser
             access_policy: resource_access_policy_status(&config_map.spec.access_policy),
         })?),
         ResourceManifest::Secret(secret) => Ok(serde_json::to_value(SecretStatus {
-            keys: secret.spec.string_data.keys().cloned().collect::<Vec<_>>(),
+            keys: secret.spec.keys(),
+            digests: secret_digests(&secret.spec),
+            sources: secret_source_descriptions(&secret.spec),
+            sealed: secret.spec.string_data.is_empty(),
             access_policy: resource_access_policy_status(&secret.spec.access_policy),
         })?),
         ResourceManifest::Volume(volume) => Ok(serde_json::to_value(VolumeStatus {
@@ -0,0 +1,773 @@
+use super::*;
+use crate::operator_request::{
+    OperatorRequestCreateOptions, consume_operator_request, create_operator_request,
+    show_operator_request,
+};
+
+const SECRET_KEYRING_ENV: &str = "JARVISCTL_SECRET_KEYRING";
+const SECRET_REVEAL_METHOD: &str = "secret/reveal";
+/// Longest a `fromCommand` or Secret Service lookup may run before it is killed.
+const SECRET_COMMAND_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Key-encryption keys for Secret values. Kept outside `~/.jarvis` by default so a backup
+/// of the control-plane root does not carry the keys that open it.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SecretKeyring {
+    active: String,
+    /// Key id to base64 key bytes.
+    keys: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SecretKeyRotationResult {
+    pub keyring_path: String,
+    pub key_id: String,
+    pub resealed: Vec<String>,
+    pub retired_keys: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum SecretRevealResult {
+    /// No approved request was given; one was filed for the operator.
+    PendingApproval { request_id: String, secret: String },
+    Revealed {
+        request_id: String,
+        secret: String,
+        values: BTreeMap<String, String>,
+    },
+}
+
+pub(crate) fn secret_keyring_path() -> anyhow::Result<PathBuf> {
+    if let Some(path) = env::var_os(SECRET_KEYRING_ENV).filter(|value| !value.is_empty()) {
+        return Ok(PathBuf::from(path));
+    }
+    let config = match env::var_os("XDG_CONFIG_HOME").filter(|value| !value.is_empty()) {
+        Some(config) => PathBuf::from(config),
+        None => PathBuf::from(env::var_os("HOME").context("HOME is not set")?).join(".config"),
+    };
+    Ok(config.join("jarvisctl").join("secret-keyring.json"))
+}
+
+fn load_secret_keyring() -> anyhow::Result<Option<SecretKeyring>> {
+    let path = secret_keyring_path()?;
+    if !path.exists() {
+        return Ok(None);
+    }
+    let raw = fs::read_to_string(&path)
+        .with_context(|| format!("failed to read secret keyring '{}'", path.display()))?;
+    let keyring: SecretKeyring = serde_json::from_str(&raw)
+        .with_context(|| format!("failed to parse secret keyring '{}'", path.display()))?;
+    ensure!(
+        keyring.keys.contains_key(&keyring.active),
+        "secret keyring '{}' has no active key '{}'",
+        path.display(),
+        keyring.active
+    );
+    Ok(Some(keyring))
+}
+
+fn save_secret_keyring(keyring: &SecretKeyring) -> anyhow::Result<()> {
+    let path = secret_keyring_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create '{}'", parent.display()))?;
+    }
+    let raw = serde_json::to_string_pretty(keyring).context("failed to encode secret keyring")?;
+    atomic_write_private_bytes(&path, raw.as_bytes())
+}
+
+fn generate_secret_key(keyring: &mut SecretKeyring) -> anyhow::Result<String> {
+    let rng = rand::SystemRandom::new();
+    let mut key = [0_u8; 32];
+    rand::SecureRandom::fill(&rng, &mut key)
+        .map_err(|_| anyhow!("failed to generate secret key"))?;
+    let mut id = format!("kek-{}", now_epoch_ms());
+    let mut suffix = 2;
+    while keyring.keys.contains_key(&id) {
+        id = format!("kek-{}-{}", now_epoch_ms(), suffix);
+        suffix += 1;
+    }
+    keyring.keys.insert(id.clone(), BASE64.encode(key));
+    keyring.active = id.clone();
+    Ok(id)
+}
+
+fn load_or_create_secret_keyring() -> anyhow::Result<SecretKeyring> {
+    if let Some(keyring) = load_secret_keyring()? {
+        return Ok(keyring);
+    }
+    let mut keyring = SecretKeyring::default();
+    generate_secret_key(&mut keyring)?;
+    save_secret_keyring(&keyring)?;
+    Ok(keyring)
+}
+
+fn secret_aead_key(keyring: &SecretKeyring, key_id: &str) -> anyhow::Result<aead::LessSafeKey> {
+    let encoded = keyring.keys.get(key_id).ok_or_else(|| {
+        anyhow!(
+            "secret keyring has no key '{}'; it may have been rotated away",
+            key_id
+        )
+    })?;
+    let raw = BASE64
+        .decode(encoded.as_bytes())
+        .with_context(|| format!("failed to decode secret key '{}'", key_id))?;
+    let unbound = aead::UnboundKey::new(&aead::CHACHA20_POLY1305, &raw)
+        .map_err(|_| anyhow!("secret key '{}' must be 32 bytes", key_id))?;
+    Ok(aead::LessSafeKey::new(unbound))
+}
+
+/// Binds each ciphertext to its Secret and key so sealed values cannot be swapped around.
+fn secret_aad(namespace: &str, name: &str, key: &str) -> String {
+    format!("jarvisctl-secret-v1:{namespace}/{name}/{key}")
+}
+
+pub(crate) fn secret_value_digest(value: &str) -> String {
+    format!("sha256:{}", &sha256_hex(value.as_bytes())[..16])
+}
+
+fn seal_secret_value(
+    keyring: &SecretKeyring,
+    aad: &str,
+    value: &str,
+) -> anyhow::Result<SealedSecretValue> {
+    let key = secret_aead_key(keyring, &keyring.active)?;
+    let rng = rand::SystemRandom::new();
+    let mut nonce_bytes = [0_u8; 12];
+    rand::SecureRandom::fill(&rng, &mut nonce_bytes)
+        .map_err(|_| anyhow!("failed to generate nonce"))?;
+    let mut in_out = value.as_bytes().to_vec();
+    key.seal_in_place_append_tag(
+        aead::Nonce::assume_unique_for_key(nonce_bytes),
+        aead::Aad::from(aad.as_bytes()),
+        &mut in_out,
+    )
+    .map_err(|_| anyhow!("failed to encrypt secret value"))?;
+    Ok(SealedSecretValue {
+        key_id: keyring.active.clone(),
+        nonce: BASE64.encode(nonce_bytes),
+        ciphertext: BASE64.encode(in_out),
+        digest: secret_value_digest(value),
+    })
+}
+
+fn open_secret_value(
+    keyring: &SecretKeyring,
+    aad: &str,
+    sealed: &SealedSecretValue,
+) -> anyhow::Result<String> {
+    let key = secret_aead_key(keyring, &sealed.key_id)?;
+    let nonce_raw = BASE64
+        .decode(sealed.nonce.as_bytes())
+        .context("failed to decode secret nonce")?;
+    let nonce_bytes: [u8; 12] = nonce_raw
+        .try_into()
+        .map_err(|_| anyhow!("secret nonce must be 12 bytes"))?;
+    let mut in_out = BASE64
+        .decode(sealed.ciphertext.as_bytes())
+        .context("failed to decode secret ciphertext")?;
+    let plaintext = key
+        .open_in_place(
+            aead::Nonce::assume_unique_for_key(nonce_bytes),
+            aead::Aad::from(aad.as_bytes()),
+            &mut in_out,
+        )
+        .map_err(|_| anyhow!("failed to authenticate or decrypt secret value"))?;
+    String::from_utf8(plaintext.to_vec()).context("secret value was not UTF-8")
+}
+
+/// Moves plaintext `stringData` into `sealedData` under the active key.
+pub(crate) fn seal_secret(secret: &mut ResourceEnvelope<SecretSpec>) -> anyhow::Result<()> {
+    if secret.spec.string_data.is_empty() {
+        return Ok(());
+    }
+    let keyring = load_or_create_secret_keyring()?;
+    let namespace = secret.namespace_key().to_string();
+    for (key, value) in std::mem::take(&mut secret.spec.string_data) {
+        let aad = secret_aad(&namespace, &secret.metadata.name, &key);
+        let sealed = seal_secret_value(&keyring, &aad, &value)?;
+        secret.spec.sealed_data.insert(key, sealed);
+    }
+    Ok(())
+}
+
+/// Plaintext values of a Secret: stored values, with sealed entries opened by the local
+/// keyring, plus every `valueFrom` source resolved now.
+pub(crate) fn secret_values(
+    secret: &ResourceEnvelope<SecretSpec>,
+) -> anyhow::Result<BTreeMap<String, String>> {
+    let mut values = stored_secret_values(secret)?;
+    for (key, source) in &secret.spec.value_from {
+        values.insert(key.clone(), resolve_secret_source(secret, key, source)?);
+    }
+    Ok(values)
+}
+
+/// One Secret value, resolving only that key's source.
+pub(crate) fn secret_value(
+    secret: &ResourceEnvelope<SecretSpec>,
+    key: &str,
+) -> anyhow::Result<Option<String>> {
+    if let Some(source) = secret.spec.value_from.get(key) {
+        return resolve_secret_source(secret, key, source).map(Some);
+    }
+    Ok(stored_secret_values(secret)?.remove(key))
+}
+
+/// Values kept in the manifest itself, opening sealed entries with the local keyring.
+fn stored_secret_values(
+    secret: &ResourceEnvelope<SecretSpec>,
+) -> anyhow::Result<BTreeMap<String, String>> {
+    let mut values = secret.spec.string_data.clone();
+    if secret.spec.sealed_data.is_empty() {
+        return Ok(values);
+    }
+    let namespace = secret.namespace_key();
+    let keyring = load_secret_keyring()?.ok_or_else(|| {
+        anyhow!(
+            "secret '{}/{}' is sealed but no secret keyring exists at '{}'",
+            namespace,
+            secret.metadata.name,
+            secret_keyring_path()
+                .map(|path| path.display().to_string())
+                .unwrap_or_default()
+        )
+    })?;
+    for (key, sealed) in &secret.spec.sealed_data {
+        let aad = secret_aad(namespace, &secret.metadata.name, key);
+        let value = open_secret_value(&keyring, &aad, sealed).with_context(|| {
+            format!(
+                "failed to open key '{}' of secret '{}/{}'",
+                key, namespace, secret.metadata.name
+            )
+        })?;
+        values.insert(key.clone(), value);
+    }
+    Ok(values)
+}
+
+pub(crate) fn secret_digests(spec: &SecretSpec) -> BTreeMap<String, String> {
+    spec.string_data
+        .iter()
+        .map(|(key, value)| (key.clone(), secret_value_digest(value)))
+        .chain(
+            spec.sealed_data
+                .iter()
+                .map(|(key, sealed)| (key.clone(), sealed.digest.clone())),
+        )
+        .collect()
+}
+
+fn resolve_secret_source(
+    secret: &ResourceEnvelope<SecretSpec>,
+    key: &str,
+    source: &SecretValueSource,
+) -> anyhow::Result<String> {
+    let resolved = source.kind().and_then(|kind| match kind {
+        SecretSourceKind::File(path) => read_secret_file(path),
+        SecretSourceKind::Env(name) => env::var(name)
+            .ok()
+            .filter(|value| !value.trim().is_empty())
+            .ok_or_else(|| anyhow!("environment variable '{}' is not set", name)),
+        SecretSourceKind::Command(command) => run_secret_command(command),
+        SecretSourceKind::Keyring(attributes) => lookup_secret_service(attributes),
+    });
+    resolved
+        .map(|value| {
+            let value = value.strip_suffix('\n').map_or(value.as_str(), |line| {
+                line.strip_suffix('\r').unwrap_or(line)
+            });
+            value.to_string()
+        })
+        .with_context(|| {
+            format!(
+                "failed to resolve key '{}' of secret '{}/{}'",
+                key,
+                secret.namespace_key(),
+                secret.metadata.name
+            )
+        })
+}
+
+/// Reads a secret file only when it is a regular file owned by this user and closed to
+/// group and others, the same rule ssh applies to private keys.
+fn read_secret_file(path: &str) -> anyhow::Result<String> {
+    let path = expand_home_pathbuf(Path::new(path));
+    let metadata = fs::metadata(&path)
+        .with_context(|| format!("failed to read secret file '{}'", path.display()))?;
+    ensure!(
+        metadata.is_file(),
+        "secret file '{}' is not a regular file",
+        path.display()
+    );
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{MetadataExt, PermissionsExt};
+        let mode = metadata.permissions().mode() & 0o777;
+        ensure!(
+            mode & 0o077 == 0,
+            "secret file '{}' has mode {:o}; restrict it with `chmod 600`",
+            path.display(),
+            mode
+        );
+        // SAFETY: geteuid has no preconditions and cannot fail.
+        let uid = unsafe { libc::geteuid() };
+        ensure!(
+            metadata.uid() == uid,
+            "secret file '{}' is not owned by the current user",
+            path.display()
+        );
+    }
+    fs::read_to_string(&path)
+        .with_context(|| format!("failed to read secret file '{}'", path.display()))
+}
+
+fn run_secret_command(command: &str) -> anyhow::Result<String> {
+    let args = shell_words::split(command)
+        .with_context(|| format!("failed to parse secret command '{}'", command))?;
+    let (program, args) = args
+        .split_first()
+        .ok_or_else(|| anyhow!("secret command is empty"))?;
+    let output = output_with_timeout(
+        ProcessCommand::new(program).args(args),
+        SECRET_COMMAND_TIMEOUT,
+    )
+    .with_context(|| format!("failed to run secret command '{}'", program))?;
+    ensure!(
+        output.status.success(),
+        "secret command '{}' failed with {}: {}",
+        program,
+        output.status,
+        String::from_utf8_lossy(&output.stderr).trim()
+    );
+    String::from_utf8(output.stdout).context("secret command output was not UTF-8")
+}
+
+/// Runs a secret helper without a terminal on stdin, so a helper that wants to prompt (a
+/// pinentry for `pass`, a locked keyring) fails instead of hanging, and kills it once
+/// `timeout` passes.
+fn output_with_timeout(
+    command: &mut ProcessCommand,
+    timeout: Duration,
+) -> anyhow::Result<std::process::Output> {
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let read_pipe = |pipe: Option<Box<dyn io::Read + Send>>| {
+        thread::spawn(move || {
+            let mut buffer = Vec::new();
+            if let Some(mut pipe) = pipe {
+                let _ = pipe.read_to_end(&mut buffer);
+            }
+            buffer
+        })
+    };
+    let stdout = read_pipe(child.stdout.take().map(|pipe| Box::new(pipe) as _));
+    let stderr = read_pipe(child.stderr.take().map(|pipe| Box::new(pipe) as _));
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            bail!("timed out after {}s", timeout.as_secs_f32());
+        }
+        thread::sleep(Duration::from_millis(20));
+    };
+    Ok(std::process::Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    })
+}
+
+/// Looks an item up through the Secret Service D-Bus API (GNOME Keyring, KeePassXC, KWallet)
+/// using libsecret's `secret-tool`.
+fn lookup_secret_service(attributes: &BTreeMap<String, String>) -> anyhow::Result<String> {
+    let output = output_with_timeout(
+        ProcessCommand::new("secret-tool").arg("lookup").args(
+            attributes
+                .iter()
+                .flat_map(|(name, value)| [name.as_str(), value.as_str()]),
+        ),
+        SECRET_COMMAND_TIMEOUT,
+    )
+    .context("fromKeyring needs `secret-tool` (libsecret) to query the Secret Service")?;
+    let value = String::from_utf8(output.stdout).context("keyring secret was not UTF-8")?;
+    ensure!(
+        output.status.success() && !value.is_empty(),
+        "no Secret Service item matches {}",
+        describe_keyring_attributes(attributes)
+    );
+    Ok(value)
+}
+
+fn describe_keyring_attributes(attributes: &BTreeMap<String, String>) -> String {
+    attributes
+        .iter()
+        .map(|(name, value)| format!("{name}={value}"))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Where each `valueFrom` key is read from, for `describe`.
+pub(crate) fn secret_source_descriptions(spec: &SecretSpec) -> BTreeMap<String, String> {
+    spec.value_from
+        .iter()
+        .map(|(key, source)| {
+            let description = match source.kind() {
+                Ok(SecretSourceKind::File(path)) => format!("fromFile {path}"),
+                Ok(SecretSourceKind::Env(name)) => format!("fromEnv {name}"),
+                Ok(SecretSourceKind::Command(command)) => format!("fromCommand {command}"),
+                Ok(SecretSourceKind::Keyring(attributes)) => {
+                    format!("fromKeyring {}", describe_keyring_attributes(attributes))
+                }
+                Err(error) => format!("invalid: {error}"),
+            };
+            (key.clone(), description)
+        })
+        .collect()
+}
+
+pub(crate) fn validate_secret(manifest: &ResourceEnvelope<SecretSpec>) -> anyhow::Result<()> {
+    for (key, source) in &manifest.spec.value_from {
+        ensure!(
+            !manifest.spec.string_data.contains_key(key)
+                && !manifest.spec.sealed_data.contains_key(key),
+            "Secret '{}' sets key '{}' in both stringData and valueFrom",
+            manifest.metadata.name,
+            key
+        );
+        let kind = source.kind().with_context(|| {
+            format!(
+                "Secret '{}' has an invalid valueFrom for key '{}'",
+                manifest.metadata.name, key
+            )
+        })?;
+        if let SecretSourceKind::Command(command) = kind {
+            let args = shell_words::split(command).with_context(|| {
+                format!(
+                    "Secret '{}' key '{}' has an unparsable fromCommand",
+                    manifest.metadata.name, key
+                )
+            })?;
+            ensure!(
+                !args.is_empty(),
+                "Secret '{}' key '{}' has an empty fromCommand",
+                manifest.metadata.name,
+                key
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Generates a new active key, reseals every stored Secret under it (sealing any still
+/// in plaintext), and drops keys no Secret uses any more.
+pub fn rotate_secret_key() -> anyhow::Result<SecretKeyRotationResult> {
+    let mut keyring = load_secret_keyring()?.unwrap_or_default();
+    let key_id = generate_secret_key(&mut keyring)?;
+    save_secret_keyring(&keyring)?;
+
+    let mut resealed = Vec::new();
+    for manifest in load_manifests_by_kind(ResourceKind::Secret, None)? {
+        let ResourceManifest::Secret(mut secret) = manifest else {
+            continue;
+        };
+        secret.spec.string_data = stored_secret_values(&secret)?;
+        secret.spec.sealed_data.clear();
+        resealed.push(format!(
+            "{}/{}",
+            secret.namespace_key(),
+            secret.metadata.name
+        ));
+        save_manifest(&ResourceManifest::Secret(secret))?;
+    }
+
+    let retired_keys = keyring
+        .keys
+        .keys()
+        .filter(|id| **id != key_id)
+        .cloned()
+        .collect::<Vec<_>>();
+    keyring.keys.retain(|id, _| *id == key_id);
+    save_secret_keyring(&keyring)?;
+    Ok(SecretKeyRotationResult {
+        keyring_path: secret_keyring_path()?.display().to_string(),
+        key_id,
+        resealed,
+        retired_keys,
+    })
+}
+
+/// Reveals Secret values only against an approved, unused `secret/reveal` operator request
+/// for the same Secret. Without a request id, files one and returns it for approval.
+pub fn reveal_secret(
+    name: &str,
+    namespace: Option<&str>,
+    key: Option<&str>,
+    request_id: Option<&str>,
+) -> anyhow::Result<SecretRevealResult> {
+    let control_namespace = normalize_namespaced_resource_namespace(namespace);
+    let secret_ref = format!("{}/{}", control_namespace, name);
+    let manifest = load_manifest(ResourceKind::Secret, name, Some(&control_namespace))?;
+    let ResourceManifest::Secret(secret) = manifest else {
+        bail!("resource '{}' is not a Secret", secret_ref);
+    };
+    if let Some(key) = key {
+        ensure!(
+            secret.spec.keys().iter().any(|existing| existing == key),
+            "secret '{}' does not contain key '{}'",
+            secret_ref,
+            key
+        );
+    }
+    let params = json!({
+        "namespace": control_namespace,
+        "name": name,
+        "key": key,
+    });
+
+    let Some(request_id) = request_id else {
+        let request = create_operator_request(OperatorRequestCreateOptions {
+            title: format!("Reveal secret {}", secret_ref),
+            kind: "secret".to_string(),
+            severity: "high".to_string(),
+            reason: format!(
+                "An operator asked to print the {} of Secret '{}'.",
+                key.map(|key| format!("'{key}' value"))
+                    .unwrap_or_else(|| "values".to_string()),
+                secret_ref
+            ),
+            risk: Some(
+                "Approving prints the plaintext credential to the requesting terminal.".to_string(),
+            ),
+            requested_by: env::var("USER").ok(),
+            namespace: Some(control_namespace.clone()),
+            request_id: None,
+            method: Some(SECRET_REVEAL_METHOD.to_string()),
+            command: Some(format!(
+                "jarvisctl secret reveal {} -n {}{}",
+                name,
+                control_namespace,
+                key.map(|key| format!(" --key {key}")).unwrap_or_default()
+            )),
+            params: Some(params),
+            ttl_seconds: Some(15 * 60),
+        })?;
+        return Ok(SecretRevealResult::PendingApproval {
+            request_id: request.id,
+            secret: secret_ref,
+        });
+    };
+
+    let request = show_operator_request(request_id)?;
+    ensure!(
+        request.method.as_deref() == Some(SECRET_REVEAL_METHOD) && request.params == Some(params),
+        "operator request '{}' does not approve revealing {}{}",
+        request_id,
+        secret_ref,
+        key.map(|key| format!(" key '{key}'")).unwrap_or_default()
+    );
+    consume_operator_request(request_id)?;
+    let mut values = secret_values(&secret)?;
+    if let Some(key) = key {
+        values.retain(|existing, _| existing == key);
+    }
+    append_auth_audit_event(
+        "secret_reveal",
+        &local_node_name().unwrap_or_else(|_| "local".to_string()),
+        &control_namespace,
+        "revealed",
+        &format!(
+            "{} keys={} request={}",
+            secret_ref,
+            values.keys().cloned().collect::<Vec<_>>().join(","),
+            request_id
+        ),
+    )?;
+    Ok(SecretRevealResult::Revealed {
+        request_id: request_id.to_string(),
+        secret: secret_ref,
+        values,
+    })
+}
+
+pub fn render_secret_reveal_output(
+    result: &SecretRevealResult,
+    output: ControlPlaneOutput,
+) -> anyhow::Result<String> {
+    match output {
+        ControlPlaneOutput::Json => {
+            serde_json::to_string_pretty(result).context("failed to encode secret reveal")
+        }
+        ControlPlaneOutput::Yaml => {
+            serde_yaml::to_string(result).context("failed to encode secret reveal")
+        }
+        ControlPlaneOutput::Table => match result {
+            SecretRevealResult::PendingApproval { request_id, secret } => Ok(format!(
+                "filed operator request {request_id} to reveal {secret}\napprove it with `jarvisctl operator-request resolve {request_id}`, then rerun with --request {request_id}"
+            )),
+            SecretRevealResult::Revealed { values, .. } => {
+                let mut lines = vec!["KEY\tVALUE".to_string()];
+                for (key, value) in values {
+                    lines.push(format!("{key}\t{value}"));
+                }
+                Ok(lines.join("\n"))
+            }
+        },
+    }
+}
+
+pub fn render_secret_key_rotation_output(
+    result: &SecretKeyRotationResult,
+    output: ControlPlaneOutput,
+) -> anyhow::Result<String> {
+    match output {
+        ControlPlaneOutput::Json => {
+            serde_json::to_string_pretty(result).context("failed to encode secret key rotation")
+        }
+        ControlPlaneOutput::Yaml => {
+            serde_yaml::to_string(result).context("failed to encode secret key rotation")
+        }
+        ControlPlaneOutput::Table => {
+            let mut lines = vec!["KEY_ID\tKEYRING\tRESEALED\tRETIRED".to_string()];
+            lines.push(format!(
+                "{}\t{}\t{}\t{}",
+                result.key_id,
+                result.keyring_path,
+                result.resealed.len(),
+                if result.retired_keys.is_empty() {
+                    "-".to_string()
+                } else {
+                    result.retired_keys.join(",")
+                }
+            ));
+            Ok(lines.join("\n"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sealed_values_open_only_for_their_secret_and_survive_new_keys() {
+        let mut keyring = SecretKeyring::default();
+        let first = generate_secret_key(&mut keyring).unwrap();
+        let aad = secret_aad("default", "openai", "OPENAI_API_KEY");
+        let sealed = seal_secret_value(&keyring, &aad, "sk-test").unwrap();
+        assert_eq!(sealed.key_id, first);
+        assert!(!sealed.ciphertext.contains("sk-test"));
+        assert_eq!(sealed.digest, secret_value_digest("sk-test"));
+
+        let other = secret_aad("default", "anthropic", "OPENAI_API_KEY");
+        assert!(open_secret_value(&keyring, &other, &sealed).is_err());
+
+        let second = generate_secret_key(&mut keyring).unwrap();
+        assert_ne!(first, second);
+        assert_eq!(
+            open_secret_value(&keyring, &aad, &sealed).unwrap(),
+            "sk-test"
+        );
+
+        keyring.keys.remove(&first);
+        assert!(open_secret_value(&keyring, &aad, &sealed).is_err());
+    }
+
+    #[test]
+    fn value_from_sources_resolve_at_use_and_check_file_permissions() {
+        let root = crate::test_support::unique_temp_dir("jarvisctl-secret-sources");
+        fs::create_dir_all(&root).unwrap();
+        let token = root.join("token");
+        fs::write(&token, "file-token\r\n").unwrap();
+        // Read a variable every test process already has instead of mutating the environment.
+        let env_name = "PATH";
+
+        let manifest = format!(
+            "apiVersion: jarvisctl.io/v1alpha1\nkind: Secret\nmetadata:\n  name: sources\nspec:\n  valueFrom:\n    FILE:\n      fromFile: {}\n    ENV:\n      fromEnv: {}\n    COMMAND:\n      fromCommand: printf 'command-token\\r\\n\\n'\n",
+            token.display(),
+            env_name
+        );
+        let mut parsed = parse_manifest_documents(&manifest).unwrap();
+        let ResourceManifest::Secret(secret) = parsed.remove(0) else {
+            panic!("expected a Secret");
+        };
+        assert_eq!(secret.spec.keys(), vec!["COMMAND", "ENV", "FILE"]);
+        assert_eq!(
+            secret_value(&secret, "COMMAND").unwrap().as_deref(),
+            Some("command-token\r\n")
+        );
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&token, fs::Permissions::from_mode(0o644)).unwrap();
+            assert!(secret_value(&secret, "FILE").is_err());
+            fs::set_permissions(&token, fs::Permissions::from_mode(0o600)).unwrap();
+        }
+        let values = secret_values(&secret).unwrap();
+        assert_eq!(values["FILE"], "file-token");
+        assert_eq!(values["ENV"], env::var(env_name).unwrap());
+
+        let ambiguous = "apiVersion: jarvisctl.io/v1alpha1\nkind: Secret\nmetadata:\n  name: bad\nspec:\n  valueFrom:\n    KEY:\n      fromEnv: A\n      fromCommand: pass show a\n";
+        assert!(parse_manifest_documents(ambiguous).is_err());
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn kubernetes_secrets_carry_value_from_keys() {
+        let manifests = parse_manifest_documents(
+            "apiVersion: jarvisctl.io/v1alpha1\nkind: Secret\nmetadata:\n  name: kube\nspec:\n  stringData:\n    PLAIN: inline\n  valueFrom:\n    FROM_COMMAND:\n      fromCommand: printf 'command-token'\n",
+        )
+        .unwrap();
+        let compiled = compile_kubernetes_manifests(&manifests).unwrap();
+        assert_eq!(
+            compiled.manifests[0]["stringData"],
+            json!({"FROM_COMMAND": "command-token", "PLAIN": "inline"})
+        );
+    }
+
+    #[test]
+    fn secret_commands_get_no_stdin_and_are_killed_on_timeout() {
+        let output =
+            output_with_timeout(&mut ProcessCommand::new("cat"), Duration::from_secs(5)).unwrap();
+        assert!(output.status.success());
+        assert!(output.stdout.is_empty());
+
+        let started = Instant::now();
+        let error = output_with_timeout(
+            ProcessCommand::new("sleep").arg("30"),
+            Duration::from_millis(200),
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("timed out"), "{error}");
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn private_files_are_created_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+        let root = crate::test_support::unique_temp_dir("jarvisctl-private-write");
+        fs::create_dir_all(&root).unwrap();
+        let path = root.join("secret-keyring.json");
+        atomic_write_private_bytes(&path, b"{}").unwrap();
+        atomic_write_private_bytes(&path, b"{\"keys\":{}}").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{\"keys\":{}}");
+        assert_eq!(
+            fs::metadata(&path).unwrap().permissions().mode() & 0o777,
+            0o600
+        );
+        assert_eq!(fs::read_dir(&root).unwrap().count(), 1);
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -6,7 +6,15 @@ pub(crate) fn save_manifest(manifest: &ResourceManifest) -> anyhow::Result<()> {
         fs::create_dir_all(parent)
             .with_context(|| format!("failed to create '{}'", parent.display()))?;
     }
-    let raw = serde_yaml::to_string(manifest).context("failed to encode manifest")?;
+    let raw = match manifest {
+        ResourceManifest::Secret(secret) if !secret.spec.string_data.is_empty() => {
+            let mut secret = secret.clone();
+            seal_secret(&mut secret)?;
+            serde_yaml::to_string(&ResourceManifest::Secret(secret))
+        }
+        _ => serde_yaml::to_string(manifest),
+    }
+    .context("failed to encode manifest")?;
     atomic_write_string(&path, &raw)
 }
 
@@ -72,6 +80,46 @@ pub(crate) fn atomic_write_bytes(path: &Path, raw: &[u8]) -> anyhow::Result<()>
     })
 }
 
+/// Like `atomic_write_bytes`, but the temp file is created owner-only so the contents are
+/// never readable by others, not even between the write and the rename.
+pub(crate) fn atomic_write_private_bytes(path: &Path, raw: &[u8]) -> anyhow::Result<()> {
+    let parent = path
+        .parent()
+        .ok_or_else(|| anyhow!("path '{}' has no parent", path.display()))?;
+    let file_name = path
+        .file_name()
+        .and_then(|value| value.to_str())
+        .ok_or_else(|| anyhow!("path '{}' has no file name", path.display()))?;
+    let temp_path = parent.join(format!(
+        ".{}.tmp.{}.{}",
+        file_name,
+        std::process::id(),
+        Utc::now().timestamp_nanos_opt().unwrap_or_default()
+    ));
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let written = options
+        .open(&temp_path)
+        .and_then(|mut file| file.write_all(raw))
+        .with_context(|| format!("failed to write '{}'", temp_path.display()));
+    if let Err(error) = written {
+        let _ = fs::remove_file(&temp_path);
+        return Err(error);
+    }
+    fs::rename(&temp_path, path).with_context(|| {
+        format!(
+            "failed to rename '{}' to '{}'",
+            temp_path.display(),
+            path.display()
+        )
+    })
+}
+
 pub(crate) fn load_all_manifests(namespace: Option<&str>) -> anyhow::Result<Vec<ResourceManifest>> {
     let mut manifests = Vec::new();
     for kind in [
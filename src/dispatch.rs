@@ -5,29 +5,37 @@ use crate::codex::{
     discover_codex_session_id, discover_latest_launch_session_id, launch_codex_ticket,
 };
 use crate::control_plane::{
-    NodeStartSessionOptions, NodeStartSessionResult, load_or_create_orchestration_policy,
-    start_node_session,
+    ControlPlaneOutput, NodeStartSessionOptions, NodeStartSessionResult, atomic_write_string,
+    load_or_create_orchestration_policy, start_node_session,
 };
 use crate::mission::{MissionEventOptions, append_mission_event};
-use crate::native::RuntimeContextMetadata;
+use crate::native::{AgentRestartPolicy, RuntimeContextMetadata};
 use crate::runtime::{
     RuntimeSessionState, cancel_runtime_session, delete_runtime_session_if_exists,
-    probe_runtime_session_state,
+    interrupt_runtime_session, probe_runtime_session_state, runtime_context_for_namespace,
 };
 use crate::ticket::TicketNote;
+use crate::ticket_graph::{BlockingDependency, TicketGraph};
+use crate::watch::VaultWatcher;
 use anyhow::{Context, anyhow};
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::thread;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use time::OffsetDateTime;
 use time::format_description::well_known::Rfc3339;
 use tracing::{info, warn};
 
 const READY_FOR_CODEX: &str = "ready for codex";
 const CODEX_WORKING: &str = "codex working";
+const QUEUED_FOR_CODEX: &str = "Queued for Codex";
+const DEFAULT_RUN_ESTIMATE_MS: u128 = 30 * 60 * 1000;
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(300);
+const WATCH_MAX_BATCH: Duration = Duration::from_secs(2);
+const MAX_RECORDED_CONFLICTS: usize = 50;
+const TICKET_WRITE_ATTEMPTS: usize = 3;
 
 #[derive(Debug, Clone)]
 pub struct DispatchOptions {
@@ -37,11 +45,14 @@ pub struct DispatchOptions {
     pub boards: Vec<PathBuf>,
     pub interval_seconds: u64,
     pub once: bool,
+    pub watch: bool,
     pub dry_run: bool,
     pub state_file: Option<PathBuf>,
     pub agent: String,
     pub agents: usize,
     pub startup_delay_ms: u64,
+    pub max_active_runs: Option<usize>,
+    pub max_active_runs_per_project: Option<usize>,
     pub command: Vec<String>,
 }
 
@@ -52,6 +63,34 @@ pub struct DispatchState {
     pub active_runs: BTreeMap<String, ActiveRunState>,
     #[serde(default)]
     pub tickets: BTreeMap<String, TicketRuntimeState>,
+    #[serde(default)]
+    pub blocked: BTreeMap<String, BlockedTicketState>,
+    #[serde(default)]
+    pub queued: BTreeMap<String, QueuedTicketState>,
+    #[serde(default)]
+    pub retries: BTreeMap<String, RetryTicketState>,
+    #[serde(default)]
+    pub limits: DispatchLimits,
+    #[serde(default)]
+    pub conflicts: Vec<BoardConflictState>,
+}
+
+/// A card move the dispatcher dropped because the operator changed the same card on disk.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BoardConflictState {
+    pub board_path: String,
+    pub ticket_link: String,
+    pub reason: String,
+    pub detected_at_epoch_ms: u128,
+}
+
+/// Concurrency limits from the last dispatcher pass, kept so `dispatch queue` can estimate ETAs.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default)]
+pub struct DispatchLimits {
+    #[serde(default)]
+    pub max_active_runs: Option<usize>,
+    #[serde(default)]
+    pub max_active_runs_per_project: Option<usize>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -81,6 +120,71 @@ pub struct ActiveRunState {
     pub launched_at_epoch_ms: u128,
     #[serde(default)]
     pub codex_session_id: Option<String>,
+    #[serde(default)]
+    pub project: Option<String>,
+    #[serde(default)]
+    pub last_turn_status: Option<String>,
+    #[serde(default)]
+    pub last_error: Option<String>,
+    #[serde(default)]
+    pub last_activity_epoch_ms: Option<u128>,
+    #[serde(default)]
+    pub activity_fingerprint: Option<String>,
+}
+
+/// A ready card held back until its `depends_on:` tickets reach a done status.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockedTicketState {
+    pub ticket_link: String,
+    pub board_path: String,
+    pub blocked_at_epoch_ms: u128,
+    #[serde(default)]
+    pub waiting_on: Vec<String>,
+}
+
+/// A ready card waiting for a free run slot under the dispatcher's concurrency limits.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueuedTicketState {
+    pub ticket_link: String,
+    pub board_path: String,
+    pub project: String,
+    pub priority_rank: u32,
+    pub queued_at_epoch_ms: u128,
+    /// The card's Kanban `@{date}`, breaking priority ties so earlier due dates run first.
+    #[serde(default)]
+    pub due: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// A failed card waiting in the failure column until its retry backoff elapses.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetryTicketState {
+    pub ticket_link: String,
+    pub board_path: String,
+    pub attempt: u32,
+    pub not_before_epoch_ms: u128,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DispatchScheduleEntry {
+    pub ticket: String,
+    pub schedule: String,
+    pub last_run_epoch_ms: Option<u128>,
+    pub next_run_epoch_ms: Option<u128>,
+    pub last_outcome: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DispatchQueueEntry {
+    pub position: usize,
+    pub ticket: String,
+    pub project: String,
+    pub priority_rank: u32,
+    pub due: Option<String>,
+    pub tags: Vec<String>,
+    pub queued_at_epoch_ms: u128,
+    pub eta_epoch_ms: u128,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -95,6 +199,48 @@ pub struct TicketRuntimeState {
     pub last_outcome: Option<String>,
     #[serde(default)]
     pub last_transition_epoch_ms: Option<u128>,
+    #[serde(default)]
+    pub last_run_duration_ms: Option<u128>,
+    #[serde(default)]
+    pub retry_count: u32,
+    #[serde(default)]
+    pub schedule: Option<String>,
+    #[serde(default)]
+    pub last_scheduled_run_epoch_ms: Option<u128>,
+    #[serde(default)]
+    pub next_scheduled_run_epoch_ms: Option<u128>,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum RunOutcomeKind {
+    Success,
+    TurnFailed,
+    RuntimeCrashed,
+    TimedOut,
+    Interrupted,
+}
+
+impl RunOutcomeKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Success => "success",
+            Self::TurnFailed => "turn-failed",
+            Self::RuntimeCrashed => "runtime-crashed",
+            Self::TimedOut => "timed-out",
+            Self::Interrupted => "interrupted",
+        }
+    }
+
+    /// Interrupts and timeouts are deliberate stops, so only genuine failures are retried.
+    fn is_retryable(self) -> bool {
+        matches!(self, Self::TurnFailed | Self::RuntimeCrashed)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RunOutcome {
+    kind: RunOutcomeKind,
+    reason: String,
 }
 
 #[derive(Debug, Clone, Deserialize, Default)]
@@ -118,11 +264,25 @@ impl Default for DispatchState {
             boards: BTreeMap::new(),
             active_runs: BTreeMap::new(),
             tickets: BTreeMap::new(),
+            blocked: BTreeMap::new(),
+            queued: BTreeMap::new(),
+            retries: BTreeMap::new(),
+            limits: DispatchLimits::default(),
+            conflicts: Vec::new(),
         }
     }
 }
 
 pub fn run_dispatch_loop(options: DispatchOptions) -> anyhow::Result<()> {
+    if options.watch && !options.once {
+        match VaultWatcher::new(&options.vault_path) {
+            Ok(watcher) => return run_dispatch_watch_loop(&options, watcher),
+            Err(error) => warn!(
+                "Falling back to polling every {}s: {error:#}",
+                options.interval_seconds.max(1)
+            ),
+        }
+    }
     loop {
         dispatch_once(&options)?;
         if options.once {
@@ -132,17 +292,95 @@ pub fn run_dispatch_loop(options: DispatchOptions) -> anyhow::Result<()> {
     }
 }
 
+/// Runs a pass whenever the vault changes, and at least every `interval_seconds` so runtime
+/// completions that never touch the vault are still noticed.
+fn run_dispatch_watch_loop(
+    options: &DispatchOptions,
+    mut watcher: VaultWatcher,
+) -> anyhow::Result<()> {
+    let state_path = options
+        .state_file
+        .clone()
+        .unwrap_or(default_state_file(&options.vault_path)?);
+    info!(
+        "Watching '{}' for board and ticket changes",
+        options.vault_path.display()
+    );
+    dispatch_once(options)?;
+    let deadline = || Instant::now() + Duration::from_secs(options.interval_seconds.max(1));
+    let mut next_pass = deadline();
+    loop {
+        match watcher.wait(
+            next_pass.saturating_duration_since(Instant::now()),
+            WATCH_DEBOUNCE,
+            WATCH_MAX_BATCH,
+        ) {
+            Ok(changed) => {
+                let changed = changed
+                    .into_iter()
+                    .filter(|path| *path != state_path)
+                    .count();
+                if changed == 0 && Instant::now() < next_pass {
+                    continue;
+                }
+                if changed > 0 {
+                    info!("Vault changed ({} file(s)); rescanning", changed);
+                }
+                dispatch_once(options)?;
+                next_pass = deadline();
+            }
+            Err(error) => {
+                warn!(
+                    "Vault watch failed; falling back to polling every {}s: {error:#}",
+                    options.interval_seconds.max(1)
+                );
+                loop {
+                    thread::sleep(Duration::from_secs(options.interval_seconds.max(1)));
+                    dispatch_once(options)?;
+                }
+            }
+        }
+    }
+}
+
 fn dispatch_once(options: &DispatchOptions) -> anyhow::Result<()> {
     let state_path = options
         .state_file
         .clone()
         .unwrap_or(default_state_file(&options.vault_path)?);
     let mut state = load_state(&state_path)?;
+    state.limits = DispatchLimits {
+        max_active_runs: options.max_active_runs,
+        max_active_runs_per_project: options.max_active_runs_per_project,
+    };
     let board_paths = resolve_board_paths(options)?;
     let active_board_paths = state
         .active_runs
         .values()
         .map(|run| PathBuf::from(&run.board_path))
+        .chain(
+            state
+                .blocked
+                .values()
+                .map(|blocked| PathBuf::from(&blocked.board_path)),
+        )
+        .chain(
+            state
+                .queued
+                .values()
+                .map(|queued| PathBuf::from(&queued.board_path)),
+        )
+        .chain(
+            state
+                .retries
+                .values()
+                .map(|retry| PathBuf::from(&retry.board_path)),
+        )
+        .chain(due_schedule_board_paths(
+            &options.vault_path,
+            &state,
+            now_epoch_ms()?,
+        ))
         .collect::<BTreeSet<_>>();
     let mut boards_to_load = Vec::new();
     for board_path in &board_paths {
@@ -166,6 +404,11 @@ fn dispatch_once(options: &DispatchOptions) -> anyhow::Result<()> {
 
     let canceled_links = process_manual_cancellations(&mut boards, &mut state, options)?;
     process_completed_runs(&mut boards, &mut state, options, &mut dirty_boards)?;
+    let cards = known_cards(&boards, &state);
+    process_blocked_tickets(&mut boards, &mut state, options, &cards, &mut dirty_boards)?;
+    process_queued_tickets(&mut boards, &mut state, options, &cards, &mut dirty_boards)?;
+    process_retries(&mut boards, &mut state, options, &cards, &mut dirty_boards)?;
+    process_scheduled_tickets(&mut boards, &mut state, options, &cards, &mut dirty_boards)?;
 
     for board_path in &board_paths {
         info!("Scanning board '{}'", board_path.display());
@@ -194,11 +437,18 @@ fn dispatch_once(options: &DispatchOptions) -> anyhow::Result<()> {
                 && previous_column.map(|value| normalize_column(value))
                     != Some(READY_FOR_CODEX.to_string())
             {
+                let ticket_key = resolve_wiki_link(&options.vault_path, ticket_link)
+                    .display()
+                    .to_string();
+                if let Some(runtime) = state.tickets.get_mut(&ticket_key) {
+                    runtime.retry_count = 0;
+                }
                 handle_ready_transition(
                     board,
                     ticket_link,
                     options,
                     &mut state,
+                    &cards,
                     &mut dirty_boards,
                 )?;
             }
@@ -208,10 +458,24 @@ fn dispatch_once(options: &DispatchOptions) -> anyhow::Result<()> {
     if !options.dry_run {
         for board_path in &dirty_boards {
             let board = boards
-                .get(board_path)
+                .get_mut(board_path)
                 .ok_or_else(|| anyhow!("board '{}' was not loaded", board_path.display()))?;
-            board.save()?;
+            for conflict in board.save()? {
+                warn!(
+                    "Board '{}' changed on disk: {}",
+                    board_path.display(),
+                    conflict
+                );
+                state.conflicts.push(BoardConflictState {
+                    board_path: board_path.display().to_string(),
+                    ticket_link: conflict.link.clone(),
+                    reason: conflict.to_string(),
+                    detected_at_epoch_ms: now_epoch_ms()?,
+                });
+            }
         }
+        let overflow = state.conflicts.len().saturating_sub(MAX_RECORDED_CONFLICTS);
+        state.conflicts.drain(..overflow);
         for board_path in boards.keys() {
             let board = boards
                 .get(board_path)
@@ -231,6 +495,7 @@ fn handle_ready_transition(
     ticket_link: &str,
     options: &DispatchOptions,
     state: &mut DispatchState,
+    cards: &[(String, String)],
     dirty_boards: &mut BTreeSet<PathBuf>,
 ) -> anyhow::Result<()> {
     let ticket_path = resolve_wiki_link(&options.vault_path, ticket_link);
@@ -260,6 +525,21 @@ fn handle_ready_transition(
         return Ok(());
     }
 
+    let blocking =
+        TicketGraph::load(&options.vault_path, cards).blocking_dependencies(&ticket_path);
+    if !blocking.is_empty() {
+        return hold_blocked_ticket(
+            board,
+            ticket_link,
+            &ticket,
+            &blocking,
+            options,
+            state,
+            dirty_boards,
+        );
+    }
+
+    let project = ticket_project(&ticket, board);
     let expected_namespace = default_namespace_for_ticket(&ticket);
     let last_codex_session_id = state
         .tickets
@@ -300,6 +580,11 @@ fn handle_ready_transition(
                     record_file: String::new(),
                     launched_at_epoch_ms: now_epoch_ms()?,
                     codex_session_id,
+                    project: Some(project),
+                    last_turn_status: None,
+                    last_error: None,
+                    last_activity_epoch_ms: None,
+                    activity_fingerprint: None,
                 },
             );
             return Ok(());
@@ -317,6 +602,18 @@ fn handle_ready_transition(
         RuntimeSessionState::Missing => {}
     }
 
+    if !has_run_capacity(state, options, &project) {
+        return queue_ticket(
+            board,
+            ticket_link,
+            &ticket,
+            project,
+            options,
+            state,
+            dirty_boards,
+        );
+    }
+
     if last_codex_session_id.is_some() {
         info!(
             "Resuming prior Codex conversation for '{}'",
@@ -404,6 +701,11 @@ fn handle_ready_transition(
                 record_file: String::new(),
                 launched_at_epoch_ms: now_epoch_ms()?,
                 codex_session_id: last_codex_session_id,
+                project: Some(project),
+                last_turn_status: None,
+                last_error: None,
+                last_activity_epoch_ms: None,
+                activity_fingerprint: None,
             },
         );
         return Ok(());
@@ -426,6 +728,7 @@ fn handle_ready_transition(
         context_overlay: RuntimeContextMetadata::default(),
         extra_runtime_args: Vec::new(),
         startup_delay_ms: options.startup_delay_ms,
+        restart_policy: AgentRestartPolicy::default(),
         command: options.command.clone(),
     })?;
 
@@ -468,12 +771,698 @@ fn handle_ready_transition(
             record_file: launch.record_file,
             launched_at_epoch_ms: launch.launched_at_epoch_ms,
             codex_session_id: launch.codex_session_id,
+            project: Some(project),
+            last_turn_status: None,
+            last_error: None,
+            last_activity_epoch_ms: None,
+            activity_fingerprint: None,
+        },
+    );
+
+    Ok(())
+}
+
+fn hold_blocked_ticket(
+    board: &mut BoardFile,
+    ticket_link: &str,
+    ticket: &TicketNote,
+    blocking: &[BlockingDependency],
+    options: &DispatchOptions,
+    state: &mut DispatchState,
+    dirty_boards: &mut BTreeSet<PathBuf>,
+) -> anyhow::Result<()> {
+    let waiting_on = describe_blocking(blocking);
+    let blocked_column = ticket.blocked_column();
+    info!(
+        "Holding '{}' in '{}' until dependencies finish: {}",
+        ticket.path.display(),
+        blocked_column,
+        waiting_on.join(", ")
+    );
+    if options.dry_run {
+        return Ok(());
+    }
+
+    let ticket_key = ticket.path.display().to_string();
+    board.move_card(ticket_link, &blocked_column)?;
+    dirty_boards.insert(board.path.clone());
+    append_ticket_progress(
+        &ticket.path,
+        &format!(
+            "Dispatch held the card in '{}' waiting on {}. It launches once they are done.",
+            blocked_column,
+            waiting_on.join(", ")
+        ),
+    )?;
+    update_ticket_status(&ticket.path, "blocked")?;
+    remember_ticket_runtime(state, &ticket_key, None, None, None, "blocked")?;
+    state.blocked.insert(
+        ticket_key,
+        BlockedTicketState {
+            ticket_link: ticket_link.to_string(),
+            board_path: board.path.display().to_string(),
+            blocked_at_epoch_ms: now_epoch_ms()?,
+            waiting_on,
         },
     );
+    Ok(())
+}
+
+fn process_blocked_tickets(
+    boards: &mut BTreeMap<PathBuf, BoardFile>,
+    state: &mut DispatchState,
+    options: &DispatchOptions,
+    cards: &[(String, String)],
+    dirty_boards: &mut BTreeSet<PathBuf>,
+) -> anyhow::Result<()> {
+    if state.blocked.is_empty() {
+        return Ok(());
+    }
+    let graph = TicketGraph::load(&options.vault_path, cards);
+
+    for (ticket_note, blocked) in state.blocked.clone() {
+        let board_path = PathBuf::from(&blocked.board_path);
+        let ticket_path = PathBuf::from(&ticket_note);
+        let Some(board) = boards.get_mut(&board_path) else {
+            state.blocked.remove(&ticket_note);
+            continue;
+        };
+        let ticket = TicketNote::load(&ticket_path)?;
+        let current_column = column_for_ticket(board, &blocked.ticket_link);
+        if current_column.as_deref().map(normalize_column)
+            != Some(normalize_column(&ticket.blocked_column()))
+        {
+            info!(
+                "Releasing dependency hold on '{}': card moved to '{}'",
+                ticket_note,
+                current_column.as_deref().unwrap_or("Removed")
+            );
+            state.blocked.remove(&ticket_note);
+            continue;
+        }
+
+        let blocking = graph.blocking_dependencies(&ticket_path);
+        if !blocking.is_empty() {
+            if let Some(entry) = state.blocked.get_mut(&ticket_note) {
+                entry.waiting_on = describe_blocking(&blocking);
+            }
+            continue;
+        }
+
+        info!(
+            "Dependencies for '{}' are done; launching",
+            ticket_path.display()
+        );
+        if options.dry_run {
+            continue;
+        }
+        state.blocked.remove(&ticket_note);
+        append_ticket_progress(
+            &ticket_path,
+            "Dependencies are done. Dispatch moved the card back to 'Ready for Codex'.",
+        )?;
+        board.move_card(&blocked.ticket_link, READY_FOR_CODEX)?;
+        dirty_boards.insert(board_path);
+        handle_ready_transition(
+            board,
+            &blocked.ticket_link,
+            options,
+            state,
+            cards,
+            dirty_boards,
+        )?;
+    }
+
+    Ok(())
+}
+
+fn queue_ticket(
+    board: &mut BoardFile,
+    ticket_link: &str,
+    ticket: &TicketNote,
+    project: String,
+    options: &DispatchOptions,
+    state: &mut DispatchState,
+    dirty_boards: &mut BTreeSet<PathBuf>,
+) -> anyhow::Result<()> {
+    let ticket_key = ticket.path.display().to_string();
+    let card = board.card(ticket_link);
+    let queued = QueuedTicketState {
+        ticket_link: ticket_link.to_string(),
+        board_path: board.path.display().to_string(),
+        project,
+        priority_rank: ticket.priority_rank(),
+        queued_at_epoch_ms: state
+            .queued
+            .get(&ticket_key)
+            .map(|queued| queued.queued_at_epoch_ms)
+            .unwrap_or(now_epoch_ms()?),
+        due: card.and_then(|card| card.due.clone()),
+        tags: card.map(|card| card.tags.clone()).unwrap_or_default(),
+    };
+    let position = queue_position(state, &ticket_key, &queued);
+    info!(
+        "Queueing '{}' at position {}: concurrency limit reached for project '{}'",
+        ticket.path.display(),
+        position,
+        queued.project
+    );
+    if options.dry_run {
+        return Ok(());
+    }
+
+    board.move_card(ticket_link, QUEUED_FOR_CODEX)?;
+    dirty_boards.insert(board.path.clone());
+    append_ticket_progress(
+        &ticket.path,
+        &format!(
+            "Dispatch queued the card at position {} in '{}' because the concurrency limit is reached.",
+            position, QUEUED_FOR_CODEX
+        ),
+    )?;
+    update_ticket_status(&ticket.path, "queued")?;
+    remember_ticket_runtime(state, &ticket_key, None, None, None, "queued")?;
+    state.queued.insert(ticket_key, queued);
+    Ok(())
+}
+
+fn process_queued_tickets(
+    boards: &mut BTreeMap<PathBuf, BoardFile>,
+    state: &mut DispatchState,
+    options: &DispatchOptions,
+    cards: &[(String, String)],
+    dirty_boards: &mut BTreeSet<PathBuf>,
+) -> anyhow::Result<()> {
+    for (ticket_note, queued) in ordered_queue(state) {
+        let board_path = PathBuf::from(&queued.board_path);
+        let Some(board) = boards.get_mut(&board_path) else {
+            state.queued.remove(&ticket_note);
+            continue;
+        };
+        let current_column = column_for_ticket(board, &queued.ticket_link);
+        if current_column.as_deref().map(normalize_column)
+            != Some(normalize_column(QUEUED_FOR_CODEX))
+        {
+            info!(
+                "Dropping '{}' from the dispatch queue: card moved to '{}'",
+                ticket_note,
+                current_column.as_deref().unwrap_or("Removed")
+            );
+            state.queued.remove(&ticket_note);
+            continue;
+        }
+        if !has_run_capacity(state, options, &queued.project) {
+            continue;
+        }
+
+        info!("Run slot free; launching queued '{}'", ticket_note);
+        if options.dry_run {
+            continue;
+        }
+        state.queued.remove(&ticket_note);
+        board.move_card(&queued.ticket_link, READY_FOR_CODEX)?;
+        dirty_boards.insert(board_path);
+        handle_ready_transition(
+            board,
+            &queued.ticket_link,
+            options,
+            state,
+            cards,
+            dirty_boards,
+        )?;
+    }
+    Ok(())
+}
+
+/// Moves failed cards back to Ready once their retry backoff has elapsed, dropping retries
+/// whose card the operator has moved out of the failure column.
+fn process_retries(
+    boards: &mut BTreeMap<PathBuf, BoardFile>,
+    state: &mut DispatchState,
+    options: &DispatchOptions,
+    cards: &[(String, String)],
+    dirty_boards: &mut BTreeSet<PathBuf>,
+) -> anyhow::Result<()> {
+    let now = now_epoch_ms()?;
+    for (ticket_note, retry) in state.retries.clone() {
+        let board_path = PathBuf::from(&retry.board_path);
+        let ticket_path = PathBuf::from(&ticket_note);
+        let Some(board) = boards.get_mut(&board_path) else {
+            state.retries.remove(&ticket_note);
+            continue;
+        };
+        let ticket = TicketNote::load(&ticket_path)?;
+        let current_column = column_for_ticket(board, &retry.ticket_link);
+        if current_column.as_deref().map(normalize_column)
+            != Some(normalize_column(&ticket.failure_column()))
+        {
+            info!(
+                "Canceling retry for '{}': card moved to '{}'",
+                ticket_note,
+                current_column.as_deref().unwrap_or("Removed")
+            );
+            state.retries.remove(&ticket_note);
+            continue;
+        }
+        if now < retry.not_before_epoch_ms {
+            continue;
+        }
+
+        info!(
+            "Retrying '{}' (attempt {} of {})",
+            ticket_path.display(),
+            retry.attempt,
+            ticket.max_retries()
+        );
+        if options.dry_run {
+            continue;
+        }
+        state.retries.remove(&ticket_note);
+        append_ticket_progress(
+            &ticket_path,
+            &format!(
+                "Dispatch retry {} of {}: moved the card back to 'Ready for Codex'.",
+                retry.attempt,
+                ticket.max_retries()
+            ),
+        )?;
+        board.move_card(&retry.ticket_link, READY_FOR_CODEX)?;
+        dirty_boards.insert(board_path);
+        handle_ready_transition(
+            board,
+            &retry.ticket_link,
+            options,
+            state,
+            cards,
+            dirty_boards,
+        )?;
+    }
 
     Ok(())
 }
 
+/// Boards holding a card whose scheduled run is due, so the pass loads them and can move it.
+fn due_schedule_board_paths(vault_path: &Path, state: &DispatchState, now: u128) -> Vec<PathBuf> {
+    state
+        .boards
+        .iter()
+        .filter(|(_, snapshot)| {
+            snapshot.card_columns.keys().any(|link| {
+                state
+                    .tickets
+                    .get(&resolve_wiki_link(vault_path, link).display().to_string())
+                    .and_then(|runtime| runtime.next_scheduled_run_epoch_ms)
+                    .is_some_and(|next| next <= now)
+            })
+        })
+        .map(|(board_path, _)| PathBuf::from(board_path))
+        .collect()
+}
+
+fn process_scheduled_tickets(
+    boards: &mut BTreeMap<PathBuf, BoardFile>,
+    state: &mut DispatchState,
+    options: &DispatchOptions,
+    cards: &[(String, String)],
+    dirty_boards: &mut BTreeSet<PathBuf>,
+) -> anyhow::Result<()> {
+    let now = now_epoch_ms()?;
+    for (ticket_link, _) in cards {
+        let ticket_path = resolve_wiki_link(&options.vault_path, ticket_link);
+        let ticket_key = ticket_path.display().to_string();
+        let Ok(ticket) = TicketNote::load(&ticket_path) else {
+            continue;
+        };
+        let schedule = ticket.schedule().unwrap_or_else(|error| {
+            warn!("{error:#}");
+            None
+        });
+        let Some(schedule) = schedule else {
+            if let Some(runtime) = state.tickets.get_mut(&ticket_key) {
+                runtime.schedule = None;
+                runtime.next_scheduled_run_epoch_ms = None;
+            }
+            continue;
+        };
+        let expression = ticket
+            .frontmatter
+            .schedule
+            .as_deref()
+            .map(str::trim)
+            .map(ToOwned::to_owned);
+        let next = next_schedule_epoch_ms(&schedule, now);
+        let in_flight = state.active_runs.contains_key(&ticket_key)
+            || state.queued.contains_key(&ticket_key)
+            || state.blocked.contains_key(&ticket_key)
+            || state.retries.contains_key(&ticket_key);
+
+        let runtime = state.tickets.entry(ticket_key.clone()).or_default();
+        if runtime.schedule != expression || runtime.next_scheduled_run_epoch_ms.is_none() {
+            runtime.schedule = expression;
+            runtime.next_scheduled_run_epoch_ms = next;
+            continue;
+        }
+        if runtime
+            .next_scheduled_run_epoch_ms
+            .is_some_and(|due| due > now)
+        {
+            continue;
+        }
+        if in_flight {
+            info!(
+                "Skipping scheduled run for '{}': the previous run is still in flight",
+                ticket_path.display()
+            );
+            runtime.next_scheduled_run_epoch_ms = next;
+            continue;
+        }
+        let Some(board) = boards
+            .values_mut()
+            .find(|board| column_for_ticket(board, ticket_link).is_some())
+        else {
+            continue;
+        };
+
+        info!(
+            "Launching scheduled run for '{}' ({})",
+            ticket_path.display(),
+            runtime.schedule.as_deref().unwrap_or_default()
+        );
+        if options.dry_run {
+            continue;
+        }
+        runtime.last_scheduled_run_epoch_ms = Some(now);
+        runtime.next_scheduled_run_epoch_ms = next;
+        runtime.last_codex_session_id = None;
+        append_ticket_progress(
+            &ticket_path,
+            &format!(
+                "Scheduled run (`{}`) moved the card to 'Ready for Codex' with a fresh Codex session.",
+                runtime.schedule.as_deref().unwrap_or_default()
+            ),
+        )?;
+        board.move_card(ticket_link, READY_FOR_CODEX)?;
+        dirty_boards.insert(board.path.clone());
+        handle_ready_transition(board, ticket_link, options, state, cards, dirty_boards)?;
+    }
+
+    Ok(())
+}
+
+fn next_schedule_epoch_ms(schedule: &cron::Schedule, after_epoch_ms: u128) -> Option<u128> {
+    let after = chrono::DateTime::from_timestamp_millis(i64::try_from(after_epoch_ms).ok()?)?
+        .with_timezone(&chrono::Local);
+    let next = schedule.after(&after).next()?;
+    u128::try_from(next.timestamp_millis()).ok()
+}
+
+pub fn dispatch_schedule(
+    vault_path: &Path,
+    state_file: Option<PathBuf>,
+) -> anyhow::Result<Vec<DispatchScheduleEntry>> {
+    let state_path = state_file.unwrap_or(default_state_file(vault_path)?);
+    let state = load_state(&state_path)?;
+    let mut entries = state
+        .tickets
+        .into_iter()
+        .filter_map(|(ticket, runtime)| {
+            Some(DispatchScheduleEntry {
+                ticket,
+                schedule: runtime.schedule?,
+                last_run_epoch_ms: runtime.last_scheduled_run_epoch_ms,
+                next_run_epoch_ms: runtime.next_scheduled_run_epoch_ms,
+                last_outcome: runtime.last_outcome,
+            })
+        })
+        .collect::<Vec<_>>();
+    entries.sort_by_key(|entry| {
+        (
+            entry.next_run_epoch_ms.unwrap_or(u128::MAX),
+            entry.ticket.clone(),
+        )
+    });
+    Ok(entries)
+}
+
+pub fn render_dispatch_schedule_output(
+    entries: &[DispatchScheduleEntry],
+    output: ControlPlaneOutput,
+) -> anyhow::Result<String> {
+    match output {
+        ControlPlaneOutput::Json => {
+            serde_json::to_string_pretty(entries).context("failed to encode dispatch schedule")
+        }
+        ControlPlaneOutput::Yaml => {
+            serde_yaml::to_string(entries).context("failed to encode dispatch schedule")
+        }
+        ControlPlaneOutput::Table => {
+            let now = now_epoch_ms()?;
+            let mut lines = vec!["TICKET\tSCHEDULE\tLAST RUN\tNEXT RUN\tOUTCOME".to_string()];
+            for entry in entries {
+                lines.push(format!(
+                    "{}\t{}\t{}\t{}\t{}",
+                    entry.ticket,
+                    entry.schedule,
+                    entry
+                        .last_run_epoch_ms
+                        .map_or("-".to_string(), |at| format!(
+                            "{} ago",
+                            compact_duration(now.saturating_sub(at))
+                        )),
+                    entry.next_run_epoch_ms.map_or("-".to_string(), |at| {
+                        if at <= now {
+                            "next pass".to_string()
+                        } else {
+                            format!("in {}", compact_duration(at - now))
+                        }
+                    }),
+                    entry.last_outcome.as_deref().unwrap_or("-")
+                ));
+            }
+            Ok(lines.join("\n"))
+        }
+    }
+}
+
+/// Where a ticket lands in the queue, counted in the same order `ordered_queue` launches.
+fn queue_position(state: &DispatchState, ticket_key: &str, queued: &QueuedTicketState) -> usize {
+    let mut probe = DispatchState {
+        queued: state.queued.clone(),
+        ..DispatchState::default()
+    };
+    probe.queued.insert(ticket_key.to_string(), queued.clone());
+    ordered_queue(&probe)
+        .iter()
+        .position(|(key, _)| key == ticket_key)
+        .unwrap_or_default()
+        + 1
+}
+
+/// Queue entries in launch order: priority first, then time spent waiting.
+fn ordered_queue(state: &DispatchState) -> Vec<(String, QueuedTicketState)> {
+    let mut queue = state
+        .queued
+        .iter()
+        .map(|(key, queued)| (key.clone(), queued.clone()))
+        .collect::<Vec<_>>();
+    queue.sort_by_key(|(key, queued)| {
+        (
+            queued.priority_rank,
+            queued.due.is_none(),
+            queued.due.clone(),
+            queued.queued_at_epoch_ms,
+            key.clone(),
+        )
+    });
+    queue
+}
+
+fn has_run_capacity(state: &DispatchState, options: &DispatchOptions, project: &str) -> bool {
+    let global_ok = options
+        .max_active_runs
+        .is_none_or(|limit| state.active_runs.len() < limit);
+    let project_ok = options.max_active_runs_per_project.is_none_or(|limit| {
+        state
+            .active_runs
+            .values()
+            .filter(|run| run_project(run) == project)
+            .count()
+            < limit
+    });
+    global_ok && project_ok
+}
+
+fn ticket_project(ticket: &TicketNote, board: &BoardFile) -> String {
+    ticket
+        .frontmatter
+        .project
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(ToOwned::to_owned)
+        .unwrap_or_else(|| board.path.display().to_string())
+}
+
+fn run_project(run: &ActiveRunState) -> &str {
+    run.project.as_deref().unwrap_or(&run.board_path)
+}
+
+/// Lists the dispatch queue with launch ETAs estimated from recent run durations.
+pub fn dispatch_queue(
+    vault_path: &Path,
+    state_file: Option<PathBuf>,
+) -> anyhow::Result<Vec<DispatchQueueEntry>> {
+    let state_path = state_file.unwrap_or(default_state_file(vault_path)?);
+    let state = load_state(&state_path)?;
+    Ok(estimate_queue(&state, now_epoch_ms()?))
+}
+
+/// Simulates the queue draining under the recorded limits, assuming every run takes the
+/// average of recent run durations.
+fn estimate_queue(state: &DispatchState, now: u128) -> Vec<DispatchQueueEntry> {
+    let durations = state
+        .tickets
+        .values()
+        .filter_map(|ticket| ticket.last_run_duration_ms)
+        .collect::<Vec<_>>();
+    let estimate = if durations.is_empty() {
+        DEFAULT_RUN_ESTIMATE_MS
+    } else {
+        durations.iter().sum::<u128>() / durations.len() as u128
+    };
+
+    // Each slot is (project, start, finish); a slot only occupies capacity while it runs.
+    let mut slots = state
+        .active_runs
+        .values()
+        .map(|run| {
+            (
+                run_project(run).to_string(),
+                run.launched_at_epoch_ms.min(now),
+                (run.launched_at_epoch_ms + estimate).max(now),
+            )
+        })
+        .collect::<Vec<_>>();
+    let mut entries = Vec::new();
+    for (position, (ticket_note, queued)) in ordered_queue(state).into_iter().enumerate() {
+        let mut candidates = slots
+            .iter()
+            .map(|(_, _, finish)| *finish)
+            .collect::<Vec<_>>();
+        candidates.push(now);
+        candidates.sort_unstable();
+        let start = candidates
+            .into_iter()
+            .find(|at| {
+                let running = slots
+                    .iter()
+                    .filter(|(_, start, finish)| start <= at && at < finish);
+                let global_ok = state
+                    .limits
+                    .max_active_runs
+                    .is_none_or(|limit| running.clone().count() < limit);
+                let project_ok = state
+                    .limits
+                    .max_active_runs_per_project
+                    .is_none_or(|limit| {
+                        running
+                            .filter(|(project, _, _)| *project == queued.project)
+                            .count()
+                            < limit
+                    });
+                global_ok && project_ok
+            })
+            .unwrap_or(now);
+        slots.push((queued.project.clone(), start, start + estimate));
+        entries.push(DispatchQueueEntry {
+            position: position + 1,
+            ticket: ticket_note,
+            project: queued.project,
+            priority_rank: queued.priority_rank,
+            due: queued.due,
+            tags: queued.tags,
+            queued_at_epoch_ms: queued.queued_at_epoch_ms,
+            eta_epoch_ms: start,
+        });
+    }
+    entries
+}
+
+pub fn render_dispatch_queue_output(
+    entries: &[DispatchQueueEntry],
+    output: ControlPlaneOutput,
+) -> anyhow::Result<String> {
+    match output {
+        ControlPlaneOutput::Json => {
+            serde_json::to_string_pretty(entries).context("failed to encode dispatch queue")
+        }
+        ControlPlaneOutput::Yaml => {
+            serde_yaml::to_string(entries).context("failed to encode dispatch queue")
+        }
+        ControlPlaneOutput::Table => {
+            let now = now_epoch_ms()?;
+            let mut lines =
+                vec!["POSITION\tTICKET\tPROJECT\tPRIORITY\tDUE\tWAITING\tETA".to_string()];
+            for entry in entries {
+                lines.push(format!(
+                    "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+                    entry.position,
+                    entry.ticket,
+                    entry.project,
+                    entry.priority_rank,
+                    entry.due.as_deref().unwrap_or("-"),
+                    compact_duration(now.saturating_sub(entry.queued_at_epoch_ms)),
+                    if entry.eta_epoch_ms <= now {
+                        "next pass".to_string()
+                    } else {
+                        format!("~{}", compact_duration(entry.eta_epoch_ms - now))
+                    }
+                ));
+            }
+            Ok(lines.join("\n"))
+        }
+    }
+}
+
+fn compact_duration(ms: u128) -> String {
+    let minutes = ms / 60_000;
+    match minutes {
+        0 => format!("{}s", ms / 1000),
+        1..=59 => format!("{minutes}m"),
+        _ => format!("{}h{:02}m", minutes / 60, minutes % 60),
+    }
+}
+
+fn describe_blocking(blocking: &[BlockingDependency]) -> Vec<String> {
+    blocking
+        .iter()
+        .map(|dependency| format!("[[{}]] ({})", dependency.link, dependency.reason))
+        .collect()
+}
+
+/// Every card the dispatcher knows about: loaded boards as they are now, others from the snapshot.
+fn known_cards(
+    boards: &BTreeMap<PathBuf, BoardFile>,
+    state: &DispatchState,
+) -> Vec<(String, String)> {
+    let mut cards = boards
+        .values()
+        .flat_map(BoardFile::card_positions)
+        .collect::<Vec<_>>();
+    for (board_path, snapshot) in &state.boards {
+        if boards.contains_key(Path::new(board_path)) {
+            continue;
+        }
+        cards.extend(
+            snapshot
+                .card_columns
+                .iter()
+                .map(|(link, column)| (link.clone(), column.clone())),
+        );
+    }
+    cards
+}
+
 fn process_manual_cancellations(
     boards: &mut BTreeMap<PathBuf, BoardFile>,
     state: &mut DispatchState,
@@ -530,7 +1519,7 @@ fn process_manual_cancellations(
     Ok(canceled_links)
 }
 
-fn parse_ticket_labels(labels: &[String]) -> anyhow::Result<BTreeMap<String, String>> {
+pub fn parse_ticket_labels(labels: &[String]) -> anyhow::Result<BTreeMap<String, String>> {
     let mut parsed = BTreeMap::new();
     for label in labels {
         let (key, value) = label.split_once('=').ok_or_else(|| {
@@ -624,7 +1613,13 @@ fn process_completed_runs(
     let active_runs = state.active_runs.clone();
     let hook_state = load_hook_state(&options.vault_path)?;
 
-    for (ticket_note, run) in active_runs {
+    for (ticket_note, mut run) in active_runs {
+        let context = runtime_context_for_namespace(&run.namespace)?;
+        observe_run_activity(&mut run, context.as_ref())?;
+        if let Some(active_run) = state.active_runs.get_mut(&ticket_note) {
+            *active_run = run.clone();
+        }
+
         match probe_runtime_session_state(&run.namespace)? {
             RuntimeSessionState::ActiveWork => {
                 let ticket_path = PathBuf::from(&ticket_note);
@@ -671,7 +1666,33 @@ fn process_completed_runs(
                         &run,
                         &ticket,
                         codex_session_id,
-                        "Run finished after Codex emitted a stop event.",
+                        classify_run_outcome(
+                            &run,
+                            "Run finished after Codex emitted a stop event.",
+                            false,
+                        ),
+                        dirty_boards,
+                    )?;
+                    completed.push(ticket_note);
+                } else if let Some(outcome) = exceeded_run_timeout(&run, &ticket)? {
+                    warn!("{}", outcome.reason);
+                    if !options.dry_run
+                        && let Err(error) = interrupt_runtime_session(&run.namespace, &run.agent)
+                    {
+                        warn!(
+                            "Failed to interrupt timed-out run '{}': {error}",
+                            run.namespace
+                        );
+                    }
+                    finalize_run(
+                        boards,
+                        state,
+                        options,
+                        &ticket_note,
+                        &run,
+                        &ticket,
+                        codex_session_id,
+                        outcome,
                         dirty_boards,
                     )?;
                     completed.push(ticket_note);
@@ -700,8 +1721,16 @@ fn process_completed_runs(
                     &ticket,
                     codex_session_id,
                     match runtime_state {
-                        RuntimeSessionState::Idle => "Run finished after the runtime became idle.",
-                        RuntimeSessionState::Missing => "Run finished after the runtime stopped.",
+                        RuntimeSessionState::Idle => classify_run_outcome(
+                            &run,
+                            "Run finished after the runtime became idle.",
+                            false,
+                        ),
+                        RuntimeSessionState::Missing => classify_run_outcome(
+                            &run,
+                            "Run finished after the runtime stopped.",
+                            true,
+                        ),
                         _ => unreachable!(),
                     },
                     dirty_boards,
@@ -718,6 +1747,134 @@ fn process_completed_runs(
     Ok(())
 }
 
+/// Tracks the last time the runtime showed progress, from its context fields changing
+/// or its event log, transcript, or record file being written.
+fn observe_run_activity(
+    run: &mut ActiveRunState,
+    context: Option<&RuntimeContextMetadata>,
+) -> anyhow::Result<()> {
+    let now = now_epoch_ms()?;
+    let mut last_activity = run
+        .last_activity_epoch_ms
+        .unwrap_or(run.launched_at_epoch_ms);
+    let mut activity_files = vec![run.record_file.clone()];
+
+    if let Some(context) = context {
+        run.last_turn_status = context.turn_status.clone();
+        run.last_error = context.last_error.clone();
+        let fingerprint = [
+            &context.turn_id,
+            &context.turn_status,
+            &context.last_activity,
+            &context.live_message,
+        ]
+        .into_iter()
+        .flatten()
+        .cloned()
+        .collect::<Vec<_>>()
+        .join("|");
+        if run.activity_fingerprint.as_deref() != Some(fingerprint.as_str()) {
+            run.activity_fingerprint = Some(fingerprint);
+            last_activity = now;
+        }
+        activity_files.extend(context.event_log_path.clone());
+        activity_files.extend(context.transcript_path.clone());
+    }
+
+    for path in activity_files.iter().filter(|path| !path.is_empty()) {
+        if let Some(modified_epoch_ms) = fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .map(|elapsed| elapsed.as_millis())
+        {
+            last_activity = last_activity.max(modified_epoch_ms.min(now));
+        }
+    }
+    run.last_activity_epoch_ms = Some(last_activity);
+    Ok(())
+}
+
+/// Returns a timed-out outcome once a run passes its ticket's wall-clock or idle limit.
+fn exceeded_run_timeout(
+    run: &ActiveRunState,
+    ticket: &TicketNote,
+) -> anyhow::Result<Option<RunOutcome>> {
+    let now = now_epoch_ms()?;
+    if let Some(limit) = ticket.run_timeout()
+        && now.saturating_sub(run.launched_at_epoch_ms) > limit.as_millis()
+    {
+        return Ok(Some(RunOutcome {
+            kind: RunOutcomeKind::TimedOut,
+            reason: format!(
+                "Dispatch interrupted the run after {} minute(s), exceeding codex_timeout_minutes.",
+                limit.as_secs() / 60
+            ),
+        }));
+    }
+    let last_activity = run
+        .last_activity_epoch_ms
+        .unwrap_or(run.launched_at_epoch_ms);
+    if let Some(limit) = ticket.idle_timeout()
+        && now.saturating_sub(last_activity) > limit.as_millis()
+    {
+        return Ok(Some(RunOutcome {
+            kind: RunOutcomeKind::TimedOut,
+            reason: format!(
+                "Dispatch interrupted the run after {} minute(s) without activity, exceeding codex_idle_timeout_minutes.",
+                limit.as_secs() / 60
+            ),
+        }));
+    }
+    Ok(None)
+}
+
+/// Classifies how a run ended from the turn status and last error observed on the runtime.
+/// A runtime that vanished mid-turn or after reporting an error is treated as a crash.
+fn classify_run_outcome(run: &ActiveRunState, ended: &str, runtime_missing: bool) -> RunOutcome {
+    let error = run
+        .last_error
+        .as_deref()
+        .map(str::trim)
+        .filter(|error| !error.is_empty());
+    let (kind, reason) = match run.last_turn_status.as_deref() {
+        Some("failed") => (
+            RunOutcomeKind::TurnFailed,
+            format!(
+                "Codex turn failed: {}.",
+                error.unwrap_or("no error was reported")
+            ),
+        ),
+        Some("interrupted") => (
+            RunOutcomeKind::Interrupted,
+            "Codex turn was interrupted before it completed.".to_string(),
+        ),
+        _ if error.is_some_and(|error| error.to_ascii_lowercase().contains("timed out")) => (
+            RunOutcomeKind::TimedOut,
+            format!("Codex run timed out: {}.", error.unwrap_or_default()),
+        ),
+        Some("inProgress") if runtime_missing => (
+            RunOutcomeKind::RuntimeCrashed,
+            "Runtime stopped while a Codex turn was still in progress.".to_string(),
+        ),
+        _ if runtime_missing && error.is_some() => (
+            RunOutcomeKind::RuntimeCrashed,
+            format!(
+                "Runtime stopped after an error: {}.",
+                error.unwrap_or_default()
+            ),
+        ),
+        _ => (RunOutcomeKind::Success, ended.to_string()),
+    };
+    RunOutcome { kind, reason }
+}
+
+/// The retry number to schedule after a run ended as `kind`, or `None` once the ticket's
+/// `codex_max_retries` is used up or the outcome is not worth retrying.
+fn next_retry_attempt(kind: RunOutcomeKind, attempts: u32, max_retries: u32) -> Option<u32> {
+    (kind.is_retryable() && attempts < max_retries).then_some(attempts + 1)
+}
+
 fn finalize_run(
     boards: &mut BTreeMap<PathBuf, BoardFile>,
     state: &mut DispatchState,
@@ -726,33 +1883,53 @@ fn finalize_run(
     run: &ActiveRunState,
     ticket: &TicketNote,
     codex_session_id: Option<String>,
-    completion_reason: &str,
+    outcome: RunOutcome,
     dirty_boards: &mut BTreeSet<PathBuf>,
 ) -> anyhow::Result<()> {
-    let completion_column = ticket.completion_column();
-    let completion_status = ticket.completion_status();
+    let failed = outcome.kind != RunOutcomeKind::Success;
+    let (target_column, target_status) = match outcome.kind {
+        RunOutcomeKind::Success => (ticket.completion_column(), ticket.completion_status()),
+        RunOutcomeKind::TimedOut => (ticket.timeout_column(), "timed_out".to_string()),
+        _ => (ticket.failure_column(), "failed".to_string()),
+    };
     let finish_mode = ticket.finish_session_policy()?.to_string();
+    let attempts = state
+        .tickets
+        .get(ticket_note)
+        .map(|runtime| runtime.retry_count)
+        .unwrap_or(0);
+    let retry_attempt = next_retry_attempt(outcome.kind, attempts, ticket.max_retries());
 
     info!(
-        "Finalizing Codex run '{}' into column '{}' with status '{}'",
-        run.namespace, completion_column, completion_status
+        "Finalizing Codex run '{}' as '{}' into column '{}' with status '{}'",
+        run.namespace,
+        outcome.kind.as_str(),
+        target_column,
+        target_status
     );
 
+    let mut summary = format!(
+        "{} Board moved to '{}' and status set to '{}'.",
+        outcome.reason, target_column, target_status
+    );
+    if let Some(attempt) = retry_attempt {
+        summary.push_str(&format!(
+            " Retry {} of {} scheduled in {}s.",
+            attempt,
+            ticket.max_retries(),
+            ticket.retry_backoff_seconds(attempt)
+        ));
+    }
+
     if !options.dry_run {
         let board_path = PathBuf::from(&run.board_path);
         let board = boards
             .get_mut(&board_path)
             .ok_or_else(|| anyhow!("board '{}' is not loaded", board_path.display()))?;
-        board.move_card(&run.ticket_link, &completion_column)?;
+        board.move_card(&run.ticket_link, &target_column)?;
         dirty_boards.insert(board.path.clone());
-        append_ticket_progress(
-            &ticket.path,
-            &format!(
-                "{} Board moved to '{}' and status set to '{}'.",
-                completion_reason, completion_column, completion_status
-            ),
-        )?;
-        update_ticket_status(&ticket.path, &completion_status)?;
+        append_ticket_progress(&ticket.path, &summary)?;
+        update_ticket_status(&ticket.path, &target_status)?;
         if finish_mode == "close" {
             delete_runtime_session_if_exists(&run.namespace)?;
         }
@@ -761,10 +1938,10 @@ fn finalize_run(
     append_ticket_mission_event(
         ticket,
         "verify",
-        &completion_status,
+        &target_status,
         &format!(
             "{} Runtime namespace '{}' reached '{}'.",
-            completion_reason, run.namespace, completion_status
+            outcome.reason, run.namespace, target_status
         ),
         Some(&run.namespace),
         None,
@@ -780,8 +1957,33 @@ fn finalize_run(
         Some(run.namespace.clone()),
         record_file_option(&run.record_file),
         codex_session_id,
-        &completion_status,
+        if failed {
+            outcome.kind.as_str()
+        } else {
+            &target_status
+        },
     )?;
+    if let Some(runtime) = state.tickets.get_mut(ticket_note) {
+        runtime.last_run_duration_ms =
+            Some(now_epoch_ms()?.saturating_sub(run.launched_at_epoch_ms));
+        if !failed {
+            runtime.retry_count = 0;
+        } else if let Some(attempt) = retry_attempt {
+            runtime.retry_count = attempt;
+        }
+    }
+    if let Some(attempt) = retry_attempt {
+        state.retries.insert(
+            ticket_note.to_string(),
+            RetryTicketState {
+                ticket_link: run.ticket_link.clone(),
+                board_path: run.board_path.clone(),
+                attempt,
+                not_before_epoch_ms: now_epoch_ms()?
+                    + u128::from(ticket.retry_backoff_seconds(attempt)) * 1000,
+            },
+        );
+    }
 
     Ok(())
 }
@@ -926,7 +2128,7 @@ fn save_state(path: &Path, state: &DispatchState) -> anyhow::Result<()> {
     }
 
     let raw = serde_json::to_string_pretty(state).context("failed to serialize dispatch state")?;
-    fs::write(path, raw).with_context(|| format!("failed to write '{}'", path.display()))
+    atomic_write_string(path, &raw)
 }
 
 fn stop_hook_epoch_ms(
@@ -1039,8 +2241,15 @@ fn update_ticket_status(path: &Path, new_status: &str) -> anyhow::Result<()> {
 }
 
 fn update_ticket_frontmatter_value(path: &Path, field: &str, value: &str) -> anyhow::Result<()> {
-    let raw = fs::read_to_string(path)
-        .with_context(|| format!("failed to read ticket '{}'", path.display()))?;
+    rewrite_ticket(path, |raw| set_frontmatter_value(path, raw, field, value))
+}
+
+fn set_frontmatter_value(
+    path: &Path,
+    raw: &str,
+    field: &str,
+    value: &str,
+) -> anyhow::Result<String> {
     let mut lines = raw.lines().map(ToOwned::to_owned).collect::<Vec<_>>();
 
     if lines.first().map(|line| line.trim()) != Some("---") {
@@ -1077,13 +2286,14 @@ fn update_ticket_frontmatter_value(path: &Path, field: &str, value: &str) -> any
 
     let mut rendered = lines.join("\n");
     rendered.push('\n');
-    fs::write(path, rendered)
-        .with_context(|| format!("failed to write ticket '{}'", path.display()))
+    Ok(rendered)
 }
 
 fn append_ticket_progress(path: &Path, message: &str) -> anyhow::Result<()> {
-    let raw = fs::read_to_string(path)
-        .with_context(|| format!("failed to read ticket '{}'", path.display()))?;
+    rewrite_ticket(path, |raw| Ok(append_progress_line(raw, message)))
+}
+
+fn append_progress_line(raw: &str, message: &str) -> String {
     let mut lines = raw.lines().map(ToOwned::to_owned).collect::<Vec<_>>();
     let progress_line = format!("- {}", message.trim());
 
@@ -1117,8 +2327,35 @@ fn append_ticket_progress(path: &Path, message: &str) -> anyhow::Result<()> {
 
     let mut rendered = lines.join("\n");
     rendered.push('\n');
-    fs::write(path, rendered)
-        .with_context(|| format!("failed to write ticket '{}'", path.display()))
+    rendered
+}
+
+/// Applies `edit` to the latest note and writes it atomically, redoing the edit when the
+/// note changed on disk (an Obsidian save or sync) while it was being computed.
+fn rewrite_ticket(
+    path: &Path,
+    mut edit: impl FnMut(&str) -> anyhow::Result<String>,
+) -> anyhow::Result<()> {
+    let read = || {
+        fs::read_to_string(path)
+            .with_context(|| format!("failed to read ticket '{}'", path.display()))
+    };
+    for _ in 0..TICKET_WRITE_ATTEMPTS {
+        let raw = read()?;
+        let updated = edit(&raw)?;
+        if read()? != raw {
+            continue;
+        }
+        if updated != raw {
+            atomic_write_string(path, &updated)
+                .with_context(|| format!("failed to write ticket '{}'", path.display()))?;
+        }
+        return Ok(());
+    }
+    Err(anyhow!(
+        "ticket '{}' kept changing on disk while dispatch was updating it",
+        path.display()
+    ))
 }
 
 fn now_epoch_ms() -> anyhow::Result<u128> {
@@ -1127,3 +2364,247 @@ fn now_epoch_ms() -> anyhow::Result<u128> {
         .context("system clock is before UNIX_EPOCH")?
         .as_millis())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ticket::TicketFrontmatter;
+
+    fn active_run(turn_status: Option<&str>, error: Option<&str>) -> ActiveRunState {
+        ActiveRunState {
+            ticket_note: "/vault/Tickets/demo.md".to_string(),
+            ticket_link: "Tickets/demo".to_string(),
+            board_path: "/vault/Board.md".to_string(),
+            namespace: "codex-demo".to_string(),
+            agent: "agent1".to_string(),
+            record_file: String::new(),
+            launched_at_epoch_ms: 0,
+            codex_session_id: None,
+            project: None,
+            last_turn_status: turn_status.map(ToOwned::to_owned),
+            last_error: error.map(ToOwned::to_owned),
+            last_activity_epoch_ms: None,
+            activity_fingerprint: None,
+        }
+    }
+
+    #[test]
+    fn run_outcomes_classify_failures_crashes_and_timeouts() {
+        let classify = |turn_status, error, runtime_missing| {
+            classify_run_outcome(
+                &active_run(turn_status, error),
+                "Codex finished.",
+                runtime_missing,
+            )
+            .kind
+        };
+
+        assert_eq!(
+            classify(Some("failed"), Some("boom"), false),
+            RunOutcomeKind::TurnFailed
+        );
+        assert_eq!(
+            classify(Some("interrupted"), None, true),
+            RunOutcomeKind::Interrupted
+        );
+        assert_eq!(
+            classify(Some("completed"), Some("Request Timed Out"), false),
+            RunOutcomeKind::TimedOut
+        );
+        assert_eq!(
+            classify(Some("inProgress"), None, true),
+            RunOutcomeKind::RuntimeCrashed
+        );
+        assert_eq!(
+            classify(Some("inProgress"), None, false),
+            RunOutcomeKind::Success
+        );
+        assert_eq!(
+            classify(Some("completed"), Some("stream closed"), true),
+            RunOutcomeKind::RuntimeCrashed
+        );
+        assert_eq!(
+            classify(Some("completed"), Some("  "), true),
+            RunOutcomeKind::Success
+        );
+        let success = classify_run_outcome(&active_run(None, None), "Codex finished.", false);
+        assert_eq!(success.kind, RunOutcomeKind::Success);
+        assert_eq!(success.reason, "Codex finished.");
+    }
+
+    #[test]
+    fn retries_are_gated_by_outcome_and_max_retries() {
+        assert_eq!(
+            next_retry_attempt(RunOutcomeKind::TurnFailed, 0, 2),
+            Some(1)
+        );
+        assert_eq!(
+            next_retry_attempt(RunOutcomeKind::RuntimeCrashed, 1, 2),
+            Some(2)
+        );
+        assert_eq!(next_retry_attempt(RunOutcomeKind::TurnFailed, 2, 2), None);
+        assert_eq!(next_retry_attempt(RunOutcomeKind::TurnFailed, 0, 0), None);
+        assert_eq!(next_retry_attempt(RunOutcomeKind::TimedOut, 0, 2), None);
+        assert_eq!(next_retry_attempt(RunOutcomeKind::Interrupted, 0, 2), None);
+        assert_eq!(next_retry_attempt(RunOutcomeKind::Success, 0, 2), None);
+
+        let mut ticket = TicketNote {
+            path: PathBuf::from("/vault/Tickets/demo.md"),
+            frontmatter: TicketFrontmatter::default(),
+            title: "Demo".to_string(),
+            sections: BTreeMap::new(),
+        };
+        assert_eq!(ticket.max_retries(), 0);
+        assert_eq!(ticket.retry_backoff_seconds(1), 60);
+        ticket.frontmatter.codex_max_retries = Some(3);
+        ticket.frontmatter.codex_retry_backoff_seconds = Some(30);
+        assert_eq!(
+            (1..=3)
+                .map(|attempt| ticket.retry_backoff_seconds(attempt))
+                .collect::<Vec<_>>(),
+            vec![30, 60, 120]
+        );
+    }
+
+    fn queued(project: &str, priority_rank: u32, due: Option<&str>, at: u128) -> QueuedTicketState {
+        QueuedTicketState {
+            ticket_link: String::new(),
+            board_path: "/vault/Board.md".to_string(),
+            project: project.to_string(),
+            priority_rank,
+            queued_at_epoch_ms: at,
+            due: due.map(ToOwned::to_owned),
+            tags: Vec::new(),
+        }
+    }
+
+    fn limits(global: Option<usize>, per_project: Option<usize>) -> DispatchOptions {
+        DispatchOptions {
+            backend: SessionBackend::Native,
+            driver: CodexRuntimeDriver::AppServer,
+            vault_path: PathBuf::from("/vault"),
+            boards: Vec::new(),
+            interval_seconds: 5,
+            once: true,
+            watch: false,
+            dry_run: true,
+            state_file: None,
+            agent: "codex".to_string(),
+            agents: 1,
+            startup_delay_ms: 0,
+            max_active_runs: global,
+            max_active_runs_per_project: per_project,
+            command: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn queue_orders_by_priority_due_date_then_wait() {
+        let mut state = DispatchState::default();
+        state
+            .queued
+            .insert("late".to_string(), queued("A", 2, None, 1));
+        state
+            .queued
+            .insert("undated".to_string(), queued("A", 1, None, 2));
+        state.queued.insert(
+            "due-soon".to_string(),
+            queued("B", 1, Some("2026-10-20"), 5),
+        );
+        state.queued.insert(
+            "due-later".to_string(),
+            queued("A", 1, Some("2026-11-01"), 3),
+        );
+        state
+            .queued
+            .insert("undated-newer".to_string(), queued("B", 1, None, 4));
+
+        let order = ordered_queue(&state)
+            .into_iter()
+            .map(|(key, _)| key)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            order,
+            vec!["due-soon", "due-later", "undated", "undated-newer", "late"]
+        );
+        assert_eq!(queue_position(&state, "new", &queued("A", 1, None, 6)), 5);
+        assert_eq!(queue_position(&state, "new", &queued("A", 0, None, 6)), 1);
+        let undated = state.queued["undated"].clone();
+        assert_eq!(queue_position(&state, "undated", &undated), 3);
+    }
+
+    #[test]
+    fn run_capacity_honors_global_and_per_project_limits() {
+        let mut state = DispatchState::default();
+        for (key, project) in [("a1", "A"), ("a2", "A"), ("b1", "B")] {
+            let mut run = active_run(None, None);
+            run.project = Some(project.to_string());
+            state.active_runs.insert(key.to_string(), run);
+        }
+
+        assert!(has_run_capacity(&state, &limits(None, None), "A"));
+        assert!(!has_run_capacity(&state, &limits(Some(3), None), "C"));
+        assert!(has_run_capacity(&state, &limits(Some(4), None), "C"));
+        assert!(!has_run_capacity(&state, &limits(None, Some(2)), "A"));
+        assert!(has_run_capacity(&state, &limits(None, Some(2)), "B"));
+        assert!(!has_run_capacity(&state, &limits(Some(3), Some(2)), "B"));
+    }
+
+    #[test]
+    fn queue_etas_follow_limits_and_average_run_duration() {
+        let minute = 60 * 1000;
+        let now = 100 * minute;
+        let mut state = DispatchState {
+            limits: DispatchLimits {
+                max_active_runs: Some(2),
+                max_active_runs_per_project: Some(1),
+            },
+            ..DispatchState::default()
+        };
+        for (key, duration) in [("done-1", 20), ("done-2", 40)] {
+            state.tickets.insert(
+                key.to_string(),
+                TicketRuntimeState {
+                    last_run_duration_ms: Some(duration * minute),
+                    ..TicketRuntimeState::default()
+                },
+            );
+        }
+        let mut run = active_run(None, None);
+        run.project = Some("A".to_string());
+        run.launched_at_epoch_ms = now - 10 * minute;
+        state.active_runs.insert("running".to_string(), run);
+        state
+            .queued
+            .insert("a-first".to_string(), queued("A", 1, None, 1));
+        state
+            .queued
+            .insert("b-first".to_string(), queued("B", 1, None, 2));
+        state
+            .queued
+            .insert("a-second".to_string(), queued("A", 2, None, 3));
+
+        let etas = estimate_queue(&state, now)
+            .into_iter()
+            .map(|entry| (entry.ticket, (entry.eta_epoch_ms - now) / minute))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            etas,
+            vec![
+                ("a-first".to_string(), 20),
+                ("b-first".to_string(), 0),
+                ("a-second".to_string(), 50),
+            ]
+        );
+
+        let idle = DispatchState {
+            queued: state.queued.clone(),
+            ..DispatchState::default()
+        };
+        assert!(
+            estimate_queue(&idle, now)
+                .iter()
+                .all(|entry| entry.eta_epoch_ms == now)
+        );
+    }
+}
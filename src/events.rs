@@ -0,0 +1,357 @@
+use crate::native::NativeSessionMetadata;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, mpsc};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+const EVENT_BACKLOG_LIMIT: usize = 256;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum RuntimeEventKind {
+    SessionStarted,
+    SessionExited,
+    AgentStarted,
+    AgentExited,
+    AgentRestarted,
+    TurnStarted,
+    TurnCompleted,
+    ServerRequestPending,
+    ErrorRecorded,
+    SubagentUpdated,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuntimeEvent {
+    pub kind: RuntimeEventKind,
+    pub namespace: String,
+    pub timestamp_epoch_ms: u128,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl RuntimeEvent {
+    pub fn new(kind: RuntimeEventKind, namespace: &str) -> Self {
+        Self {
+            kind,
+            namespace: namespace.to_string(),
+            timestamp_epoch_ms: now_epoch_ms(),
+            node: None,
+            agent: None,
+            id: None,
+            status: None,
+            detail: None,
+        }
+    }
+}
+
+struct EventSubscriber {
+    kinds: Vec<RuntimeEventKind>,
+    sender: mpsc::Sender<RuntimeEvent>,
+}
+
+/// Fans session events out to `Subscribe` connections and keeps a short backlog.
+#[derive(Default)]
+pub struct RuntimeEventBus {
+    backlog: Mutex<VecDeque<RuntimeEvent>>,
+    subscribers: Mutex<Vec<EventSubscriber>>,
+    active: Arc<AtomicUsize>,
+}
+
+/// Receiving side of a subscription; dropping it marks the stream as finished.
+pub struct RuntimeEventSubscription {
+    pub backlog: Vec<RuntimeEvent>,
+    pub receiver: mpsc::Receiver<RuntimeEvent>,
+    active: Arc<AtomicUsize>,
+}
+
+impl Drop for RuntimeEventSubscription {
+    fn drop(&mut self) {
+        self.active.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl RuntimeEventBus {
+    pub fn subscribe(&self, kinds: Vec<RuntimeEventKind>) -> RuntimeEventSubscription {
+        let backlog = self
+            .backlog
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|event| kinds.is_empty() || kinds.contains(&event.kind))
+            .cloned()
+            .collect();
+        let (sender, receiver) = mpsc::channel();
+        self.active.fetch_add(1, Ordering::SeqCst);
+        self.subscribers
+            .lock()
+            .unwrap()
+            .push(EventSubscriber { kinds, sender });
+        RuntimeEventSubscription {
+            backlog,
+            receiver,
+            active: Arc::clone(&self.active),
+        }
+    }
+
+    pub fn publish(&self, events: Vec<RuntimeEvent>) {
+        if events.is_empty() {
+            return;
+        }
+        {
+            let mut backlog = self.backlog.lock().unwrap();
+            backlog.extend(events.iter().cloned());
+            while backlog.len() > EVENT_BACKLOG_LIMIT {
+                backlog.pop_front();
+            }
+        }
+        let mut subscribers = self.subscribers.lock().unwrap();
+        subscribers.retain(|subscriber| {
+            events
+                .iter()
+                .filter(|event| {
+                    subscriber.kinds.is_empty() || subscriber.kinds.contains(&event.kind)
+                })
+                .all(|event| subscriber.sender.send(event.clone()).is_ok())
+        });
+    }
+
+    /// Ends every subscription and gives the streams up to `timeout` to flush.
+    pub fn close(&self, timeout: Duration) {
+        self.subscribers.lock().unwrap().clear();
+        let deadline = Instant::now() + timeout;
+        while self.active.load(Ordering::SeqCst) > 0 && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+}
+
+/// Writes a subscription's backlog and, when following, live events until the bus closes
+/// or the client goes away.
+pub fn pump_subscription(
+    subscription: RuntimeEventSubscription,
+    follow: bool,
+    mut send: impl FnMut(RuntimeEvent) -> anyhow::Result<()>,
+) -> anyhow::Result<()> {
+    for event in subscription.backlog.iter().cloned() {
+        send(event)?;
+    }
+    if !follow {
+        return Ok(());
+    }
+    while let Ok(event) = subscription.receiver.recv() {
+        send(event)?;
+    }
+    Ok(())
+}
+
+/// Derives the events implied by the change between two metadata snapshots.
+pub fn diff_session_events(
+    before: &NativeSessionMetadata,
+    after: &NativeSessionMetadata,
+) -> Vec<RuntimeEvent> {
+    let namespace = after.namespace.as_str();
+    let mut events = Vec::new();
+
+    let previous_agents = before
+        .agents
+        .iter()
+        .map(|agent| (agent.name.as_str(), agent))
+        .collect::<BTreeMap<_, _>>();
+    for agent in &after.agents {
+        let previous = previous_agents.get(agent.name.as_str());
+        let agent_event = |kind| RuntimeEvent {
+            agent: Some(agent.name.clone()),
+            ..RuntimeEvent::new(kind, namespace)
+        };
+        match previous {
+            None if agent.running => events.push(RuntimeEvent {
+                detail: Some(format!("pid {}", agent.pid)),
+                ..agent_event(RuntimeEventKind::AgentStarted)
+            }),
+            Some(previous) if previous.running && !agent.running => events.push(RuntimeEvent {
+                status: agent.exit_code.map(|code| code.to_string()),
+                ..agent_event(RuntimeEventKind::AgentExited)
+            }),
+            Some(previous) if agent.restarts > previous.restarts => events.push(RuntimeEvent {
+                status: agent.last_exit_code.map(|code| code.to_string()),
+                detail: Some(format!("restart {} pid {}", agent.restarts, agent.pid)),
+                ..agent_event(RuntimeEventKind::AgentRestarted)
+            }),
+            _ => {}
+        }
+    }
+    for previous in before.agents.iter().filter(|agent| agent.running) {
+        if !after.agents.iter().any(|agent| agent.name == previous.name) {
+            events.push(RuntimeEvent {
+                agent: Some(previous.name.clone()),
+                detail: Some("removed".to_string()),
+                ..RuntimeEvent::new(RuntimeEventKind::AgentExited, namespace)
+            });
+        }
+    }
+
+    let empty = Default::default();
+    let old = before.context.as_ref().unwrap_or(&empty);
+    let new = after.context.as_ref().unwrap_or(&empty);
+
+    let was_active = old.turn_status.as_deref() == Some("inProgress");
+    let is_active = new.turn_status.as_deref() == Some("inProgress");
+    if is_active && (!was_active || old.turn_id != new.turn_id) {
+        events.push(RuntimeEvent {
+            id: new.turn_id.clone(),
+            status: new.turn_status.clone(),
+            ..RuntimeEvent::new(RuntimeEventKind::TurnStarted, namespace)
+        });
+    } else if was_active && !is_active {
+        events.push(RuntimeEvent {
+            id: old.turn_id.clone().or_else(|| new.turn_id.clone()),
+            status: new.turn_status.clone(),
+            ..RuntimeEvent::new(RuntimeEventKind::TurnCompleted, namespace)
+        });
+    }
+
+    for request in &new.server_requests {
+        let was_pending = old
+            .server_requests
+            .iter()
+            .any(|previous| previous.id == request.id && previous.status == "pending");
+        if request.status == "pending" && !was_pending {
+            events.push(RuntimeEvent {
+                id: Some(request.id.clone()),
+                status: Some(request.status.clone()),
+                detail: Some(match request.detail.as_deref() {
+                    Some(detail) => format!("{}: {}", request.method, detail),
+                    None => request.method.clone(),
+                }),
+                ..RuntimeEvent::new(RuntimeEventKind::ServerRequestPending, namespace)
+            });
+        }
+    }
+
+    if new.last_error.is_some() && new.last_error != old.last_error {
+        events.push(RuntimeEvent {
+            detail: new.last_error.clone(),
+            ..RuntimeEvent::new(RuntimeEventKind::ErrorRecorded, namespace)
+        });
+    }
+
+    for subagent in &new.subagents {
+        let changed = old
+            .subagents
+            .iter()
+            .find(|previous| previous.thread_id == subagent.thread_id)
+            .is_none_or(|previous| {
+                previous.status != subagent.status
+                    || previous.updated_at_epoch_ms != subagent.updated_at_epoch_ms
+            });
+        if changed {
+            events.push(RuntimeEvent {
+                id: Some(subagent.thread_id.clone()),
+                status: Some(subagent.status.clone()),
+                detail: Some(subagent.tool.clone()),
+                ..RuntimeEvent::new(RuntimeEventKind::SubagentUpdated, namespace)
+            });
+        }
+    }
+
+    events
+}
+
+fn now_epoch_ms() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::native::{NativeAgentMetadata, RuntimeContextMetadata, RuntimeServerRequest};
+
+    fn snapshot(running: bool, turn_status: &str, pending: &[&str]) -> NativeSessionMetadata {
+        NativeSessionMetadata {
+            namespace: "ops".to_string(),
+            backend: "codex-app".to_string(),
+            created_at_epoch_ms: 1,
+            working_directory: None,
+            shell_command: "codex".to_string(),
+            context: Some(RuntimeContextMetadata {
+                turn_id: Some("turn-1".to_string()),
+                turn_status: Some(turn_status.to_string()),
+                server_requests: pending
+                    .iter()
+                    .map(|id| RuntimeServerRequest {
+                        id: id.to_string(),
+                        method: "item/commandExecution/requestApproval".to_string(),
+                        status: "pending".to_string(),
+                        ..RuntimeServerRequest::default()
+                    })
+                    .collect(),
+                ..RuntimeContextMetadata::default()
+            }),
+            agents: vec![NativeAgentMetadata {
+                name: "agent0".to_string(),
+                pid: 7,
+                running,
+                exit_code: (!running).then_some(0),
+                restarts: 0,
+                last_exit_code: None,
+                idle: false,
+            }],
+            clients: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn metadata_diff_reports_turns_requests_and_exits_once() {
+        let idle = snapshot(true, "completed", &[]);
+        let working = snapshot(true, "inProgress", &["req-1"]);
+        let kinds = |events: Vec<RuntimeEvent>| {
+            events
+                .into_iter()
+                .map(|event| event.kind)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            kinds(diff_session_events(&idle, &working)),
+            vec![
+                RuntimeEventKind::TurnStarted,
+                RuntimeEventKind::ServerRequestPending
+            ]
+        );
+        assert!(diff_session_events(&working, &working).is_empty());
+        assert_eq!(
+            kinds(diff_session_events(
+                &working,
+                &snapshot(false, "completed", &["req-1"])
+            )),
+            vec![
+                RuntimeEventKind::AgentExited,
+                RuntimeEventKind::TurnCompleted
+            ]
+        );
+
+        let bus = RuntimeEventBus::default();
+        let subscription = bus.subscribe(vec![RuntimeEventKind::TurnCompleted]);
+        bus.publish(diff_session_events(&working, &idle));
+        bus.publish(diff_session_events(&idle, &working));
+        let received = subscription.receiver.try_iter().collect::<Vec<_>>();
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].id.as_deref(), Some("turn-1"));
+        assert_eq!(bus.subscribe(Vec::new()).backlog.len(), 3);
+    }
+}
@@ -8,9 +8,9 @@ use std::{
     env,
     ffi::OsStr,
     fs,
-    io::{self, Read},
+    io::{self, Read, Write},
     net::TcpStream,
-    path::PathBuf,
+    path::{Path, PathBuf},
     process::{Child, Command as ProcessCommand, ExitCode, Stdio},
     thread,
     time::{Duration, SystemTime, UNIX_EPOCH},
@@ -29,16 +29,26 @@ mod codex;
 mod codex_app;
 mod control_plane;
 mod dispatch;
+mod events;
 mod mission;
 mod native;
 mod operator_request;
 mod orchestration;
 mod proposal;
+mod recording;
+mod request_policy;
 mod runtime;
 #[cfg(test)]
 mod test_support;
 mod ticket;
+mod ticket_graph;
+mod ticket_lint;
+mod ticket_template;
+mod timeline;
+mod trigger;
 mod tui;
+mod usage;
+mod watch;
 
 use agent::spawn_agent;
 use autonomy::{
@@ -62,19 +72,21 @@ use codex_app::{
     search_codex_app_threads, serve_codex_app_session, tell_codex_app_with_mode_tcp,
 };
 use control_plane::{
-    ControlPlaneOutput, ControlPlaneResourceKindArg, EvidenceBundleOptions, EvidenceBundleReport,
-    KubernetesRenderOutput, NodeBootstrapOptions, NodeFanoutOptions, NodeLinksOptions,
-    NodePairSessionOptions, NodeRegisterOptions, NodeScheduleOptions, NodeStartSessionOptions,
-    NodeSudoOptions, NodeVisitOptions, PairDemoCleanupReport, PairDemoOptions,
-    PairDemoSequenceOptions, PairDemoSequenceReport, PairLedgerFinalizeOptions,
-    PairLedgerFinalizeReport, RelayMessageSendOptions, RemoteOperatorRequestResolveOptions,
-    WorkerDriftSmokeOptions, WorkerDriftSmokeScheduleOptions, WorkerOffloadOptions,
-    ack_cluster_relay_message, ack_relay_message, apply_kubernetes_resources, apply_kustomization,
-    apply_manifests, attach_cluster_runtime_session, authorize_runtime_message, bootstrap_node,
-    bundle_evidence, check_node_links, cleanup_node, cleanup_pair_demos, cluster_index,
-    collect_node_task_note, configure_worker_drift_smoke_schedule, delete_cluster_runtime_session,
-    doctor_nodes, export_pair_ledger, finalize_pair_ledger, flush_cluster_relay_messages,
-    flush_relay_messages, heartbeat_node, inspect_node, install_node_heartbeat_user_service,
+    ControlPlaneOutput, ControlPlaneResourceKindArg, DeleteCascade, DeleteOptions,
+    EvidenceBundleOptions, EvidenceBundleReport, KubernetesRenderOutput, NodeBootstrapOptions,
+    NodeFanoutOptions, NodeLinksOptions, NodePairSessionOptions, NodeRegisterOptions,
+    NodeScheduleOptions, NodeStartSessionOptions, NodeSudoOptions, NodeVisitOptions,
+    PairDemoCleanupReport, PairDemoOptions, PairDemoSequenceOptions, PairDemoSequenceReport,
+    PairLedgerFinalizeOptions, PairLedgerFinalizeReport, RelayMessageSendOptions,
+    RemoteOperatorRequestResolveOptions, WorkerDriftSmokeOptions, WorkerDriftSmokeScheduleOptions,
+    WorkerOffloadOptions, ack_cluster_relay_message, ack_relay_message, apply_kubernetes_resources,
+    apply_kustomization, apply_manifests, attach_cluster_runtime_session,
+    authorize_runtime_message, bootstrap_node, bundle_evidence, capsule_identity, check_node_links,
+    cleanup_node, cleanup_pair_demos, cluster_index, collect_node_task_note,
+    configure_worker_drift_smoke_schedule, delete_cluster_runtime_session,
+    delete_declared_resources, delete_resource, diff_manifests, doctor_nodes, dry_run_apply,
+    export_pair_ledger, finalize_pair_ledger, flush_cluster_relay_messages, flush_relay_messages,
+    heartbeat_node, inspect_node, install_node_heartbeat_user_service,
     interrupt_cluster_runtime_session, list_cluster_operator_requests, list_cluster_relay_messages,
     list_pair_ledgers, list_relay_messages, list_worker_run_records,
     load_or_create_orchestration_policy, load_worker_run_record, mark_worker_run,
@@ -82,36 +94,44 @@ use control_plane::{
     orchestration_policy_path, pause_deployment_rollout, preflight_nodes,
     prune_cluster_relay_messages, prune_completed_runtime_sessions, prune_relay_messages,
     prune_worker_runs, read_auth_audit_events, read_worker_run_artifact, reconcile_nodes,
-    register_node, render_codex_doctor_output, render_describe_output,
+    register_node, render_apply_dry_run_output, render_codex_doctor_output, render_describe_output,
     render_evidence_bundle_output, render_get_output, render_kubernetes_resources,
-    render_node_heartbeat_service_install, render_node_heartbeat_service_status,
-    render_node_probe_output, render_node_sudo_output, render_pair_demo_cleanup_output,
-    render_pair_demo_sequence_output, render_pair_export_output, render_pair_finalize_output,
-    render_pair_ledgers_output, render_pair_stale_review_output, render_relay_message_output,
-    render_relay_messages_output, render_relay_prune_output, render_rollout_history_output,
-    render_rollout_status_output, render_runtime_prune_output, render_worker_drift_smoke_output,
-    render_worker_drift_smoke_schedule_status, render_worker_model_validation_output,
-    render_worker_run_artifact_output, render_worker_run_prune_output, render_worker_runs_output,
-    render_worker_validation_output, resolve_cluster_operator_request, resolve_service_target,
-    resolve_service_target_for_message, respond_cluster_runtime_server_request,
-    restart_deployment_rollout, resume_deployment_rollout, retry_cluster_relay_message,
-    retry_relay_message, review_stale_pair_ledgers, rotate_capsule_key, run_codex_doctor_for_node,
-    run_node_fanout, run_node_sudo, run_node_visit, run_pair_demo_sequence,
-    run_recurring_worker_drift_smoke, run_worker_drift_smoke, run_worker_offload, schedule_node,
-    send_relay_message, set_node_cordoned, set_node_taint, show_cluster_operator_request,
-    start_node_pair_session, start_node_session, start_pair_demo, supersede_cluster_relay_message,
+    render_manifest_diff_output, render_node_heartbeat_service_install,
+    render_node_heartbeat_service_status, render_node_probe_output, render_node_sudo_output,
+    render_pair_demo_cleanup_output, render_pair_demo_sequence_output, render_pair_export_output,
+    render_pair_finalize_output, render_pair_ledgers_output, render_pair_stale_review_output,
+    render_relay_message_output, render_relay_messages_output, render_relay_prune_output,
+    render_rollout_history_output, render_rollout_status_output, render_runtime_prune_output,
+    render_secret_key_rotation_output, render_secret_reveal_output,
+    render_worker_drift_smoke_output, render_worker_drift_smoke_schedule_status,
+    render_worker_model_validation_output, render_worker_run_artifact_output,
+    render_worker_run_prune_output, render_worker_runs_output, render_worker_validation_output,
+    resolve_cluster_operator_request, resolve_service_target, resolve_service_target_for_message,
+    respond_cluster_runtime_server_request, restart_deployment_rollout, resume_deployment_rollout,
+    retry_cluster_relay_message, retry_relay_message, reveal_secret, review_stale_pair_ledgers,
+    rotate_capsule_key, rotate_secret_key, run_codex_doctor_for_node, run_node_fanout,
+    run_node_sudo, run_node_visit, run_pair_demo_sequence, run_recurring_worker_drift_smoke,
+    run_worker_drift_smoke, run_worker_offload, schedule_node, send_relay_message,
+    set_node_cordoned, set_node_taint, show_cluster_operator_request, start_node_pair_session,
+    start_node_session, start_pair_demo, stream_cluster_events, supersede_cluster_relay_message,
     supersede_relay_message, sync_codex_auth_to_node, tell_cluster_runtime_session,
     tell_runtime_session_on_node, undo_deployment_rollout, validate_worker_models,
     wait_for_rollout_status_output, worker_drift_smoke_schedule_status,
 };
-use dispatch::{DispatchOptions, run_dispatch_loop};
+use dispatch::{
+    DispatchOptions, dispatch_queue, dispatch_schedule, render_dispatch_queue_output,
+    render_dispatch_schedule_output, run_dispatch_loop,
+};
+use events::{RuntimeEvent, RuntimeEventKind};
 use mission::{
     MissionCreateOptions, MissionEventOptions, append_mission_event, complete_mission,
     create_mission, list_missions, render_mission_detail_output, render_mission_templates_output,
     render_missions_output, show_mission,
 };
 use native::{
-    NativeSessionMetadata, RuntimeContextMetadata, serve_native_session, spawn_native_session,
+    AgentRestartPolicy, NativeSessionMetadata, NativeSessionOptions, RestartPolicyKind,
+    RuntimeContextMetadata, compact_token_count, replay_native_recording, serve_native_session,
+    spawn_native_session,
 };
 use operator_request::{
     OperatorRequestCreateOptions, OperatorRequestResolveOptions, create_operator_request,
@@ -128,11 +148,24 @@ use proposal::{
     list_proposals, render_proposal_output, render_proposals_output, show_proposal,
 };
 use runtime::{
-    attach_runtime_session, collect_runtime_sessions, delete_runtime_session,
-    interrupt_runtime_session, respond_runtime_server_request, tell_runtime_session,
+    add_runtime_agent, attach_runtime_session, collect_runtime_sessions, delete_runtime_session,
+    interrupt_runtime_session, remove_runtime_agent, respond_runtime_server_request,
+    stream_runtime_events, tell_runtime_session,
 };
 use ticket::slugify;
+use ticket_graph::{load_vault_ticket_graph, render_ticket_graph_output};
+use ticket_lint::{lint_tickets, render_ticket_lint_output};
+use ticket_template::{
+    TicketNewOptions, create_ticket, render_ticket_new_output, render_ticket_templates_output,
+    ticket_templates,
+};
+use timeline::{TimelineFormat, build_timeline, render_timeline};
+use trigger::{OutputTriggerRule, load_trigger_file};
 use tui::{run_dashboard, view_agent};
+use usage::{
+    TokenUsageGrouping, load_usage_records, records_for_mission, records_for_ticket,
+    render_usage_output, rollup_usage,
+};
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
 pub enum SessionBackend {
@@ -211,6 +244,26 @@ enum Command {
         #[arg(long, alias = "wd", value_hint = ValueHint::DirPath)]
         working_directory: Option<String>,
 
+        /// Restart policy applied when an agent's command exits
+        #[arg(long, value_enum, default_value_t = RestartPolicyKind::Never)]
+        restart: RestartPolicyKind,
+
+        /// Maximum consecutive restarts before an agent stays down
+        #[arg(long, default_value_t = 5)]
+        max_restarts: u32,
+
+        /// Initial restart delay; doubles after each consecutive failure
+        #[arg(long, default_value_t = 1000)]
+        restart_backoff_ms: u64,
+
+        /// Output trigger as PATTERN=notify|interrupt|send-text:TEXT|operator-request|mark-idle
+        #[arg(long = "on-output")]
+        on_output: Vec<String>,
+
+        /// YAML file with a `triggers:` list of output trigger rules
+        #[arg(long, value_hint = ValueHint::FilePath)]
+        triggers: Option<PathBuf>,
+
         /// Command and args to run per agent
         #[arg(required = true, last = true, value_hint = ValueHint::CommandString)]
         command: Vec<String>,
@@ -289,6 +342,9 @@ enum Command {
 
     /// Watch Obsidian boards and dispatch Codex runs from ticket transitions
     Dispatch {
+        #[command(subcommand)]
+        action: Option<DispatchCommand>,
+
         /// Deprecated compatibility flag; native is the only backend
         #[arg(long, value_enum, default_value_t = SessionBackend::Native, hide = true)]
         backend: SessionBackend,
@@ -309,11 +365,15 @@ enum Command {
         #[arg(long, default_value_t = false)]
         once: bool,
 
+        /// React to vault edits through inotify instead of waiting for the next poll; falls back to polling when inotify is unavailable
+        #[arg(long, default_value_t = false, conflicts_with = "once")]
+        watch: bool,
+
         /// Evaluate transitions without launching Codex or writing board/ticket changes
         #[arg(long, default_value_t = false)]
         dry_run: bool,
 
-        /// Polling interval in seconds when not using --once
+        /// Polling interval in seconds when not using --once; with --watch, the longest wait between passes
         #[arg(long, alias = "interval", default_value_t = 15)]
         interval_seconds: u64,
 
@@ -333,6 +393,14 @@ enum Command {
         #[arg(long, alias = "delay-ms", default_value_t = 1500)]
         startup_delay_ms: u64,
 
+        /// Maximum concurrently active runs across all boards; extra cards wait in `Queued for Codex`
+        #[arg(long, alias = "max-active")]
+        max_active_runs: Option<usize>,
+
+        /// Maximum concurrently active runs per ticket project
+        #[arg(long, alias = "max-active-per-project")]
+        max_active_runs_per_project: Option<usize>,
+
         /// Codex command override, defaults to `codex`
         #[arg(last = true, value_hint = ValueHint::CommandString)]
         command: Vec<String>,
@@ -417,6 +485,17 @@ enum Command {
     #[command(name = "capsule-open", hide = true)]
     CapsuleOpen,
 
+    /// Print this node's public visit capsule keys
+    #[command(name = "capsule-identity")]
+    CapsuleIdentity {
+        /// Trust capsules signed by NAME with the given Ed25519 public key
+        #[arg(long = "trust", value_name = "NAME=SIGNING_KEY")]
+        trust: Vec<String>,
+
+        #[arg(long, alias = "out", value_enum, default_value_t = ControlPlaneOutput::Table)]
+        output: ControlPlaneOutput,
+    },
+
     /// Apply declarative control-plane resources from YAML manifests
     Apply {
         #[arg(short = 'f', long = "file", value_hint = ValueHint::FilePath)]
@@ -424,6 +503,25 @@ enum Command {
 
         #[arg(short = 'k', long = "kustomize", value_hint = ValueHint::DirPath)]
         kustomize: Option<PathBuf>,
+
+        /// Validate and show what reconcile would do without saving or launching anything
+        #[arg(long = "dry-run", default_value_t = false)]
+        dry_run: bool,
+
+        #[arg(long, alias = "out", value_enum, default_value_t = ControlPlaneOutput::Table, requires = "dry_run")]
+        output: ControlPlaneOutput,
+    },
+
+    /// Show field-level differences between manifests and the stored resources
+    Diff {
+        #[arg(short = 'f', long = "file", value_hint = ValueHint::FilePath)]
+        file: Vec<PathBuf>,
+
+        #[arg(short = 'k', long = "kustomize", value_hint = ValueHint::DirPath)]
+        kustomize: Option<PathBuf>,
+
+        #[arg(long, alias = "out", value_enum, default_value_t = ControlPlaneOutput::Table)]
+        output: ControlPlaneOutput,
     },
 
     /// Get declarative control-plane resources
@@ -449,6 +547,12 @@ enum Command {
         output: ControlPlaneOutput,
     },
 
+    /// Reveal sealed Secret values and rotate the key that seals them
+    Secret {
+        #[command(subcommand)]
+        command: SecretCommand,
+    },
+
     /// Register and manage Jarvis cluster nodes
     Node {
         #[command(subcommand)]
@@ -461,6 +565,12 @@ enum Command {
         command: WorkerCommand,
     },
 
+    /// Inspect Obsidian ticket notes and their dependencies
+    Ticket {
+        #[command(subcommand)]
+        command: TicketCommand,
+    },
+
     /// Track business objectives, decisions, evidence, and runtime links
     Mission {
         #[command(subcommand)]
@@ -616,18 +726,92 @@ enum Command {
             requires = "service"
         )]
         resource_namespace: Option<String>,
+
+        /// Watch without forwarding keystrokes, resizes, or interrupts
+        #[arg(long, default_value_t = false)]
+        read_only: bool,
     },
 
-    /// Kill a namespace
+    /// Replay the recorded PTY stream of a native agent
+    Replay {
+        #[arg(long, alias = "ns")]
+        namespace: String,
+
+        #[arg(long, default_value = "agent0")]
+        agent: String,
+
+        /// Playback speed multiplier
+        #[arg(long, default_value_t = 1.0)]
+        speed: f64,
+
+        /// Compress pauses longer than this many seconds
+        #[arg(long = "max-idle", default_value_t = 2.0)]
+        max_idle_seconds: f64,
+    },
+
+    /// Stream structured lifecycle events from a running namespace
+    Events {
+        #[arg(long, alias = "ns", required_unless_present = "cluster")]
+        namespace: Option<String>,
+
+        /// Stream every local session and every registered remote node
+        #[arg(long, default_value_t = false, conflicts_with = "namespace")]
+        cluster: bool,
+
+        /// With --cluster, skip remote nodes (used by the SSH fan-out)
+        #[arg(long, default_value_t = false, requires = "cluster", hide = true)]
+        local_only: bool,
+
+        /// Only report these event kinds (repeatable)
+        #[arg(long = "kind", value_enum)]
+        kinds: Vec<RuntimeEventKind>,
+
+        /// Keep streaming live events after the backlog
+        #[arg(long, default_value_t = false)]
+        follow: bool,
+
+        #[arg(long, default_value_t = false)]
+        json: bool,
+    },
+
+    /// Add or remove agents in a running native namespace
+    Agent {
+        #[command(subcommand)]
+        command: AgentCommand,
+    },
+
+    /// Kill a runtime namespace, or delete declarative control-plane resources
     Delete {
         #[arg(long, value_enum, default_value_t = SessionBackend::Native, hide = true)]
         backend: SessionBackend,
 
-        #[arg(long, alias = "ns")]
-        namespace: String,
+        /// Runtime namespace to kill
+        #[arg(long, alias = "ns", required_unless_present_any = ["resource", "file", "kustomize"], conflicts_with_all = ["resource", "file", "kustomize"])]
+        namespace: Option<String>,
 
         #[arg(long)]
         mission: Option<String>,
+
+        /// Control-plane resource to delete, as KIND/NAME (e.g. deployment/planner)
+        #[arg(value_name = "KIND/NAME", conflicts_with_all = ["file", "kustomize"])]
+        resource: Option<String>,
+
+        #[arg(short = 'n', long = "resource-namespace", alias = "rns")]
+        resource_namespace: Option<String>,
+
+        /// Delete every resource declared in these manifests
+        #[arg(short = 'f', long = "file", value_hint = ValueHint::FilePath)]
+        file: Vec<PathBuf>,
+
+        #[arg(short = 'k', long = "kustomize", value_hint = ValueHint::DirPath)]
+        kustomize: Option<PathBuf>,
+
+        #[arg(long, value_enum, default_value_t = DeleteCascade::Background)]
+        cascade: DeleteCascade,
+
+        /// Delete a Namespace even if it still contains resources
+        #[arg(long, default_value_t = false)]
+        force: bool,
     },
 
     /// List namespaces and agents
@@ -642,6 +826,26 @@ enum Command {
         json: bool,
     },
 
+    /// Roll up recorded Codex token usage per ticket
+    Usage {
+        /// Only this ticket note (path or ticket id)
+        #[arg(long, conflicts_with = "mission")]
+        ticket: Option<String>,
+
+        /// Only tickets and namespaces linked to this mission
+        #[arg(long)]
+        mission: Option<String>,
+
+        #[arg(long, alias = "ns")]
+        namespace: Option<String>,
+
+        #[arg(long, value_enum, default_value_t = TokenUsageGrouping::Ticket)]
+        by: TokenUsageGrouping,
+
+        #[arg(long, alias = "out", value_enum, default_value_t = ControlPlaneOutput::Table)]
+        output: ControlPlaneOutput,
+    },
+
     /// Read Codex app-server thread history for a namespace
     History {
         #[arg(long, value_enum, default_value_t = SessionBackend::Native, hide = true)]
@@ -675,6 +879,14 @@ enum Command {
 
         #[arg(long, default_value_t = false)]
         json: bool,
+
+        /// Render a turn-level timeline instead of the one-line turn summary
+        #[arg(long, value_enum, default_value_t = TimelineFormat::Text, conflicts_with = "json")]
+        format: TimelineFormat,
+
+        /// Include every turn with untruncated command output
+        #[arg(long, default_value_t = false)]
+        full: bool,
     },
 
     /// Search persisted Codex app-server thread history from a live namespace
@@ -803,6 +1015,10 @@ enum Command {
 
         #[arg(long, default_value = "agent0")]
         agent: String,
+
+        /// Watch without forwarding keystrokes, resizes, or interrupts
+        #[arg(long, default_value_t = false)]
+        read_only: bool,
     },
 
     /// Send file or text to a running agent's TUI
@@ -916,6 +1132,136 @@ enum Command {
     },
 }
 
+#[derive(Subcommand, Debug)]
+enum DispatchCommand {
+    /// List cards waiting for a run slot with their queue position and estimated start
+    Queue {
+        /// Vault root whose dispatch state should be read
+        #[arg(long, alias = "vault", value_hint = ValueHint::DirPath, default_value = "/home/rootster/codex")]
+        vault_path: PathBuf,
+
+        /// Override the dispatch state file
+        #[arg(long, alias = "state", value_hint = ValueHint::FilePath)]
+        state_file: Option<PathBuf>,
+
+        #[arg(long, alias = "out", value_enum, default_value_t = ControlPlaneOutput::Table)]
+        output: ControlPlaneOutput,
+    },
+    /// List tickets with a `schedule:` and their last and next scheduled runs
+    Schedule {
+        /// Vault root whose dispatch state should be read
+        #[arg(long, alias = "vault", value_hint = ValueHint::DirPath, default_value = "/home/rootster/codex")]
+        vault_path: PathBuf,
+
+        /// Override the dispatch state file
+        #[arg(long, alias = "state", value_hint = ValueHint::FilePath)]
+        state_file: Option<PathBuf>,
+
+        #[arg(long, alias = "out", value_enum, default_value_t = ControlPlaneOutput::Table)]
+        output: ControlPlaneOutput,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum SecretCommand {
+    /// Print Secret values once an operator approves a reveal request
+    Reveal {
+        name: String,
+
+        #[arg(short = 'n', long = "resource-namespace", alias = "ns", alias = "rns")]
+        resource_namespace: Option<String>,
+
+        /// Reveal only this key
+        #[arg(long)]
+        key: Option<String>,
+
+        /// Approved operator request id; without one, a request is filed for approval
+        #[arg(long = "request")]
+        request: Option<String>,
+
+        #[arg(long, alias = "out", value_enum, default_value_t = ControlPlaneOutput::Table)]
+        output: ControlPlaneOutput,
+    },
+
+    /// Generate a new secret key, reseal every Secret under it, and retire old keys
+    RotateKey {
+        #[arg(long, alias = "out", value_enum, default_value_t = ControlPlaneOutput::Table)]
+        output: ControlPlaneOutput,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum TicketCommand {
+    /// Print the depends_on/blocks DAG for board tickets and report cycles
+    Graph {
+        /// Vault root used to resolve board links and default boards
+        #[arg(long, alias = "vault", value_hint = ValueHint::DirPath, default_value = "/home/rootster/codex")]
+        vault_path: PathBuf,
+
+        /// Board file to read; may be repeated. Defaults to the dispatch board and project boards in the vault.
+        #[arg(long, alias = "b", value_hint = ValueHint::FilePath)]
+        board: Vec<PathBuf>,
+
+        #[arg(long, alias = "out", value_enum, default_value_t = ControlPlaneOutput::Table)]
+        output: ControlPlaneOutput,
+    },
+    /// Validate ticket frontmatter and sections; exits non-zero when any ticket has errors
+    Lint {
+        /// Vault root used to find tickets and resolve depends_on/blocks links. Defaults to
+        /// /home/rootster/codex when no ticket notes are given.
+        #[arg(long, alias = "vault", value_hint = ValueHint::DirPath)]
+        vault_path: Option<PathBuf>,
+
+        /// Ticket notes to lint. Defaults to every `type: ticket` note in the vault.
+        #[arg(value_hint = ValueHint::FilePath)]
+        paths: Vec<PathBuf>,
+
+        #[arg(long, alias = "out", value_enum, default_value_t = ControlPlaneOutput::Table)]
+        output: ControlPlaneOutput,
+    },
+    /// Write a new ticket note from a template and optionally put its card on a board
+    New {
+        /// Vault root the ticket note and board paths are relative to
+        #[arg(long, alias = "vault", value_hint = ValueHint::DirPath, default_value = "/home/rootster/codex")]
+        vault_path: PathBuf,
+
+        /// Built-in template (bugfix, chore, feature) or a user template in ~/.jarvis/ticket-templates/
+        #[arg(long, short = 't', default_value = "feature")]
+        template: String,
+
+        #[arg(long)]
+        title: String,
+
+        /// Project directory or note, e.g. Projects/jarvisctl; the ticket is written to its Tickets/ folder
+        #[arg(long)]
+        project: Option<String>,
+
+        /// Repository the dispatched Codex run works in
+        #[arg(long = "repo", alias = "repo-path", value_hint = ValueHint::DirPath)]
+        repo_path: Option<String>,
+
+        /// Override the template's priority
+        #[arg(long)]
+        priority: Option<String>,
+
+        /// Add a card for the ticket to this board lane
+        #[arg(long)]
+        column: Option<String>,
+
+        /// Board to add the card to. Defaults to the project's Board.md, or the dispatch board without a project.
+        #[arg(long, alias = "b", value_hint = ValueHint::FilePath, requires = "column")]
+        board: Option<PathBuf>,
+
+        #[arg(long, alias = "out", value_enum, default_value_t = ControlPlaneOutput::Table)]
+        output: ControlPlaneOutput,
+    },
+    /// List built-in and user ticket templates
+    Templates {
+        #[arg(long, alias = "out", value_enum, default_value_t = ControlPlaneOutput::Table)]
+        output: ControlPlaneOutput,
+    },
+}
+
 #[derive(Subcommand, Debug)]
 enum MissionCommand {
     /// Create an operational mission ledger entry
@@ -2150,7 +2496,7 @@ enum NodeCommand {
         output: ControlPlaneOutput,
     },
 
-    /// Rotate the encrypted visit capsule key and sync it to remote nodes
+    /// Rotate this node's visit capsule keypairs and exchange public keys with remote nodes
     RotateCapsuleKey {
         #[arg(long = "no-sync", default_value_t = false)]
         no_sync: bool,
@@ -2430,6 +2776,46 @@ enum NodeCommand {
     },
 }
 
+#[derive(Subcommand, Debug)]
+enum AgentCommand {
+    /// Spawn an extra agent into a running namespace
+    Add {
+        #[arg(long, alias = "ns")]
+        namespace: String,
+
+        /// Agent name, defaults to the next free agentN
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Working directory, defaults to the namespace working directory
+        #[arg(long, alias = "wd", value_hint = ValueHint::DirPath)]
+        working_directory: Option<String>,
+
+        /// Restart policy, defaults to the policy the namespace was started with
+        #[arg(long, value_enum)]
+        restart: Option<RestartPolicyKind>,
+
+        #[arg(long, default_value_t = 5, requires = "restart")]
+        max_restarts: u32,
+
+        #[arg(long, default_value_t = 1000, requires = "restart")]
+        restart_backoff_ms: u64,
+
+        /// Command and args to run in the new agent
+        #[arg(required = true, last = true, value_hint = ValueHint::CommandString)]
+        command: Vec<String>,
+    },
+
+    /// Stop an agent and remove it from a running namespace
+    Rm {
+        #[arg(long, alias = "ns")]
+        namespace: String,
+
+        #[arg(long)]
+        name: String,
+    },
+}
+
 #[derive(Subcommand, Debug)]
 enum RolloutCommand {
     /// Show rollout status for a Deployment
@@ -2709,8 +3095,27 @@ fn dispatch(cli: Cli) -> Result<(), JarvisError> {
             namespace,
             agents,
             working_directory,
+            restart,
+            max_restarts,
+            restart_backoff_ms,
+            on_output,
+            triggers,
             command,
-        } => run_session(backend, &namespace, agents, &working_directory, &command),
+        } => run_session(
+            backend,
+            &namespace,
+            agents,
+            &working_directory,
+            NativeSessionOptions {
+                restart_policy: AgentRestartPolicy {
+                    policy: restart,
+                    max_retries: max_restarts,
+                    backoff_ms: restart_backoff_ms,
+                },
+                triggers: collect_output_triggers(&on_output, triggers.as_deref())?,
+            },
+            &command,
+        ),
         Command::Codex {
             backend,
             driver,
@@ -2751,20 +3156,29 @@ fn dispatch(cli: Cli) -> Result<(), JarvisError> {
             },
             extra_runtime_args: Vec::new(),
             startup_delay_ms,
+            restart_policy: AgentRestartPolicy::default(),
             command,
         }),
         Command::Dispatch {
+            action: Some(action),
+            ..
+        } => dispatch_command(action),
+        Command::Dispatch {
+            action: None,
             backend,
             driver,
             vault_path,
             board,
             once,
+            watch,
             dry_run,
             interval_seconds,
             state_file,
             agent,
             agents,
             startup_delay_ms,
+            max_active_runs,
+            max_active_runs_per_project,
             command,
         } => run_dispatch_loop(DispatchOptions {
             backend,
@@ -2773,11 +3187,14 @@ fn dispatch(cli: Cli) -> Result<(), JarvisError> {
             boards: board,
             interval_seconds,
             once,
+            watch,
             dry_run,
             state_file,
             agent,
             agents,
             startup_delay_ms,
+            max_active_runs,
+            max_active_runs_per_project,
             command,
         })
         .map_err(JarvisError::from),
@@ -2821,7 +3238,37 @@ fn dispatch(cli: Cli) -> Result<(), JarvisError> {
             full,
         ),
         Command::CapsuleOpen => capsule_open(),
-        Command::Apply { file, kustomize } => apply_resources(&file, kustomize.as_deref()),
+        Command::CapsuleIdentity { trust, output } => capsule_identity_command(&trust, output),
+        Command::Apply {
+            file,
+            kustomize,
+            dry_run,
+            output,
+        } => {
+            if dry_run {
+                let report =
+                    dry_run_apply(&file, kustomize.as_deref()).map_err(JarvisError::from)?;
+                println!(
+                    "{}",
+                    render_apply_dry_run_output(&report, output).map_err(JarvisError::from)?
+                );
+                Ok(())
+            } else {
+                apply_resources(&file, kustomize.as_deref())
+            }
+        }
+        Command::Diff {
+            file,
+            kustomize,
+            output,
+        } => {
+            let diffs = diff_manifests(&file, kustomize.as_deref()).map_err(JarvisError::from)?;
+            println!(
+                "{}",
+                render_manifest_diff_output(&diffs, output).map_err(JarvisError::from)?
+            );
+            Ok(())
+        }
         Command::Get {
             kind,
             resource_namespace,
@@ -2833,8 +3280,10 @@ fn dispatch(cli: Cli) -> Result<(), JarvisError> {
             resource_namespace,
             output,
         } => describe_resource(kind, &name, resource_namespace.as_deref(), output),
+        Command::Secret { command } => secret_command(command),
         Command::Node { command } => node_command(command),
         Command::Worker { command } => worker_command(command),
+        Command::Ticket { command } => ticket_command(command),
         Command::Mission { command } => mission_command(command),
         Command::Proposal { command } => proposal_command(command),
         Command::Pair { command } => pair_command(command),
@@ -2875,6 +3324,22 @@ fn dispatch(cli: Cli) -> Result<(), JarvisError> {
         Command::OperatorRequest { command } => operator_request_command(command),
         Command::Message { command } => message_command(command),
         Command::Rollout { command } => rollout_command(command),
+        Command::Events {
+            namespace,
+            cluster,
+            local_only,
+            kinds,
+            follow,
+            json,
+        } => events_command(
+            namespace.as_deref(),
+            cluster,
+            local_only,
+            &kinds,
+            follow,
+            json,
+        ),
+        Command::Agent { command } => agent_command(command),
         Command::Kube { command } => kube_command(command),
         Command::Dashboard {
             backend,
@@ -2886,6 +3351,7 @@ fn dispatch(cli: Cli) -> Result<(), JarvisError> {
             namespace,
             service,
             resource_namespace,
+            read_only,
         } => attach_session(
             backend,
             resolve_runtime_namespace(
@@ -2894,17 +3360,60 @@ fn dispatch(cli: Cli) -> Result<(), JarvisError> {
                 resource_namespace.as_deref(),
             )?
             .as_str(),
+            read_only,
         ),
+        Command::Replay {
+            namespace,
+            agent,
+            speed,
+            max_idle_seconds,
+        } => replay_native_recording(&namespace, &agent, speed, max_idle_seconds)
+            .map_err(JarvisError::from),
         Command::Delete {
             backend,
             namespace,
             mission,
-        } => delete_session(backend, &namespace, mission.as_deref()),
+            resource,
+            resource_namespace,
+            file,
+            kustomize,
+            cascade,
+            force,
+        } => {
+            if let Some(namespace) = namespace {
+                return delete_session(backend, &namespace, mission.as_deref());
+            }
+            let options = DeleteOptions { cascade, force };
+            let messages = match resource {
+                Some(resource) => {
+                    delete_resource(&resource, resource_namespace.as_deref(), options)
+                }
+                None => delete_declared_resources(&file, kustomize.as_deref(), options),
+            }
+            .map_err(JarvisError::from)?;
+            for message in messages {
+                println!("{}", message);
+            }
+            Ok(())
+        }
         Command::List {
             backend,
             namespace,
             json,
         } => list_sessions(backend, namespace, json),
+        Command::Usage {
+            ticket,
+            mission,
+            namespace,
+            by,
+            output,
+        } => usage_command(
+            ticket.as_deref(),
+            mission.as_deref(),
+            namespace.as_deref(),
+            by,
+            output,
+        ),
         Command::History {
             backend,
             namespace,
@@ -2912,13 +3421,15 @@ fn dispatch(cli: Cli) -> Result<(), JarvisError> {
             resource_namespace,
             include_turns,
             json,
+            format,
+            full,
         } => {
             let namespace = resolve_runtime_namespace(
                 namespace.as_deref(),
                 service.as_deref(),
                 resource_namespace.as_deref(),
             )?;
-            history(backend, &namespace, include_turns, json)
+            history(backend, &namespace, include_turns, json, format, full)
         }
         Command::SearchHistory {
             backend,
@@ -2968,13 +3479,14 @@ fn dispatch(cli: Cli) -> Result<(), JarvisError> {
             service,
             resource_namespace,
             agent,
+            read_only,
         } => {
             let namespace = resolve_runtime_namespace(
                 namespace.as_deref(),
                 service.as_deref(),
                 resource_namespace.as_deref(),
             )?;
-            exec_agent(backend, &namespace, &agent)
+            exec_agent(backend, &namespace, &agent, read_only)
         }
         Command::Tell {
             backend,
@@ -3094,16 +3606,31 @@ fn inspect(name: Option<String>, pid: Option<u32>, exec_shell: bool) -> Result<(
     Ok(())
 }
 
+fn collect_output_triggers(
+    specs: &[String],
+    file: Option<&Path>,
+) -> Result<Vec<OutputTriggerRule>, JarvisError> {
+    let mut rules = match file {
+        Some(path) => load_trigger_file(path).map_err(JarvisError::from)?,
+        None => Vec::new(),
+    };
+    for spec in specs {
+        rules.push(OutputTriggerRule::parse_spec(spec).map_err(JarvisError::from)?);
+    }
+    Ok(rules)
+}
+
 #[instrument(err)]
 fn run_session(
     backend: SessionBackend,
     namespace: &str,
     agents: usize,
     working_dir: &Option<String>,
+    options: NativeSessionOptions,
     cmd: &[String],
 ) -> Result<(), JarvisError> {
     let joined = shell_words::join(cmd);
-    run_session_shell(backend, namespace, agents, working_dir, &joined)
+    run_session_shell(backend, namespace, agents, working_dir, &joined, options)
 }
 
 pub(crate) fn run_session_shell(
@@ -3112,8 +3639,17 @@ pub(crate) fn run_session_shell(
     agents: usize,
     working_dir: &Option<String>,
     joined: &str,
+    options: NativeSessionOptions,
 ) -> Result<(), JarvisError> {
-    run_session_shell_with_context(backend, namespace, agents, working_dir, joined, None)
+    run_session_shell_with_context(
+        backend,
+        namespace,
+        agents,
+        working_dir,
+        joined,
+        None,
+        options,
+    )
 }
 
 pub(crate) fn run_session_shell_with_context(
@@ -3123,10 +3659,18 @@ pub(crate) fn run_session_shell_with_context(
     working_dir: &Option<String>,
     joined: &str,
     context: Option<RuntimeContextMetadata>,
+    options: NativeSessionOptions,
 ) -> Result<(), JarvisError> {
     let _ = backend;
-    spawn_native_session(namespace, agents, working_dir.as_deref(), joined, context)
-        .map_err(JarvisError::from)?;
+    spawn_native_session(
+        namespace,
+        agents,
+        working_dir.as_deref(),
+        joined,
+        context,
+        options,
+    )
+    .map_err(JarvisError::from)?;
 
     println!(
         "✅ Started {} agent(s) in '{}' using the native runtime. Attach: jarvisctl attach --namespace {}",
@@ -3262,6 +3806,31 @@ fn capsule_open() -> Result<(), JarvisError> {
     Ok(())
 }
 
+fn capsule_identity_command(
+    trust: &[String],
+    output: ControlPlaneOutput,
+) -> Result<(), JarvisError> {
+    let identity = capsule_identity(trust).map_err(JarvisError::from)?;
+    match output {
+        ControlPlaneOutput::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&identity).map_err(anyhow::Error::from)?
+        ),
+        ControlPlaneOutput::Yaml => println!(
+            "{}",
+            serde_yaml::to_string(&identity).map_err(anyhow::Error::from)?
+        ),
+        ControlPlaneOutput::Table => {
+            println!("NODE\tENCRYPTION_KEY\tSIGNING_KEY");
+            println!(
+                "{}\t{}\t{}",
+                identity.node, identity.encryption_public_key, identity.signing_public_key
+            );
+        }
+    }
+    Ok(())
+}
+
 fn now_millis_for_namespace() -> u128 {
     std::time::SystemTime::now()
         .duration_since(std::time::SystemTime::UNIX_EPOCH)
@@ -4101,10 +4670,11 @@ fn node_command(command: NodeCommand) -> Result<(), JarvisError> {
                     )
                 }
                 ControlPlaneOutput::Table => {
-                    println!("KEY_PATH\tSYNCED_NODES\tFAILURES");
+                    println!("KEY_PATH\tSIGNING_KEY\tSYNCED_NODES\tFAILURES");
                     println!(
-                        "{}\t{}\t{}",
+                        "{}\t{}\t{}\t{}",
                         result.key_path,
+                        result.signing_public_key,
                         if result.synced_nodes.is_empty() {
                             "-".to_string()
                         } else {
@@ -4736,6 +5306,152 @@ fn parse_key_value_pairs(
     Ok(parsed)
 }
 
+fn dispatch_command(command: DispatchCommand) -> Result<(), JarvisError> {
+    match command {
+        DispatchCommand::Queue {
+            vault_path,
+            state_file,
+            output,
+        } => {
+            let entries = dispatch_queue(&vault_path, state_file).map_err(JarvisError::from)?;
+            println!(
+                "{}",
+                render_dispatch_queue_output(&entries, output).map_err(JarvisError::from)?
+            );
+            Ok(())
+        }
+        DispatchCommand::Schedule {
+            vault_path,
+            state_file,
+            output,
+        } => {
+            let entries = dispatch_schedule(&vault_path, state_file).map_err(JarvisError::from)?;
+            println!(
+                "{}",
+                render_dispatch_schedule_output(&entries, output).map_err(JarvisError::from)?
+            );
+            Ok(())
+        }
+    }
+}
+
+fn secret_command(command: SecretCommand) -> Result<(), JarvisError> {
+    match command {
+        SecretCommand::Reveal {
+            name,
+            resource_namespace,
+            key,
+            request,
+            output,
+        } => {
+            let result = reveal_secret(
+                &name,
+                resource_namespace.as_deref(),
+                key.as_deref(),
+                request.as_deref(),
+            )
+            .map_err(JarvisError::from)?;
+            println!(
+                "{}",
+                render_secret_reveal_output(&result, output).map_err(JarvisError::from)?
+            );
+            Ok(())
+        }
+        SecretCommand::RotateKey { output } => {
+            let result = rotate_secret_key().map_err(JarvisError::from)?;
+            println!(
+                "{}",
+                render_secret_key_rotation_output(&result, output).map_err(JarvisError::from)?
+            );
+            Ok(())
+        }
+    }
+}
+
+fn ticket_command(command: TicketCommand) -> Result<(), JarvisError> {
+    match command {
+        TicketCommand::Graph {
+            vault_path,
+            board,
+            output,
+        } => {
+            let report = load_vault_ticket_graph(&vault_path, &board)
+                .map_err(JarvisError::from)?
+                .report();
+            println!(
+                "{}",
+                render_ticket_graph_output(&report, output).map_err(JarvisError::from)?
+            );
+            if !report.cycles.is_empty() {
+                return Err(JarvisError::Other(anyhow::anyhow!(
+                    "ticket dependency graph has {} cycle(s)",
+                    report.cycles.len()
+                )));
+            }
+            Ok(())
+        }
+        TicketCommand::Lint {
+            vault_path,
+            paths,
+            output,
+        } => {
+            let vault_path = vault_path.or_else(|| {
+                paths
+                    .is_empty()
+                    .then(|| PathBuf::from("/home/rootster/codex"))
+            });
+            let report = lint_tickets(vault_path.as_deref(), &paths).map_err(JarvisError::from)?;
+            println!(
+                "{}",
+                render_ticket_lint_output(&report, output).map_err(JarvisError::from)?
+            );
+            if report.errors > 0 {
+                return Err(JarvisError::Other(anyhow::anyhow!(
+                    "ticket lint found {} error(s)",
+                    report.errors
+                )));
+            }
+            Ok(())
+        }
+        TicketCommand::New {
+            vault_path,
+            template,
+            title,
+            project,
+            repo_path,
+            priority,
+            column,
+            board,
+            output,
+        } => {
+            let result = create_ticket(TicketNewOptions {
+                vault_path,
+                template,
+                title,
+                project,
+                repo_path,
+                priority,
+                column,
+                board,
+            })
+            .map_err(JarvisError::from)?;
+            println!(
+                "{}",
+                render_ticket_new_output(&result, output).map_err(JarvisError::from)?
+            );
+            Ok(())
+        }
+        TicketCommand::Templates { output } => {
+            let templates = ticket_templates().map_err(JarvisError::from)?;
+            println!(
+                "{}",
+                render_ticket_templates_output(&templates, output).map_err(JarvisError::from)?
+            );
+            Ok(())
+        }
+    }
+}
+
 fn mission_command(command: MissionCommand) -> Result<(), JarvisError> {
     match command {
         MissionCommand::Create {
@@ -6732,6 +7448,127 @@ fn append_cli_mission_event(
     .map_err(JarvisError::from)
 }
 
+fn usage_command(
+    ticket: Option<&str>,
+    mission: Option<&str>,
+    namespace: Option<&str>,
+    by: TokenUsageGrouping,
+    output: ControlPlaneOutput,
+) -> Result<(), JarvisError> {
+    let mut records = load_usage_records().map_err(JarvisError::from)?;
+    if let Some(ticket) = ticket {
+        records = records_for_ticket(records, ticket);
+    }
+    if let Some(mission) = mission {
+        let detail = show_mission(mission).map_err(JarvisError::from)?;
+        records = records_for_mission(records, &detail.mission);
+    }
+    if let Some(namespace) = namespace {
+        records.retain(|record| record.namespace == namespace);
+    }
+    let rollups = rollup_usage(&records, by);
+    println!(
+        "{}",
+        render_usage_output(&rollups, output).map_err(JarvisError::from)?
+    );
+    Ok(())
+}
+
+fn events_command(
+    namespace: Option<&str>,
+    cluster: bool,
+    local_only: bool,
+    kinds: &[RuntimeEventKind],
+    follow: bool,
+    json: bool,
+) -> Result<(), JarvisError> {
+    let sink = |event: RuntimeEvent| {
+        let line = if json {
+            serde_json::to_string(&event)?
+        } else {
+            render_runtime_event(&event)
+        };
+        let mut stdout = io::stdout().lock();
+        writeln!(stdout, "{}", line)?;
+        stdout.flush()?;
+        Ok(())
+    };
+    match namespace {
+        Some(namespace) if !cluster => stream_runtime_events(namespace, kinds, follow, sink),
+        _ => stream_cluster_events(kinds, follow, local_only, sink),
+    }
+    .map_err(JarvisError::from)
+}
+
+fn render_runtime_event(event: &RuntimeEvent) -> String {
+    let mut line = format!(
+        "{} {}{} {}",
+        event.timestamp_epoch_ms,
+        event
+            .node
+            .as_deref()
+            .map(|node| format!("{}/", node))
+            .unwrap_or_default(),
+        event.namespace,
+        event
+            .kind
+            .to_possible_value()
+            .map(|value| value.get_name().to_string())
+            .unwrap_or_default()
+    );
+    for (label, value) in [
+        ("agent", &event.agent),
+        ("id", &event.id),
+        ("status", &event.status),
+    ] {
+        if let Some(value) = value {
+            line.push_str(&format!(" {}={}", label, value));
+        }
+    }
+    if let Some(detail) = &event.detail {
+        line.push_str(&format!(" {}", detail));
+    }
+    line
+}
+
+fn agent_command(command: AgentCommand) -> Result<(), JarvisError> {
+    match command {
+        AgentCommand::Add {
+            namespace,
+            name,
+            working_directory,
+            restart,
+            max_restarts,
+            restart_backoff_ms,
+            command,
+        } => {
+            let restart_policy = restart.map(|policy| AgentRestartPolicy {
+                policy,
+                max_retries: max_restarts,
+                backoff_ms: restart_backoff_ms,
+            });
+            let (agent, pid) = add_runtime_agent(
+                &namespace,
+                name.as_deref(),
+                working_directory.as_deref(),
+                &shell_words::join(&command),
+                restart_policy,
+            )
+            .map_err(JarvisError::from)?;
+            println!(
+                "✅ Added {} (pid {}) to '{}'. Attach: jarvisctl exec --namespace {} --agent {}",
+                agent, pid, namespace, namespace, agent
+            );
+            Ok(())
+        }
+        AgentCommand::Rm { namespace, name } => {
+            remove_runtime_agent(&namespace, &name).map_err(JarvisError::from)?;
+            println!("✅ Removed {} from '{}'", name, namespace);
+            Ok(())
+        }
+    }
+}
+
 fn rollout_command(command: RolloutCommand) -> Result<(), JarvisError> {
     match command {
         RolloutCommand::Status {
@@ -7559,6 +8396,39 @@ fn print_runtime_sessions(sessions: &[NativeSessionMetadata]) {
                 if let Some(session_id) = context.codex_session_id.as_deref() {
                     summary.push_str(&format!(" session={}", session_id));
                 }
+                if let Some(usage) = context.token_usage {
+                    summary.push_str(&format!(
+                        " tokens={}",
+                        compact_token_count(usage.total_tokens)
+                    ));
+                    if let Some(budget) = context.token_budget {
+                        summary.push_str(&format!("/{}", compact_token_count(budget)));
+                    }
+                }
+            }
+            if agent.restarts > 0 {
+                summary.push_str(&format!(" restarts={}", agent.restarts));
+            }
+            if let Some(code) = agent.last_exit_code {
+                summary.push_str(&format!(" last_exit={}", code));
+            }
+            if agent.idle {
+                summary.push_str(" idle=true");
+            }
+            let clients = session
+                .clients
+                .iter()
+                .filter(|client| client.agent == agent.name)
+                .collect::<Vec<_>>();
+            if !clients.is_empty() {
+                let read_only = clients
+                    .iter()
+                    .filter(|client| client.mode == "read-only")
+                    .count();
+                summary.push_str(&format!(" clients={}", clients.len()));
+                if read_only > 0 {
+                    summary.push_str(&format!(" ({} read-only)", read_only));
+                }
             }
             println!("{}", summary);
         }
@@ -7571,6 +8441,8 @@ fn history(
     namespace: &str,
     include_turns: bool,
     json: bool,
+    format: TimelineFormat,
+    full: bool,
 ) -> Result<(), JarvisError> {
     let _ = backend;
     let metadata = runtime::session_metadata_for_namespace(namespace).map_err(JarvisError::from)?;
@@ -7590,6 +8462,18 @@ fn history(
         );
         return Ok(());
     }
+    if format != TimelineFormat::Text || full {
+        let server_requests = metadata
+            .context
+            .map(|context| context.server_requests)
+            .unwrap_or_default();
+        let timeline = build_timeline(namespace, &response, &server_requests, full);
+        println!(
+            "{}",
+            render_timeline(&timeline, format).map_err(JarvisError::from)?
+        );
+        return Ok(());
+    }
 
     print_thread_history(namespace, &response);
     Ok(())
@@ -7867,9 +8751,14 @@ fn truncate_plain(raw: &str, limit: usize) -> String {
 }
 
 #[instrument(err)]
-fn exec_agent(backend: SessionBackend, namespace: &str, agent: &str) -> Result<(), JarvisError> {
+fn exec_agent(
+    backend: SessionBackend,
+    namespace: &str,
+    agent: &str,
+    read_only: bool,
+) -> Result<(), JarvisError> {
     let _ = backend;
-    attach_runtime_session(namespace, agent).map_err(JarvisError::from)
+    attach_runtime_session(namespace, agent, read_only).map_err(JarvisError::from)
 }
 
 #[derive(Debug)]
@@ -8069,10 +8958,16 @@ fn launch_and_print_codex(options: CodexLaunchOptions) -> Result<(), JarvisError
 }
 
 #[instrument(err)]
-fn attach_session(backend: SessionBackend, namespace: &str) -> Result<(), JarvisError> {
+fn attach_session(
+    backend: SessionBackend,
+    namespace: &str,
+    read_only: bool,
+) -> Result<(), JarvisError> {
     let _ = backend;
-    if let Err(local_error) = attach_runtime_session(namespace, "agent0") {
-        if !attach_cluster_runtime_session(namespace, "agent0").map_err(JarvisError::from)? {
+    if let Err(local_error) = attach_runtime_session(namespace, "agent0", read_only) {
+        if !attach_cluster_runtime_session(namespace, "agent0", read_only)
+            .map_err(JarvisError::from)?
+        {
             return Err(JarvisError::from(local_error));
         }
     }
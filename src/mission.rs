@@ -1,4 +1,6 @@
 use crate::control_plane::ControlPlaneOutput;
+use crate::native::RuntimeTokenUsage;
+use crate::usage::{load_usage_records, records_for_mission, total_usage};
 use anyhow::{Context, anyhow, bail};
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
@@ -65,6 +67,8 @@ pub struct MissionEvent {
 pub struct MissionDetail {
     pub mission: MissionRecord,
     pub events: Vec<MissionEvent>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_usage: Option<RuntimeTokenUsage>,
 }
 
 #[derive(Debug, Clone)]
@@ -333,10 +337,20 @@ pub fn list_missions() -> anyhow::Result<Vec<MissionRecord>> {
     Ok(missions)
 }
 
+pub fn mission_exists(id: &str) -> anyhow::Result<bool> {
+    Ok(mission_path(id)?.exists())
+}
+
 pub fn show_mission(id: &str) -> anyhow::Result<MissionDetail> {
+    let mission = load_mission(id)?;
+    let token_usage = load_usage_records()
+        .ok()
+        .map(|records| total_usage(&records_for_mission(records, &mission)))
+        .filter(|usage| !usage.is_empty());
     Ok(MissionDetail {
-        mission: load_mission(id)?,
+        mission,
         events: read_mission_events(id)?,
+        token_usage,
     })
 }
 
@@ -456,6 +470,13 @@ fn render_mission_detail_table(detail: &MissionDetail) -> String {
         format!("nodes\t{}", mission.nodes.join(", ")),
         format!("evidence\t{}", mission.evidence.join(", ")),
         format!("outcome\t{}", mission.outcome.as_deref().unwrap_or("-")),
+        format!(
+            "tokens\t{}",
+            detail
+                .token_usage
+                .map(|usage| usage.summary())
+                .unwrap_or_else(|| "-".to_string())
+        ),
         "".to_string(),
         "EVENT\tSTAGE\tSTATUS\tSUMMARY".to_string(),
     ];
@@ -578,7 +599,7 @@ fn mission_events_dir() -> anyhow::Result<PathBuf> {
     Ok(jarvis_codex_dir()?.join("mission-events"))
 }
 
-fn jarvis_codex_dir() -> anyhow::Result<PathBuf> {
+pub(crate) fn jarvis_codex_dir() -> anyhow::Result<PathBuf> {
     if let Some(path) = env::var_os("JARVIS_CODEX_DIR") {
         return Ok(PathBuf::from(path));
     }
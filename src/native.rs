@@ -1,6 +1,7 @@
 use anyhow::{Context, anyhow, bail, ensure};
 use base64::Engine;
 use base64::engine::general_purpose::STANDARD as BASE64;
+use clap::ValueEnum;
 use crossterm::{
     cursor,
     event::{
@@ -20,12 +21,18 @@ use std::os::unix::net::{UnixListener, UnixStream};
 use std::os::unix::process::CommandExt;
 use std::path::PathBuf;
 use std::process::{Command, Stdio};
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex, mpsc};
 use std::thread;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tracing::debug;
 
+use crate::events::{
+    RuntimeEvent, RuntimeEventBus, RuntimeEventKind, diff_session_events, pump_subscription,
+};
+use crate::operator_request::{OperatorRequestCreateOptions, create_operator_request};
+use crate::recording::{SessionRecorder, latest_recording_segments, replay_segments};
+use crate::trigger::{OutputTriggerAction, OutputTriggerRule, OutputTriggerSet, TriggerHit};
 use crossterm::terminal::size as terminal_size;
 
 const SESSION_DIR_NAME: &str = "sessions";
@@ -33,7 +40,10 @@ const MANIFEST_DIR_NAME: &str = "manifests";
 const COMPLETION_DIR_NAME: &str = "completions";
 const SOCKET_FILE_NAME: &str = "control.sock";
 const METADATA_FILE_NAME: &str = "metadata.json";
+const RECORDING_DIR_NAME: &str = "recordings";
 const LOG_LIMIT_BYTES: usize = 512 * 1024;
+const RESTART_BACKOFF_CAP_MS: u64 = 5 * 60 * 1000;
+const RESTART_STABLE_RUN: Duration = Duration::from_secs(10 * 60);
 const ALT_DETACH_BYTE: u8 = 0x1c;
 const PRIMARY_DETACH_BYTE: u8 = 0x1d;
 
@@ -57,6 +67,7 @@ struct AttachBindings {
     leader_label: &'static str,
     leader_key_label: &'static str,
     literal_key: u8,
+    read_only: bool,
 }
 
 struct NativeAttachRawMode {
@@ -83,6 +94,17 @@ struct NativeSessionManifest {
     initial_rows: Option<u16>,
     initial_cols: Option<u16>,
     created_at_epoch_ms: u128,
+    #[serde(default)]
+    restart_policy: AgentRestartPolicy,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    triggers: Vec<OutputTriggerRule>,
+}
+
+/// Per-session supervision settings applied to every agent the server spawns.
+#[derive(Debug, Clone, Default)]
+pub struct NativeSessionOptions {
+    pub restart_policy: AgentRestartPolicy,
+    pub triggers: Vec<OutputTriggerRule>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -149,6 +171,69 @@ pub struct RuntimeSubagentMetadata {
     pub recent_actions: Vec<RuntimeSubagentAction>,
 }
 
+/// Token counts reported by the Codex app-server, either for one turn or cumulative.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize, Default)]
+pub struct RuntimeTokenUsage {
+    #[serde(default)]
+    pub input_tokens: u64,
+    #[serde(default)]
+    pub cached_input_tokens: u64,
+    #[serde(default)]
+    pub output_tokens: u64,
+    #[serde(default)]
+    pub reasoning_output_tokens: u64,
+    #[serde(default)]
+    pub total_tokens: u64,
+}
+
+impl RuntimeTokenUsage {
+    pub fn is_empty(&self) -> bool {
+        self.total_tokens == 0 && self.input_tokens == 0 && self.output_tokens == 0
+    }
+
+    pub fn add(&mut self, other: &Self) {
+        self.input_tokens += other.input_tokens;
+        self.cached_input_tokens += other.cached_input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.reasoning_output_tokens += other.reasoning_output_tokens;
+        self.total_tokens += other.total_tokens;
+    }
+
+    pub fn since(&self, baseline: &Self) -> Self {
+        Self {
+            input_tokens: self.input_tokens.saturating_sub(baseline.input_tokens),
+            cached_input_tokens: self
+                .cached_input_tokens
+                .saturating_sub(baseline.cached_input_tokens),
+            output_tokens: self.output_tokens.saturating_sub(baseline.output_tokens),
+            reasoning_output_tokens: self
+                .reasoning_output_tokens
+                .saturating_sub(baseline.reasoning_output_tokens),
+            total_tokens: self.total_tokens.saturating_sub(baseline.total_tokens),
+        }
+    }
+
+    /// `in=… out=… reasoning=… total=…` with compact counts.
+    pub fn summary(&self) -> String {
+        format!(
+            "in={} cached={} out={} reasoning={} total={}",
+            compact_token_count(self.input_tokens),
+            compact_token_count(self.cached_input_tokens),
+            compact_token_count(self.output_tokens),
+            compact_token_count(self.reasoning_output_tokens),
+            compact_token_count(self.total_tokens)
+        )
+    }
+}
+
+pub fn compact_token_count(count: u64) -> String {
+    match count {
+        0..1_000 => count.to_string(),
+        1_000..1_000_000 => format!("{:.1}k", count as f64 / 1_000.0),
+        _ => format!("{:.2}M", count as f64 / 1_000_000.0),
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct RuntimeContextMetadata {
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -221,6 +306,12 @@ pub struct RuntimeContextMetadata {
     pub server_requests: Vec<RuntimeServerRequest>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub subagents: Vec<RuntimeSubagentMetadata>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub turn_token_usage: Option<RuntimeTokenUsage>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_usage: Option<RuntimeTokenUsage>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_budget: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -233,6 +324,19 @@ pub struct NativeSessionMetadata {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub context: Option<RuntimeContextMetadata>,
     pub agents: Vec<NativeAgentMetadata>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub clients: Vec<NativeAttachedClient>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NativeAttachedClient {
+    pub agent: String,
+    pub mode: String,
+    pub attached_at_epoch_ms: u128,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pid: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tty: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -242,6 +346,82 @@ pub struct NativeAgentMetadata {
     pub running: bool,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub exit_code: Option<i32>,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub restarts: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_exit_code: Option<i32>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub idle: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum RestartPolicyKind {
+    #[default]
+    Never,
+    OnFailure,
+    Always,
+}
+
+/// Decides whether a native PTY agent is respawned after its child exits.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub struct AgentRestartPolicy {
+    #[serde(default)]
+    pub policy: RestartPolicyKind,
+    #[serde(default = "default_max_restarts", rename = "maxRetries")]
+    pub max_retries: u32,
+    #[serde(default = "default_restart_backoff_ms", rename = "backoffMs")]
+    pub backoff_ms: u64,
+}
+
+impl Default for AgentRestartPolicy {
+    fn default() -> Self {
+        Self {
+            policy: RestartPolicyKind::Never,
+            max_retries: default_max_restarts(),
+            backoff_ms: default_restart_backoff_ms(),
+        }
+    }
+}
+
+impl AgentRestartPolicy {
+    pub fn is_never(&self) -> bool {
+        self.policy == RestartPolicyKind::Never
+    }
+
+    /// Returns the delay before restart number `attempt` (1-based), or `None` to stay down.
+    fn restart_delay(&self, exit_code: Option<i32>, attempt: u32) -> Option<Duration> {
+        let wanted = match self.policy {
+            RestartPolicyKind::Never => false,
+            RestartPolicyKind::OnFailure => exit_code != Some(0),
+            RestartPolicyKind::Always => true,
+        };
+        if !wanted || attempt == 0 || attempt > self.max_retries {
+            return None;
+        }
+        let factor = 1_u64 << (attempt - 1).min(16);
+        Some(Duration::from_millis(
+            self.backoff_ms
+                .saturating_mul(factor)
+                .min(RESTART_BACKOFF_CAP_MS),
+        ))
+    }
+}
+
+fn default_max_restarts() -> u32 {
+    5
+}
+
+fn default_restart_backoff_ms() -> u64 {
+    1_000
+}
+
+fn is_zero(value: &u32) -> bool {
+    *value == 0
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -266,6 +446,10 @@ enum ClientMessage {
         agent: String,
         rows: Option<u16>,
         cols: Option<u16>,
+        #[serde(default)]
+        read_only: bool,
+        #[serde(default)]
+        tty: Option<String>,
     },
     Interrupt {
         agent: String,
@@ -284,6 +468,25 @@ enum ClientMessage {
         text: String,
         press_enter: bool,
     },
+    SpawnAgent {
+        #[serde(default)]
+        agent: Option<String>,
+        #[serde(default)]
+        working_directory: Option<String>,
+        #[serde(default)]
+        shell_command: Option<String>,
+        #[serde(default)]
+        restart_policy: Option<AgentRestartPolicy>,
+    },
+    KillAgent {
+        agent: String,
+    },
+    Subscribe {
+        #[serde(default)]
+        kinds: Vec<RuntimeEventKind>,
+        #[serde(default)]
+        follow: bool,
+    },
     KillSession,
 }
 
@@ -294,6 +497,8 @@ enum ServerMessage {
     Error { message: String },
     Metadata(NativeSessionMetadata),
     Attached { namespace: String, agent: String },
+    AgentSpawned { agent: String, pid: u32 },
+    Event(RuntimeEvent),
     Output { data_base64: String },
     Exited { agent: String },
 }
@@ -305,19 +510,45 @@ struct NativeSession {
     shell_command: String,
     context: Option<RuntimeContextMetadata>,
     session_dir: PathBuf,
-    agents: BTreeMap<String, Arc<ManagedAgent>>,
+    restart_policy: AgentRestartPolicy,
+    triggers: Vec<OutputTriggerRule>,
+    agents: Mutex<BTreeMap<String, Arc<ManagedAgent>>>,
+    clients: Mutex<BTreeMap<u64, NativeAttachedClient>>,
+    next_client_id: AtomicU64,
+    events: RuntimeEventBus,
     shutdown_requested: AtomicBool,
 }
 
+struct AttachedClientGuard {
+    session: Arc<NativeSession>,
+    id: u64,
+}
+
+impl Drop for AttachedClientGuard {
+    fn drop(&mut self) {
+        self.session.clients.lock().unwrap().remove(&self.id);
+    }
+}
+
 struct ManagedAgent {
     name: String,
-    pid: u32,
+    working_dir: Option<String>,
+    shell_command: String,
+    restart_policy: AgentRestartPolicy,
+    pid: AtomicU32,
+    size: Mutex<(u16, u16)>,
     running: AtomicBool,
+    stopping: AtomicBool,
+    restarts: AtomicU32,
     exit_code: Mutex<Option<i32>>,
+    last_exit_code: Mutex<Option<i32>>,
     master: Mutex<Box<dyn MasterPty + Send>>,
     writer: Mutex<Box<dyn Write + Send>>,
     child: Mutex<Box<dyn portable_pty::Child + Send>>,
     log: Mutex<VecDeque<u8>>,
+    recorder: Mutex<Option<SessionRecorder>>,
+    triggers: Mutex<Option<OutputTriggerSet>>,
+    idle: AtomicBool,
     subscribers: Mutex<Vec<mpsc::Sender<Vec<u8>>>>,
 }
 
@@ -332,14 +563,29 @@ impl NativeSession {
             context: self.context.clone(),
             agents: self
                 .agents
+                .lock()
+                .unwrap()
                 .values()
                 .map(|agent| NativeAgentMetadata {
                     name: agent.name.clone(),
-                    pid: agent.pid,
+                    pid: agent.pid.load(Ordering::SeqCst),
                     running: agent.is_running(),
                     exit_code: *agent.exit_code.lock().unwrap(),
+                    restarts: agent.restarts.load(Ordering::SeqCst),
+                    last_exit_code: *agent.last_exit_code.lock().unwrap(),
+                    idle: agent.idle.load(Ordering::SeqCst),
                 })
                 .collect(),
+            clients: self.clients.lock().unwrap().values().cloned().collect(),
+        }
+    }
+
+    fn register_client(self: &Arc<Self>, client: NativeAttachedClient) -> AttachedClientGuard {
+        let id = self.next_client_id.fetch_add(1, Ordering::SeqCst);
+        self.clients.lock().unwrap().insert(id, client);
+        AttachedClientGuard {
+            session: Arc::clone(self),
+            id,
         }
     }
 
@@ -359,13 +605,18 @@ impl NativeSession {
     }
 
     fn agent(&self, name: &str) -> anyhow::Result<Arc<ManagedAgent>> {
-        self.agents.get(name).cloned().ok_or_else(|| {
-            anyhow!(
-                "native session '{}' has no agent '{}'",
-                self.namespace,
-                name
-            )
-        })
+        self.agents
+            .lock()
+            .unwrap()
+            .get(name)
+            .cloned()
+            .ok_or_else(|| {
+                anyhow!(
+                    "native session '{}' has no agent '{}'",
+                    self.namespace,
+                    name
+                )
+            })
     }
 
     fn shutdown(&self) {
@@ -375,7 +626,7 @@ impl NativeSession {
 
         let _ = self.write_completion_record();
 
-        for agent in self.agents.values() {
+        for agent in self.agents.lock().unwrap().values() {
             let _ = agent.kill();
         }
 
@@ -385,7 +636,118 @@ impl NativeSession {
     }
 
     fn all_agents_exited(&self) -> bool {
-        self.agents.values().all(|agent| !agent.is_running())
+        self.agents
+            .lock()
+            .unwrap()
+            .values()
+            .all(|agent| !agent.is_running())
+    }
+
+    fn spawn_agent(
+        &self,
+        name: Option<String>,
+        working_directory: Option<String>,
+        shell_command: Option<String>,
+        restart_policy: Option<AgentRestartPolicy>,
+    ) -> anyhow::Result<String> {
+        // Only hold the agents lock to pick the name and size; forking the PTY child can be
+        // slow and must not stall output, resize, and attach handling for the other agents.
+        let (name, size) = {
+            let agents = self.agents.lock().unwrap();
+            let name = match name {
+                Some(name) => {
+                    ensure!(
+                        !name.is_empty()
+                            && name
+                                .chars()
+                                .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_'),
+                        "agent name '{}' may only contain letters, digits, '-' and '_'",
+                        name
+                    );
+                    self.ensure_agent_slot_free(&agents, &name)?;
+                    name
+                }
+                None => (0..)
+                    .map(|index| format!("agent{}", index))
+                    .find(|candidate| !agents.contains_key(candidate))
+                    .expect("unbounded agent index range"),
+            };
+            let size = agents
+                .values()
+                .next()
+                .map(|agent| *agent.size.lock().unwrap())
+                .unwrap_or((24, 80));
+            (name, size)
+        };
+        let working_directory = working_directory.or_else(|| self.working_directory.clone());
+        let shell_command = shell_command.unwrap_or_else(|| self.shell_command.clone());
+        let recorder = SessionRecorder::create(
+            &self.session_dir.join(RECORDING_DIR_NAME),
+            &name,
+            now_epoch_ms()?,
+            &format!("{}/{}", self.namespace, name),
+            size,
+        )
+        .map_err(|error| debug!(agent = %name, %error, "native recording unavailable"))
+        .ok();
+        let triggers = OutputTriggerSet::for_agent(&self.triggers, &self.namespace, &name)?;
+        let agent = spawn_managed_agent(
+            &name,
+            working_directory.as_deref(),
+            &shell_command,
+            Some(size),
+            restart_policy.unwrap_or(self.restart_policy),
+            recorder,
+            triggers,
+        )?;
+
+        let mut agents = self.agents.lock().unwrap();
+        // A concurrent `agent add` may have claimed the same name while the child started.
+        if let Err(error) = self.ensure_agent_slot_free(&agents, &name) {
+            drop(agents);
+            let _ = agent.kill();
+            return Err(error);
+        }
+        agents.insert(name.clone(), agent);
+        Ok(name)
+    }
+
+    fn ensure_agent_slot_free(
+        &self,
+        agents: &BTreeMap<String, Arc<ManagedAgent>>,
+        name: &str,
+    ) -> anyhow::Result<()> {
+        if let Some(existing) = agents.get(name) {
+            ensure!(
+                !existing.is_running(),
+                "native session '{}' already has a running agent '{}'",
+                self.namespace,
+                name
+            );
+        }
+        Ok(())
+    }
+
+    fn remove_agent(&self, name: &str) -> anyhow::Result<()> {
+        let mut agents = self.agents.lock().unwrap();
+        ensure!(
+            agents.contains_key(name),
+            "native session '{}' has no agent '{}'",
+            self.namespace,
+            name
+        );
+        ensure!(
+            agents
+                .iter()
+                .any(|(other, agent)| other != name && agent.is_running()),
+            "'{}' is the last running agent in '{}'; use `jarvisctl delete --namespace {}` to close the namespace",
+            name,
+            self.namespace,
+            self.namespace
+        );
+        let agent = agents.remove(name).expect("agent presence checked above");
+        drop(agents);
+        agent.kill()
     }
 
     fn write_completion_record(&self) -> anyhow::Result<()> {
@@ -394,6 +756,8 @@ impl NativeSession {
             created_at_epoch_ms: self.created_at_epoch_ms,
             agents: self
                 .agents
+                .lock()
+                .unwrap()
                 .values()
                 .map(|agent| NativeAgentCompletion {
                     name: agent.name.clone(),
@@ -437,11 +801,52 @@ impl ManagedAgent {
             }
         }
 
+        {
+            let mut recorder = self.recorder.lock().unwrap();
+            if let Some(active) = recorder.as_mut()
+                && let Err(error) = active.record_output(chunk)
+            {
+                debug!(agent = %self.name, %error, "native recording disabled");
+                *recorder = None;
+            }
+        }
+
+        let hits = match self.triggers.lock().unwrap().as_mut() {
+            Some(triggers) => triggers.observe(chunk),
+            None => Vec::new(),
+        };
+        for hit in hits {
+            self.fire_trigger(hit);
+        }
+
         let mut subscribers = self.subscribers.lock().unwrap();
         subscribers.retain(|sender| sender.send(chunk.to_vec()).is_ok());
     }
 
+    fn fire_trigger(&self, hit: TriggerHit) {
+        debug!(agent = %self.name, pattern = %hit.pattern, action = ?hit.action, "output trigger matched");
+        match hit.action {
+            OutputTriggerAction::Interrupt => {
+                let _ = self.interrupt();
+            }
+            OutputTriggerAction::SendText => {
+                if let Some(text) = hit.text.as_deref() {
+                    let _ = self.send_text(text, true);
+                }
+            }
+            OutputTriggerAction::MarkIdle => self.idle.store(true, Ordering::SeqCst),
+            OutputTriggerAction::Notify | OutputTriggerAction::OperatorRequest => {
+                thread::spawn(move || {
+                    if let Err(error) = deliver_trigger_hit(&hit) {
+                        debug!(%error, "output trigger delivery failed");
+                    }
+                });
+            }
+        }
+    }
+
     fn send_input(&self, bytes: &[u8]) -> anyhow::Result<()> {
+        self.idle.store(false, Ordering::SeqCst);
         let mut writer = self.writer.lock().unwrap();
         writer
             .write_all(bytes)
@@ -468,11 +873,16 @@ impl ManagedAgent {
             })
             .context("failed to resize native PTY")?;
         drop(master);
+        *self.size.lock().unwrap() = (rows, cols);
+        if let Some(recorder) = self.recorder.lock().unwrap().as_mut() {
+            let _ = recorder.record_resize(rows, cols);
+        }
         self.signal(libc::SIGWINCH)
             .context("failed to signal native PTY resize")
     }
 
     fn kill(&self) -> anyhow::Result<()> {
+        self.stopping.store(true, Ordering::SeqCst);
         let mut child = self.child.lock().unwrap();
         child.kill().context("failed to kill native PTY child")
     }
@@ -492,7 +902,8 @@ impl ManagedAgent {
             }
         }
 
-        let pid = i32::try_from(self.pid).context("native agent pid does not fit in i32")?;
+        let pid = self.pid.load(Ordering::SeqCst);
+        let pid = i32::try_from(pid).context("native agent pid does not fit in i32")?;
         let pgid = unsafe { libc::getpgid(pid) };
         if pgid > 0 {
             let status = unsafe { libc::killpg(pgid, signal) };
@@ -509,7 +920,7 @@ impl ManagedAgent {
             "failed to send signal {} to native agent '{}' (pid {})",
             signal,
             self.name,
-            self.pid
+            pid
         ))
     }
 }
@@ -520,6 +931,7 @@ pub fn spawn_native_session(
     working_dir: Option<&str>,
     shell_command: &str,
     context: Option<RuntimeContextMetadata>,
+    options: NativeSessionOptions,
 ) -> anyhow::Result<()> {
     ensure!(
         !namespace.trim().is_empty(),
@@ -537,6 +949,8 @@ pub fn spawn_native_session(
         initial_rows: current_attach_viewport().map(|viewport| viewport.content_rows),
         initial_cols: current_attach_viewport().map(|viewport| viewport.cols),
         created_at_epoch_ms: now_epoch_ms()?,
+        restart_policy: options.restart_policy,
+        triggers: options.triggers,
     };
 
     let manifest_dir = native_root()?.join(MANIFEST_DIR_NAME);
@@ -593,14 +1007,30 @@ pub fn serve_native_session(manifest_path: PathBuf) -> anyhow::Result<()> {
         let _ = fs::remove_file(&socket_path);
     }
 
+    let recording_dir = session_dir.join(RECORDING_DIR_NAME);
+    let initial_size = manifest.initial_rows.zip(manifest.initial_cols);
     let mut agents = BTreeMap::new();
     for index in 0..manifest.agents {
         let agent_name = format!("agent{}", index);
+        let recorder = SessionRecorder::create(
+            &recording_dir,
+            &agent_name,
+            manifest.created_at_epoch_ms,
+            &format!("{}/{}", manifest.namespace, agent_name),
+            initial_size.unwrap_or((24, 80)),
+        )
+        .map_err(|error| debug!(agent = %agent_name, %error, "native recording unavailable"))
+        .ok();
+        let triggers =
+            OutputTriggerSet::for_agent(&manifest.triggers, &manifest.namespace, &agent_name)?;
         let agent = spawn_managed_agent(
             &agent_name,
             manifest.working_directory.as_deref(),
             &manifest.shell_command,
-            manifest.initial_rows.zip(manifest.initial_cols),
+            initial_size,
+            manifest.restart_policy,
+            recorder,
+            triggers,
         )?;
         agents.insert(agent_name, agent);
     }
@@ -612,10 +1042,19 @@ pub fn serve_native_session(manifest_path: PathBuf) -> anyhow::Result<()> {
         shell_command: manifest.shell_command,
         context: manifest.context,
         session_dir: session_dir.clone(),
-        agents,
+        restart_policy: manifest.restart_policy,
+        triggers: manifest.triggers,
+        agents: Mutex::new(agents),
+        clients: Mutex::new(BTreeMap::new()),
+        next_client_id: AtomicU64::new(0),
+        events: RuntimeEventBus::default(),
         shutdown_requested: AtomicBool::new(false),
     });
     session.write_metadata()?;
+    let mut last_metadata = NativeSessionMetadata {
+        agents: Vec::new(),
+        ..session.metadata()
+    };
 
     let listener = UnixListener::bind(&socket_path)
         .with_context(|| format!("failed to bind '{}'", socket_path.display()))?;
@@ -640,6 +1079,12 @@ pub fn serve_native_session(manifest_path: PathBuf) -> anyhow::Result<()> {
             }
         }
 
+        let metadata = session.metadata();
+        session
+            .events
+            .publish(diff_session_events(&last_metadata, &metadata));
+        last_metadata = metadata;
+
         if session.all_agents_exited() {
             let _ = session.write_completion_record();
             session.shutdown_requested.store(true, Ordering::SeqCst);
@@ -647,6 +1092,10 @@ pub fn serve_native_session(manifest_path: PathBuf) -> anyhow::Result<()> {
     }
 
     session.shutdown();
+    session
+        .events
+        .publish(diff_session_events(&last_metadata, &session.metadata()));
+    session.events.close(Duration::from_millis(500));
     Ok(())
 }
 
@@ -786,12 +1235,120 @@ pub fn interrupt_native(namespace: &str, agent: &str) -> anyhow::Result<()> {
     }
 }
 
-pub fn attach_native(namespace: &str, agent: &str) -> anyhow::Result<()> {
+pub fn spawn_native_agent(
+    namespace: &str,
+    agent: Option<&str>,
+    working_dir: Option<&str>,
+    shell_command: Option<&str>,
+    restart_policy: Option<AgentRestartPolicy>,
+) -> anyhow::Result<(String, u32)> {
+    let response = request(
+        namespace,
+        &ClientMessage::SpawnAgent {
+            agent: agent.map(ToOwned::to_owned),
+            working_directory: working_dir.map(ToOwned::to_owned),
+            shell_command: shell_command.map(ToOwned::to_owned),
+            restart_policy,
+        },
+    )?;
+    match response {
+        ServerMessage::AgentSpawned { agent, pid } => Ok((agent, pid)),
+        ServerMessage::Error { message } => Err(anyhow!(message)),
+        other => Err(anyhow!(
+            "unexpected response while adding an agent to native session '{}': {:?}",
+            namespace,
+            other
+        )),
+    }
+}
+
+pub fn kill_native_agent(namespace: &str, agent: &str) -> anyhow::Result<()> {
+    let response = request(
+        namespace,
+        &ClientMessage::KillAgent {
+            agent: agent.to_string(),
+        },
+    )?;
+    match response {
+        ServerMessage::Ok => Ok(()),
+        ServerMessage::Error { message } => Err(anyhow!(message)),
+        other => Err(anyhow!(
+            "unexpected response while removing native agent '{}:{}': {:?}",
+            namespace,
+            agent,
+            other
+        )),
+    }
+}
+
+pub fn stream_native_events(
+    namespace: &str,
+    kinds: &[RuntimeEventKind],
+    follow: bool,
+    mut sink: impl FnMut(RuntimeEvent) -> anyhow::Result<()>,
+) -> anyhow::Result<()> {
+    let socket_path = socket_path_for(namespace)?;
+    let mut stream = UnixStream::connect(&socket_path)
+        .with_context(|| format!("failed to connect '{}'", socket_path.display()))?;
+    send_message(
+        &mut stream,
+        &ClientMessage::Subscribe {
+            kinds: kinds.to_vec(),
+            follow,
+        },
+    )?;
+    let mut reader = BufReader::new(stream);
+    loop {
+        let message = match read_server_message(&mut reader) {
+            Ok(message) => message,
+            Err(error)
+                if error
+                    .to_string()
+                    .contains("native server connection closed") =>
+            {
+                return Ok(());
+            }
+            Err(error) => return Err(error),
+        };
+        match message {
+            ServerMessage::Event(event) => sink(event)?,
+            ServerMessage::Error { message } => bail!(message),
+            other => bail!(
+                "unexpected event stream message from native session '{}': {:?}",
+                namespace,
+                other
+            ),
+        }
+    }
+}
+
+pub fn native_recording_dir(namespace: &str) -> anyhow::Result<PathBuf> {
+    Ok(native_root()?
+        .join(SESSION_DIR_NAME)
+        .join(sanitize_namespace(namespace))
+        .join(RECORDING_DIR_NAME))
+}
+
+pub fn replay_native_recording(
+    namespace: &str,
+    agent: &str,
+    speed: f64,
+    max_idle_seconds: f64,
+) -> anyhow::Result<()> {
+    let segments = latest_recording_segments(&native_recording_dir(namespace)?, agent)
+        .with_context(|| format!("no native recording for '{}:{}'", namespace, agent))?;
+    replay_segments(&segments, speed, max_idle_seconds)
+}
+
+pub fn attach_native(namespace: &str, agent: &str, read_only: bool) -> anyhow::Result<()> {
     let socket_path = socket_path_for(namespace)?;
     let mut stream = UnixStream::connect(&socket_path)
         .with_context(|| format!("failed to connect '{}'", socket_path.display()))?;
     let stdin_fd = io::stdin().as_raw_fd();
-    let bindings = current_attach_bindings();
+    let bindings = AttachBindings {
+        read_only,
+        ..current_attach_bindings()
+    };
     let initial_viewport = current_attach_viewport();
     send_message(
         &mut stream,
@@ -799,6 +1356,8 @@ pub fn attach_native(namespace: &str, agent: &str) -> anyhow::Result<()> {
             agent: agent.to_string(),
             rows: initial_viewport.map(|viewport| viewport.content_rows),
             cols: initial_viewport.map(|viewport| viewport.cols),
+            read_only,
+            tty: current_tty_name(stdin_fd),
         },
     )?;
 
@@ -890,7 +1449,10 @@ pub fn attach_native(namespace: &str, agent: &str) -> anyhow::Result<()> {
                     }
                     ServerMessage::Exited { .. } => break,
                     ServerMessage::Error { message } => bail!(message),
-                    ServerMessage::Ok | ServerMessage::Metadata(..) => {}
+                    ServerMessage::Ok
+                    | ServerMessage::Metadata(..)
+                    | ServerMessage::AgentSpawned { .. }
+                    | ServerMessage::Event(..) => {}
                 }
             }
             Ok(())
@@ -936,7 +1498,7 @@ fn forward_stdin(
 ) -> anyhow::Result<()> {
     let mut buffer = [0u8; 1024];
     let mut pending_escape = Vec::new();
-    let attach_started = std::time::Instant::now();
+    let attach_started = Instant::now();
     let mut resize_retry_offsets_ms = VecDeque::from([75_u64, 200, 500, 1000]);
     let mut poll_fd = libc::pollfd {
         fd: stdin_fd,
@@ -1060,6 +1622,7 @@ fn current_attach_bindings() -> AttachBindings {
             leader_label: "ctrl+g",
             leader_key_label: "g",
             literal_key: b'g',
+            read_only: false,
         };
     }
 
@@ -1068,6 +1631,7 @@ fn current_attach_bindings() -> AttachBindings {
         leader_label: "ctrl+b",
         leader_key_label: "b",
         literal_key: b'b',
+        read_only: false,
     }
 }
 
@@ -1079,23 +1643,57 @@ fn parse_attach_bindings(raw: &str) -> Option<AttachBindings> {
             leader_label: "ctrl+a",
             leader_key_label: "a",
             literal_key: b'a',
+            read_only: false,
         }),
         "ctrl+b" | "ctrl-b" | "^b" => Some(AttachBindings {
             leader_byte: 0x02,
             leader_label: "ctrl+b",
             leader_key_label: "b",
             literal_key: b'b',
+            read_only: false,
         }),
         "ctrl+g" | "ctrl-g" | "^g" => Some(AttachBindings {
             leader_byte: 0x07,
             leader_label: "ctrl+g",
             leader_key_label: "g",
             literal_key: b'g',
+            read_only: false,
         }),
         _ => None,
     }
 }
 
+fn current_tty_name(fd: i32) -> Option<String> {
+    let name = unsafe { libc::ttyname(fd) };
+    if name.is_null() {
+        return None;
+    }
+    let name = unsafe { std::ffi::CStr::from_ptr(name) };
+    Some(name.to_string_lossy().into_owned())
+}
+
+fn peer_pid(stream: &UnixStream) -> Option<u32> {
+    let mut credentials = libc::ucred {
+        pid: 0,
+        uid: 0,
+        gid: 0,
+    };
+    let mut length = std::mem::size_of::<libc::ucred>() as libc::socklen_t;
+    let status = unsafe {
+        libc::getsockopt(
+            stream.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_PEERCRED,
+            (&mut credentials as *mut libc::ucred).cast(),
+            &mut length,
+        )
+    };
+    if status != 0 || credentials.pid <= 0 {
+        return None;
+    }
+    u32::try_from(credentials.pid).ok()
+}
+
 fn current_attach_viewport() -> Option<AttachViewport> {
     let (cols, rows) = terminal_size().ok()?;
     let content_rows = rows.saturating_sub(1).max(1);
@@ -1249,10 +1847,20 @@ fn format_footer_text(
     }
 
     let content = match footer_mode {
+        FooterMode::Normal if bindings.read_only => format!(
+            " native session (read-only) | ns:{} | ag:{} | {} d detach | ctrl+] or F12 fast-detach ",
+            namespace, agent, bindings.leader_label
+        ),
         FooterMode::Normal => format!(
             " native session | ns:{} | ag:{} | {} d detach | {} :cmd | ctrl+c int | ctrl+] or F12 fast-detach ",
             namespace, agent, bindings.leader_label, bindings.leader_label
         ),
+        FooterMode::Leader if bindings.read_only => {
+            format!(
+                " leader {} (read-only) | d detach | : command ",
+                bindings.leader_label
+            )
+        }
         FooterMode::Leader => {
             format!(
                 " leader {} | d detach | : command | c interrupt | {} send literal {} ",
@@ -1674,7 +2282,9 @@ fn run_local_leader(
             }
             b'c' | b'C' => {
                 debug!("native attach leader interrupt selected");
-                let _ = interrupt_native(namespace, agent);
+                if !bindings.read_only {
+                    let _ = interrupt_native(namespace, agent);
+                }
                 break AttachInputAction::Continue;
             }
             byte if byte.eq_ignore_ascii_case(&bindings.literal_key) => {
@@ -1751,7 +2361,7 @@ fn run_local_command(
 
         match byte {
             b'\r' | b'\n' => {
-                if execute_attach_command(namespace, agent, &command)? {
+                if execute_attach_command(namespace, agent, bindings, &command)? {
                     return Ok(AttachInputAction::Detach);
                 }
                 return Ok(AttachInputAction::Continue);
@@ -1777,12 +2387,17 @@ fn run_local_command(
     }
 }
 
-fn execute_attach_command(namespace: &str, agent: &str, command: &str) -> anyhow::Result<bool> {
+fn execute_attach_command(
+    namespace: &str,
+    agent: &str,
+    bindings: AttachBindings,
+    command: &str,
+) -> anyhow::Result<bool> {
     let normalized = command.trim().to_ascii_lowercase();
     debug!(command = %normalized, "native attach command executed");
     match normalized.as_str() {
         "" | "detach" | "d" => Ok(true),
-        "interrupt" | "int" | "ctrl-c" => {
+        "interrupt" | "int" | "ctrl-c" if !bindings.read_only => {
             let _ = interrupt_native(namespace, agent);
             Ok(false)
         }
@@ -1864,12 +2479,75 @@ fn handle_client(stream: UnixStream, session: Arc<NativeSession>) -> anyhow::Res
             session.agent(&agent)?.interrupt()?;
             send_server_message(&mut writer, &ServerMessage::Ok)?;
         }
+        ClientMessage::SpawnAgent {
+            agent,
+            working_directory,
+            shell_command,
+            restart_policy,
+        } => {
+            let response = match session.spawn_agent(
+                agent,
+                working_directory,
+                shell_command,
+                restart_policy,
+            ) {
+                Ok(agent) => {
+                    let pid = session.agent(&agent)?.pid.load(Ordering::SeqCst);
+                    let _ = session.write_metadata();
+                    ServerMessage::AgentSpawned { agent, pid }
+                }
+                Err(error) => ServerMessage::Error {
+                    message: format!("{error:#}"),
+                },
+            };
+            send_server_message(&mut writer, &response)?;
+        }
+        ClientMessage::KillAgent { agent } => {
+            let response = match session.remove_agent(&agent) {
+                Ok(()) => {
+                    let _ = session.write_metadata();
+                    ServerMessage::Ok
+                }
+                Err(error) => ServerMessage::Error {
+                    message: format!("{error:#}"),
+                },
+            };
+            send_server_message(&mut writer, &response)?;
+        }
+        ClientMessage::Subscribe { kinds, follow } => {
+            let subscription = session.events.subscribe(kinds);
+            pump_subscription(subscription, follow, |event| {
+                send_server_message(&mut writer, &ServerMessage::Event(event))
+            })?;
+        }
         ClientMessage::KillSession => {
             send_server_message(&mut writer, &ServerMessage::Ok)?;
             session.shutdown();
         }
-        ClientMessage::Attach { agent, rows, cols } => {
-            handle_attach(reader, writer, session, agent, rows, cols)?;
+        ClientMessage::Attach {
+            agent,
+            rows,
+            cols,
+            read_only,
+            tty,
+        } => {
+            let client = NativeAttachedClient {
+                agent: agent.clone(),
+                mode: if read_only { "read-only" } else { "read-write" }.to_string(),
+                attached_at_epoch_ms: now_epoch_ms()?,
+                pid: peer_pid(&writer),
+                tty,
+            };
+            let _client = session.register_client(client);
+            handle_attach(
+                reader,
+                writer,
+                Arc::clone(&session),
+                agent,
+                rows,
+                cols,
+                read_only,
+            )?;
         }
         ClientMessage::Input { .. } | ClientMessage::Resize { .. } => {
             bail!(
@@ -1888,10 +2566,11 @@ fn handle_attach(
     agent_name: String,
     rows: Option<u16>,
     cols: Option<u16>,
+    read_only: bool,
 ) -> anyhow::Result<()> {
     let agent = session.agent(&agent_name)?;
     let (backlog, rx) = agent.subscribe();
-    let initial_resize = rows.zip(cols);
+    let initial_resize = if read_only { None } else { rows.zip(cols) };
 
     if let Some((rows, cols)) = initial_resize {
         let _ = agent.resize(rows, cols);
@@ -1925,6 +2604,10 @@ fn handle_attach(
                 Err(_) => break,
             };
             match message {
+                ClientMessage::Input { .. }
+                | ClientMessage::Resize { .. }
+                | ClientMessage::SendText { .. }
+                    if read_only => {}
                 ClientMessage::Input { agent, data_base64 } if agent == input_agent_name => {
                     let bytes = BASE64
                         .decode(data_base64)
@@ -1955,7 +2638,7 @@ fn handle_attach(
     if let Some((rows, cols)) = initial_resize {
         let resize_agent = Arc::clone(&agent);
         thread::spawn(move || {
-            let attach_started = std::time::Instant::now();
+            let attach_started = Instant::now();
             for target_ms in [75_u64, 200, 500, 1000] {
                 let target = Duration::from_millis(target_ms);
                 if let Some(remaining) = target.checked_sub(attach_started.elapsed()) {
@@ -2159,6 +2842,7 @@ fn wait_for_native_shutdown(namespace: &str) -> anyhow::Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::unique_temp_dir;
 
     fn test_attach_context() -> (
         UnixStream,
@@ -2304,6 +2988,129 @@ mod tests {
         }
         assert_eq!(action, AttachInputAction::Detach);
     }
+
+    #[test]
+    fn read_only_footer_hides_input_controls() {
+        let bindings = AttachBindings {
+            read_only: true,
+            ..parse_attach_bindings("ctrl-b").expect("ctrl-b bindings")
+        };
+        let footer = format_footer_text(&FooterMode::Normal, bindings, "ns", "agent0", 200);
+        assert!(footer.contains("read-only"));
+        assert!(!footer.contains(":cmd"));
+        assert!(!footer.contains("int"));
+    }
+
+    #[test]
+    fn attached_client_pid_comes_from_peer_credentials() {
+        let (left, _right) = UnixStream::pair().expect("unix stream pair");
+        assert_eq!(peer_pid(&left), Some(std::process::id()));
+    }
+
+    #[test]
+    fn restart_policy_backs_off_and_respects_limits() {
+        let on_failure = AgentRestartPolicy {
+            policy: RestartPolicyKind::OnFailure,
+            max_retries: 3,
+            backoff_ms: 500,
+        };
+        assert_eq!(on_failure.restart_delay(Some(0), 1), None);
+        assert_eq!(
+            on_failure.restart_delay(Some(1), 1),
+            Some(Duration::from_millis(500))
+        );
+        assert_eq!(
+            on_failure.restart_delay(None, 3),
+            Some(Duration::from_millis(2_000))
+        );
+        assert_eq!(on_failure.restart_delay(Some(1), 4), None);
+
+        let always = AgentRestartPolicy {
+            policy: RestartPolicyKind::Always,
+            max_retries: 40,
+            backoff_ms: 1_000,
+        };
+        assert_eq!(
+            always.restart_delay(Some(0), 1),
+            Some(Duration::from_millis(1_000))
+        );
+        assert_eq!(
+            always.restart_delay(Some(0), 30),
+            Some(Duration::from_millis(RESTART_BACKOFF_CAP_MS))
+        );
+        assert_eq!(
+            AgentRestartPolicy::default().restart_delay(Some(1), 1),
+            None
+        );
+    }
+
+    #[test]
+    fn agent_add_and_remove_validate_names() {
+        let session_dir = unique_temp_dir("jarvisctl-native-agents");
+        fs::create_dir_all(&session_dir).unwrap();
+        let session = NativeSession {
+            namespace: "agents-test".to_string(),
+            created_at_epoch_ms: 0,
+            working_directory: None,
+            shell_command: "sleep 30".to_string(),
+            context: None,
+            session_dir: session_dir.clone(),
+            restart_policy: AgentRestartPolicy::default(),
+            triggers: Vec::new(),
+            agents: Mutex::new(BTreeMap::new()),
+            clients: Mutex::new(BTreeMap::new()),
+            next_client_id: AtomicU64::new(0),
+            events: RuntimeEventBus::default(),
+            shutdown_requested: AtomicBool::new(false),
+        };
+
+        assert_eq!(
+            session.spawn_agent(None, None, None, None).unwrap(),
+            "agent0"
+        );
+        let duplicate = session
+            .spawn_agent(Some("agent0".to_string()), None, None, None)
+            .unwrap_err();
+        assert!(
+            duplicate
+                .to_string()
+                .contains("already has a running agent 'agent0'"),
+            "{duplicate}"
+        );
+        assert!(
+            session
+                .spawn_agent(Some("bad name".to_string()), None, None, None)
+                .is_err()
+        );
+        let unknown = session.remove_agent("missing").unwrap_err();
+        assert!(
+            unknown.to_string().contains("has no agent 'missing'"),
+            "{unknown}"
+        );
+        assert!(
+            session
+                .remove_agent("agent0")
+                .unwrap_err()
+                .to_string()
+                .contains("last running agent")
+        );
+        assert_eq!(
+            session
+                .spawn_agent(Some("tests".to_string()), None, None, None)
+                .unwrap(),
+            "tests"
+        );
+        session.remove_agent("tests").unwrap();
+        assert_eq!(
+            session.agents.lock().unwrap().keys().collect::<Vec<_>>(),
+            vec!["agent0"]
+        );
+
+        for agent in session.agents.lock().unwrap().values() {
+            let _ = agent.kill();
+        }
+        let _ = fs::remove_dir_all(session_dir);
+    }
 }
 
 fn cleanup_stale_session(namespace: &str) -> anyhow::Result<()> {
@@ -2331,9 +3138,55 @@ fn spawn_managed_agent(
     working_dir: Option<&str>,
     shell_command: &str,
     initial_size: Option<(u16, u16)>,
+    restart_policy: AgentRestartPolicy,
+    recorder: Option<SessionRecorder>,
+    triggers: Option<OutputTriggerSet>,
 ) -> anyhow::Result<Arc<ManagedAgent>> {
+    let size = initial_size.unwrap_or((24, 80));
+    let pty = open_agent_pty(working_dir, shell_command, size)?;
+
+    let agent = Arc::new(ManagedAgent {
+        name: agent_name.to_string(),
+        working_dir: working_dir.map(ToOwned::to_owned),
+        shell_command: shell_command.to_string(),
+        restart_policy,
+        pid: AtomicU32::new(pty.pid),
+        size: Mutex::new(size),
+        running: AtomicBool::new(true),
+        stopping: AtomicBool::new(false),
+        restarts: AtomicU32::new(0),
+        exit_code: Mutex::new(None),
+        last_exit_code: Mutex::new(None),
+        master: Mutex::new(pty.master),
+        writer: Mutex::new(pty.writer),
+        child: Mutex::new(pty.child),
+        log: Mutex::new(VecDeque::new()),
+        recorder: Mutex::new(recorder),
+        triggers: Mutex::new(triggers),
+        idle: AtomicBool::new(false),
+        subscribers: Mutex::new(Vec::new()),
+    });
+
+    let output_agent = Arc::clone(&agent);
+    thread::spawn(move || supervise_managed_agent(output_agent, pty.reader));
+
+    Ok(agent)
+}
+
+struct AgentPty {
+    pid: u32,
+    master: Box<dyn MasterPty + Send>,
+    writer: Box<dyn Write + Send>,
+    reader: Box<dyn Read + Send>,
+    child: Box<dyn portable_pty::Child + Send>,
+}
+
+fn open_agent_pty(
+    working_dir: Option<&str>,
+    shell_command: &str,
+    (rows, cols): (u16, u16),
+) -> anyhow::Result<AgentPty> {
     let pty_system = native_pty_system();
-    let (rows, cols) = initial_size.unwrap_or((24, 80));
     let pair = pty_system
         .openpty(PtySize {
             rows,
@@ -2371,44 +3224,147 @@ fn spawn_managed_agent(
         .take_writer()
         .context("failed to take native PTY writer")?;
 
-    let agent = Arc::new(ManagedAgent {
-        name: agent_name.to_string(),
+    Ok(AgentPty {
         pid,
-        running: AtomicBool::new(true),
-        exit_code: Mutex::new(None),
-        master: Mutex::new(pair.master),
-        writer: Mutex::new(writer),
-        child: Mutex::new(child),
-        log: Mutex::new(VecDeque::new()),
-        subscribers: Mutex::new(Vec::new()),
-    });
+        master: pair.master,
+        writer,
+        reader,
+        child,
+    })
+}
 
-    let output_agent = Arc::clone(&agent);
-    thread::spawn(move || {
-        let mut reader = BufReader::new(reader);
-        let mut buffer = [0u8; 8192];
-        loop {
-            let read = match reader.read(&mut buffer) {
-                Ok(read) => read,
-                Err(_) => break,
-            };
-            if read == 0 {
-                break;
-            }
-            output_agent.append_output(&buffer[..read]);
-        }
-        let exit_code = output_agent
+fn supervise_managed_agent(agent: Arc<ManagedAgent>, mut reader: Box<dyn Read + Send>) {
+    let mut failures = 0_u32;
+    loop {
+        let started = Instant::now();
+        pump_agent_output(&agent, reader);
+        let exit_code = agent
             .child
             .lock()
             .unwrap()
             .wait()
             .ok()
             .map(|status| status.exit_code() as i32);
-        *output_agent.exit_code.lock().unwrap() = exit_code;
-        output_agent.running.store(false, Ordering::SeqCst);
-    });
+        *agent.exit_code.lock().unwrap() = exit_code;
+        *agent.last_exit_code.lock().unwrap() = exit_code;
 
-    Ok(agent)
+        if started.elapsed() >= RESTART_STABLE_RUN {
+            failures = 0;
+        }
+        failures += 1;
+        let delay = if agent.stopping.load(Ordering::SeqCst) {
+            None
+        } else {
+            agent.restart_policy.restart_delay(exit_code, failures)
+        };
+        let Some(delay) = delay else {
+            break;
+        };
+
+        agent.append_output(
+            format!(
+                "\r\n[jarvisctl] {} exited with {}; restarting in {}s (attempt {}/{})\r\n",
+                agent.name,
+                exit_code.map_or_else(
+                    || "unknown status".to_string(),
+                    |code| format!("code {code}")
+                ),
+                delay.as_secs_f64(),
+                failures,
+                agent.restart_policy.max_retries
+            )
+            .as_bytes(),
+        );
+        if !sleep_unless_stopping(&agent, delay) {
+            break;
+        }
+
+        let size = *agent.size.lock().unwrap();
+        let pty = match open_agent_pty(agent.working_dir.as_deref(), &agent.shell_command, size) {
+            Ok(pty) => pty,
+            Err(error) => {
+                debug!(agent = %agent.name, %error, "native agent restart failed");
+                agent.append_output(
+                    format!("\r\n[jarvisctl] restart failed: {error:#}\r\n").as_bytes(),
+                );
+                break;
+            }
+        };
+        *agent.master.lock().unwrap() = pty.master;
+        *agent.writer.lock().unwrap() = pty.writer;
+        *agent.child.lock().unwrap() = pty.child;
+        agent.pid.store(pty.pid, Ordering::SeqCst);
+        *agent.exit_code.lock().unwrap() = None;
+        agent.restarts.fetch_add(1, Ordering::SeqCst);
+        reader = pty.reader;
+
+        if agent.stopping.load(Ordering::SeqCst) {
+            let _ = agent.child.lock().unwrap().kill();
+        }
+    }
+    agent.running.store(false, Ordering::SeqCst);
+}
+
+fn deliver_trigger_hit(hit: &TriggerHit) -> anyhow::Result<()> {
+    let summary = format!(
+        "{}/{} matched '{}': {}",
+        hit.namespace, hit.agent, hit.pattern, hit.matched
+    );
+    match hit.action {
+        OutputTriggerAction::Notify => {
+            let status = Command::new("notify-send")
+                .arg("--app-name=jarvisctl")
+                .arg(format!("jarvisctl trigger in {}", hit.namespace))
+                .arg(&summary)
+                .status()
+                .context("failed to run notify-send")?;
+            ensure!(status.success(), "notify-send exited with {status}");
+        }
+        OutputTriggerAction::OperatorRequest => {
+            create_operator_request(OperatorRequestCreateOptions {
+                title: format!("Output trigger in {}/{}", hit.namespace, hit.agent),
+                kind: "output-trigger".to_string(),
+                severity: "medium".to_string(),
+                reason: summary,
+                risk: None,
+                requested_by: Some("jarvisctl-trigger".to_string()),
+                namespace: Some(hit.namespace.clone()),
+                request_id: None,
+                method: None,
+                command: None,
+                params: None,
+                ttl_seconds: None,
+            })?;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn pump_agent_output(agent: &ManagedAgent, reader: Box<dyn Read + Send>) {
+    let mut reader = BufReader::new(reader);
+    let mut buffer = [0u8; 8192];
+    loop {
+        let read = match reader.read(&mut buffer) {
+            Ok(read) => read,
+            Err(_) => break,
+        };
+        if read == 0 {
+            break;
+        }
+        agent.append_output(&buffer[..read]);
+    }
+}
+
+fn sleep_unless_stopping(agent: &ManagedAgent, delay: Duration) -> bool {
+    let deadline = Instant::now() + delay;
+    while Instant::now() < deadline {
+        if agent.stopping.load(Ordering::SeqCst) {
+            return false;
+        }
+        thread::sleep(Duration::from_millis(50).min(deadline - Instant::now()));
+    }
+    !agent.stopping.load(Ordering::SeqCst)
 }
 
 fn native_root() -> anyhow::Result<PathBuf> {
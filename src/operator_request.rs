@@ -90,7 +90,15 @@ pub fn create_operator_request(
 ) -> anyhow::Result<OperatorRequestRecord> {
     let now = now_epoch_ms();
     let ttl = options.ttl_seconds.unwrap_or(DEFAULT_TTL_SECONDS).max(60);
-    let id = format!("{}-{}", slugify(&options.title), now);
+    // Server requests share a title per namespace and method, so two arriving in the same
+    // millisecond would otherwise overwrite each other's record.
+    let base_id = format!("{}-{}", slugify(&options.title), now);
+    let mut id = base_id.clone();
+    let mut suffix = 2;
+    while operator_request_path(&id)?.exists() {
+        id = format!("{}-{}", base_id, suffix);
+        suffix += 1;
+    }
     let record = OperatorRequestRecord {
         id,
         source_node: None,
@@ -193,6 +201,7 @@ pub fn resolve_operator_request(
     id: &str,
     options: OperatorRequestResolveOptions,
 ) -> anyhow::Result<OperatorRequestRecord> {
+    let _lock = lock_operator_requests()?;
     let mut record = show_operator_request(id)?;
     if record.status != "pending" {
         bail!("operator request '{}' is already {}", id, record.status);
@@ -209,6 +218,28 @@ pub fn resolve_operator_request(
     Ok(record)
 }
 
+/// Marks an approved request as used so a one-shot approval cannot gate a second action.
+pub fn consume_operator_request(id: &str) -> anyhow::Result<OperatorRequestRecord> {
+    // Without the lock two concurrent consumers could both read "approved".
+    let _lock = lock_operator_requests()?;
+    let mut record = show_operator_request(id)?;
+    if record.status != "approved" {
+        bail!(
+            "operator request '{}' is {}, not approved",
+            id,
+            record.status
+        );
+    }
+    let now = now_epoch_ms();
+    if now > record.expires_at_epoch_ms {
+        bail!("operator request '{}' approval has expired", id);
+    }
+    record.status = "consumed".to_string();
+    record.updated_at_epoch_ms = now;
+    write_operator_request(&record)?;
+    Ok(record)
+}
+
 pub fn expire_operator_request(id: &str, reason: &str) -> anyhow::Result<OperatorRequestRecord> {
     resolve_operator_request(
         id,
@@ -361,6 +392,33 @@ fn write_operator_request(record: &OperatorRequestRecord) -> anyhow::Result<()>
         .with_context(|| format!("failed to move '{}' to '{}'", tmp.display(), path.display()))
 }
 
+/// Serializes status transitions across processes; the lock is released when the handle drops.
+fn lock_operator_requests() -> anyhow::Result<fs::File> {
+    let dir = operator_requests_dir()?;
+    fs::create_dir_all(&dir).with_context(|| format!("failed to create '{}'", dir.display()))?;
+    let path = dir.join(".lock");
+    let file = fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&path)
+        .with_context(|| format!("failed to open '{}'", path.display()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::io::AsRawFd;
+        loop {
+            if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX) } == 0 {
+                break;
+            }
+            let error = std::io::Error::last_os_error();
+            if error.kind() != std::io::ErrorKind::Interrupted {
+                return Err(error).with_context(|| format!("failed to lock '{}'", path.display()));
+            }
+        }
+    }
+    Ok(file)
+}
+
 fn parse_operator_request_record(raw: &str, path: &Path) -> anyhow::Result<OperatorRequestRecord> {
     match serde_json::from_str(raw) {
         Ok(record) => Ok(record),
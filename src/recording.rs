@@ -0,0 +1,287 @@
+use anyhow::{Context, bail, ensure};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+const RECORDING_EXTENSION: &str = "cast";
+const RECORDING_SEGMENT_LIMIT_BYTES: u64 = 32 * 1024 * 1024;
+const RECORDING_MAX_SEGMENTS: usize = 8;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct AsciicastHeader {
+    version: u8,
+    width: u16,
+    height: u16,
+    timestamp: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+}
+
+/// Writes one agent's PTY stream as rotating asciicast v2 segments.
+pub struct SessionRecorder {
+    dir: PathBuf,
+    stem: String,
+    title: String,
+    width: u16,
+    height: u16,
+    segment: usize,
+    segment_started: Instant,
+    file: File,
+    written: u64,
+    pending_utf8: Vec<u8>,
+}
+
+impl SessionRecorder {
+    pub fn create(
+        dir: &Path,
+        agent: &str,
+        run_epoch_ms: u128,
+        title: &str,
+        size: (u16, u16),
+    ) -> anyhow::Result<Self> {
+        fs::create_dir_all(dir).with_context(|| format!("failed to create '{}'", dir.display()))?;
+        let stem = format!("{}-{}", agent, run_epoch_ms);
+        let (rows, cols) = size;
+        let (file, written) = open_segment(dir, &stem, 0, title, cols, rows)?;
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            stem,
+            title: title.to_string(),
+            width: cols,
+            height: rows,
+            segment: 0,
+            segment_started: Instant::now(),
+            file,
+            written,
+            pending_utf8: Vec::new(),
+        })
+    }
+
+    pub fn record_output(&mut self, chunk: &[u8]) -> anyhow::Result<()> {
+        self.pending_utf8.extend_from_slice(chunk);
+        let text = drain_complete_utf8(&mut self.pending_utf8);
+        if text.is_empty() {
+            return Ok(());
+        }
+        self.write_event("o", &text)
+    }
+
+    pub fn record_resize(&mut self, rows: u16, cols: u16) -> anyhow::Result<()> {
+        if (self.height, self.width) == (rows, cols) {
+            return Ok(());
+        }
+        self.width = cols;
+        self.height = rows;
+        self.write_event("r", &format!("{}x{}", cols, rows))
+    }
+
+    fn write_event(&mut self, code: &str, data: &str) -> anyhow::Result<()> {
+        let elapsed = self.segment_started.elapsed().as_secs_f64();
+        let mut line = serde_json::to_string(&(elapsed, code, data))
+            .context("failed to encode asciicast event")?;
+        line.push('\n');
+        self.file
+            .write_all(line.as_bytes())
+            .context("failed to write asciicast event")?;
+        self.written += line.len() as u64;
+        if self.written >= RECORDING_SEGMENT_LIMIT_BYTES {
+            self.rotate()?;
+        }
+        Ok(())
+    }
+
+    fn rotate(&mut self) -> anyhow::Result<()> {
+        let next_segment = self.segment + 1;
+        let (file, written) = open_segment(
+            &self.dir,
+            &self.stem,
+            next_segment,
+            &self.title,
+            self.width,
+            self.height,
+        )?;
+        self.file = file;
+        self.written = written;
+        self.segment = next_segment;
+        self.segment_started = Instant::now();
+        if let Some(expired) = next_segment.checked_sub(RECORDING_MAX_SEGMENTS) {
+            let _ = fs::remove_file(segment_path(&self.dir, &self.stem, expired));
+        }
+        Ok(())
+    }
+}
+
+fn open_segment(
+    dir: &Path,
+    stem: &str,
+    segment: usize,
+    title: &str,
+    width: u16,
+    height: u16,
+) -> anyhow::Result<(File, u64)> {
+    let path = segment_path(dir, stem, segment);
+    let mut file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(true)
+        .open(&path)
+        .with_context(|| format!("failed to open recording '{}'", path.display()))?;
+    let header = AsciicastHeader {
+        version: 2,
+        width,
+        height,
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default(),
+        title: Some(title.to_string()),
+    };
+    let mut line = serde_json::to_string(&header).context("failed to encode asciicast header")?;
+    line.push('\n');
+    file.write_all(line.as_bytes())
+        .with_context(|| format!("failed to write recording '{}'", path.display()))?;
+    Ok((file, line.len() as u64))
+}
+
+fn segment_path(dir: &Path, stem: &str, segment: usize) -> PathBuf {
+    dir.join(format!("{}.{:04}.{}", stem, segment, RECORDING_EXTENSION))
+}
+
+fn drain_complete_utf8(pending: &mut Vec<u8>) -> String {
+    let complete = match std::str::from_utf8(pending) {
+        Ok(_) => pending.len(),
+        Err(error) if error.error_len().is_none() => error.valid_up_to(),
+        Err(_) => pending.len(),
+    };
+    let text = String::from_utf8_lossy(&pending[..complete]).into_owned();
+    pending.drain(..complete);
+    text
+}
+
+/// Returns the segments of the most recent recording for `agent`, oldest first.
+pub fn latest_recording_segments(dir: &Path, agent: &str) -> anyhow::Result<Vec<PathBuf>> {
+    ensure!(
+        dir.exists(),
+        "no recordings found under '{}'",
+        dir.display()
+    );
+    let prefix = format!("{}-", agent);
+    let mut segments = Vec::new();
+    for entry in fs::read_dir(dir).with_context(|| format!("failed to read '{}'", dir.display()))? {
+        let path = entry?.path();
+        if path.extension().and_then(|value| value.to_str()) != Some(RECORDING_EXTENSION) {
+            continue;
+        }
+        let Some(stem) = path.file_stem().and_then(|value| value.to_str()) else {
+            continue;
+        };
+        let Some(rest) = stem.strip_prefix(&prefix) else {
+            continue;
+        };
+        let Some((run, segment)) = rest.split_once('.') else {
+            continue;
+        };
+        let (Ok(run), Ok(segment)) = (run.parse::<u128>(), segment.parse::<usize>()) else {
+            continue;
+        };
+        segments.push((run, segment, path));
+    }
+    let Some(latest_run) = segments.iter().map(|(run, _, _)| *run).max() else {
+        bail!(
+            "no recordings for agent '{}' under '{}'",
+            agent,
+            dir.display()
+        );
+    };
+    segments.retain(|(run, _, _)| *run == latest_run);
+    segments.sort_by_key(|(_, segment, _)| *segment);
+    Ok(segments.into_iter().map(|(_, _, path)| path).collect())
+}
+
+/// Plays asciicast segments to stdout, compressing pauses longer than `max_idle_seconds`.
+pub fn replay_segments(
+    segments: &[PathBuf],
+    speed: f64,
+    max_idle_seconds: f64,
+) -> anyhow::Result<()> {
+    ensure!(speed > 0.0, "--speed must be greater than zero");
+    let mut stdout = io::stdout().lock();
+    for path in segments {
+        let file =
+            File::open(path).with_context(|| format!("failed to open '{}'", path.display()))?;
+        let mut lines = BufReader::new(file).lines();
+        let Some(header) = lines.next() else {
+            continue;
+        };
+        let header = header.with_context(|| format!("failed to read '{}'", path.display()))?;
+        serde_json::from_str::<AsciicastHeader>(&header)
+            .with_context(|| format!("'{}' is not an asciicast v2 recording", path.display()))?;
+
+        let mut previous = 0.0_f64;
+        for line in lines {
+            let line = line.with_context(|| format!("failed to read '{}'", path.display()))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let Ok((time, code, data)) = serde_json::from_str::<(f64, String, String)>(&line)
+            else {
+                continue;
+            };
+            let delay = (time - previous).clamp(0.0, max_idle_seconds.max(0.0)) / speed;
+            previous = time;
+            if delay > 0.0 {
+                thread::sleep(Duration::from_secs_f64(delay));
+            }
+            if code == "o" {
+                stdout
+                    .write_all(data.as_bytes())
+                    .context("failed to write replay output")?;
+                stdout.flush().context("failed to flush replay output")?;
+            }
+        }
+    }
+    stdout
+        .write_all(b"\x1b[0m\r\n")
+        .context("failed to reset terminal after replay")?;
+    stdout.flush().context("failed to flush replay output")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recorder_writes_asciicast_events_and_keeps_split_utf8_together() {
+        let dir = crate::test_support::unique_temp_dir("jarvisctl-recording-test");
+        let mut recorder = SessionRecorder::create(&dir, "agent0", 42, "ns/agent0", (24, 80))
+            .expect("create recorder");
+        let check = "✓".as_bytes();
+        recorder.record_output(b"build ").unwrap();
+        recorder.record_output(&check[..1]).unwrap();
+        recorder.record_output(&check[1..]).unwrap();
+        recorder.record_resize(40, 120).unwrap();
+
+        let segments = latest_recording_segments(&dir, "agent0").expect("segments");
+        assert_eq!(segments.len(), 1);
+        let raw = fs::read_to_string(&segments[0]).unwrap();
+        let mut lines = raw.lines();
+        let header: AsciicastHeader = serde_json::from_str(lines.next().unwrap()).unwrap();
+        assert_eq!((header.version, header.width, header.height), (2, 80, 24));
+        let events = lines
+            .map(|line| serde_json::from_str::<(f64, String, String)>(line).unwrap())
+            .map(|(_, code, data)| (code, data))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            events,
+            vec![
+                ("o".to_string(), "build ".to_string()),
+                ("o".to_string(), "✓".to_string()),
+                ("r".to_string(), "120x40".to_string()),
+            ]
+        );
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
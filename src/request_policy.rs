@@ -0,0 +1,516 @@
+use anyhow::Context;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+
+const AUDIT_LOG_FILE_NAME: &str = "request-policy-audit.jsonl";
+const NETWORK_COMMANDS: &[&str] = &[
+    "curl", "wget", "ssh", "scp", "sftp", "rsync", "nc", "ncat", "telnet", "ftp",
+];
+const NETWORK_GIT_SUBCOMMANDS: &[&str] = &["push", "pull", "fetch", "clone", "ls-remote"];
+const SHELL_CONTROL_TOKENS: &[&str] = &["&&", "||", ";", "|", "&", "`", "$(", ">", "<", "\n"];
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RequestPolicyAction {
+    Approve,
+    Deny,
+    Escalate,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum RequestPolicyKind {
+    #[default]
+    Any,
+    Command,
+    FileChange,
+}
+
+/// One rule of a declarative approval policy; every condition that is set must hold.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequestPolicyRule {
+    pub action: RequestPolicyAction,
+    #[serde(default)]
+    pub kind: RequestPolicyKind,
+    /// Command prefixes matched word by word, e.g. `cargo test` or `git status`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub commands: Vec<String>,
+    /// Regex over the command; approve rules never match chained or redirected commands.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network: Option<bool>,
+    #[serde(default, alias = "inRepo", skip_serializing_if = "Option::is_none")]
+    pub in_repo: Option<bool>,
+    #[serde(
+        default,
+        alias = "outsideWritableRoots",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub outside_writable_roots: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// What a server request asks for, extracted from its method and params.
+#[derive(Debug, Clone, Default)]
+pub struct ServerRequestFacts {
+    pub kind: Option<RequestPolicyKind>,
+    pub legacy: bool,
+    pub command: Option<String>,
+    pub cwd: Option<PathBuf>,
+    pub paths: Vec<PathBuf>,
+    pub network: bool,
+}
+
+pub struct RequestPolicyScope<'a> {
+    pub repo: Option<&'a Path>,
+    pub writable_roots: &'a [PathBuf],
+}
+
+#[derive(Debug, Clone)]
+pub struct RequestPolicyDecision {
+    pub action: RequestPolicyAction,
+    pub source: String,
+    pub rule: usize,
+    pub summary: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequestPolicyAuditEntry {
+    pub timestamp_epoch_ms: u128,
+    pub namespace: String,
+    pub request_id: String,
+    pub method: String,
+    pub action: RequestPolicyAction,
+    pub source: String,
+    pub rule: usize,
+    pub summary: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub paths: Vec<String>,
+}
+
+pub fn server_request_facts(method: &str, params: &Value) -> ServerRequestFacts {
+    let (kind, legacy) = match method {
+        "item/commandExecution/requestApproval" => (Some(RequestPolicyKind::Command), false),
+        "item/fileChange/requestApproval" => (Some(RequestPolicyKind::FileChange), false),
+        "execCommandApproval" => (Some(RequestPolicyKind::Command), true),
+        "applyPatchApproval" => (Some(RequestPolicyKind::FileChange), true),
+        _ => (None, false),
+    };
+    let command = match params.get("command") {
+        Some(Value::String(command)) => Some(unwrap_shell_wrapper(command)),
+        Some(Value::Array(parts)) => {
+            let parts = parts
+                .iter()
+                .filter_map(Value::as_str)
+                .map(ToOwned::to_owned)
+                .collect::<Vec<_>>();
+            Some(unwrap_shell_wrapper(&shell_words::join(parts)))
+        }
+        _ => None,
+    };
+    let cwd = params.get("cwd").and_then(Value::as_str).map(PathBuf::from);
+    let mut paths = Vec::new();
+    if let Some(root) = params.get("grantRoot").and_then(Value::as_str) {
+        paths.push(PathBuf::from(root));
+    }
+    if let Some(changes) = params.get("fileChanges").and_then(Value::as_object) {
+        paths.extend(changes.keys().map(PathBuf::from));
+    }
+    if let Some(changes) = params.get("changes").and_then(Value::as_array) {
+        paths.extend(
+            changes
+                .iter()
+                .filter_map(|change| change.get("path").and_then(Value::as_str))
+                .map(PathBuf::from),
+        );
+    }
+    let network = params
+        .get("networkApprovalContext")
+        .is_some_and(|value| !value.is_null())
+        || command.as_deref().is_some_and(command_uses_network);
+    ServerRequestFacts {
+        kind,
+        legacy,
+        command,
+        cwd,
+        paths,
+        network,
+    }
+}
+
+/// Compiles every rule pattern so a typo is reported where the policy is declared instead of
+/// silently never matching.
+pub fn validate_request_policy(rules: &[RequestPolicyRule]) -> anyhow::Result<()> {
+    for (index, rule) in rules.iter().enumerate() {
+        if let Some(pattern) = rule.pattern.as_deref() {
+            Regex::new(pattern).with_context(|| {
+                format!(
+                    "request policy rule {} has an invalid pattern '{}'",
+                    index + 1,
+                    pattern
+                )
+            })?;
+        }
+    }
+    Ok(())
+}
+
+/// Returns the first rule that matches, trying each `(source, rules)` set in order.
+pub fn evaluate_request_policy(
+    rule_sets: &[(String, Vec<RequestPolicyRule>)],
+    facts: &ServerRequestFacts,
+    scope: &RequestPolicyScope<'_>,
+) -> Option<RequestPolicyDecision> {
+    let kind = facts.kind?;
+    for (source, rules) in rule_sets {
+        for (index, rule) in rules.iter().enumerate() {
+            if !rule_matches(rule, kind, facts, scope) {
+                continue;
+            }
+            return Some(RequestPolicyDecision {
+                action: rule.action,
+                source: source.clone(),
+                rule: index,
+                summary: rule.reason.clone().unwrap_or_else(|| describe_rule(rule)),
+            });
+        }
+    }
+    None
+}
+
+fn rule_matches(
+    rule: &RequestPolicyRule,
+    kind: RequestPolicyKind,
+    facts: &ServerRequestFacts,
+    scope: &RequestPolicyScope<'_>,
+) -> bool {
+    if rule.kind != RequestPolicyKind::Any && rule.kind != kind {
+        return false;
+    }
+    if !rule.commands.is_empty() {
+        let Some(command) = facts
+            .command
+            .as_deref()
+            .filter(|command| is_simple(command))
+        else {
+            return false;
+        };
+        if !rule
+            .commands
+            .iter()
+            .any(|prefix| command_has_prefix(command, prefix))
+        {
+            return false;
+        }
+    }
+    if let Some(pattern) = rule.pattern.as_deref() {
+        let matched = facts
+            .command
+            .as_deref()
+            .filter(|command| rule.action != RequestPolicyAction::Approve || is_simple(command))
+            .is_some_and(|command| Regex::new(pattern).is_ok_and(|regex| regex.is_match(command)));
+        if !matched {
+            return false;
+        }
+    }
+    if rule.network.is_some_and(|network| network != facts.network) {
+        return false;
+    }
+    let base = facts.cwd.as_deref().or(scope.repo);
+    let paths = facts
+        .paths
+        .iter()
+        .map(|path| match base {
+            Some(base) if path.is_relative() => base.join(path),
+            _ => path.clone(),
+        })
+        .collect::<Vec<_>>();
+    if let Some(in_repo) = rule.in_repo {
+        let inside = scope.repo.is_some_and(|repo| {
+            facts
+                .cwd
+                .iter()
+                .chain(&paths)
+                .all(|path| within(path, repo))
+                && (facts.cwd.is_some() || !paths.is_empty())
+        });
+        if inside != in_repo {
+            return false;
+        }
+    }
+    if let Some(outside) = rule.outside_writable_roots {
+        let roots = scope
+            .repo
+            .map(Path::to_path_buf)
+            .into_iter()
+            .chain(scope.writable_roots.iter().cloned())
+            .collect::<Vec<_>>();
+        let escapes = paths
+            .iter()
+            .any(|path| !roots.iter().any(|root| within(path, root)));
+        if escapes != outside {
+            return false;
+        }
+    }
+    true
+}
+
+/// Builds the app-server response for an automatic approve or deny.
+pub fn policy_response(
+    action: RequestPolicyAction,
+    facts: &ServerRequestFacts,
+    params: &Value,
+) -> Value {
+    let approve = action == RequestPolicyAction::Approve;
+    if facts.legacy {
+        return json!({ "decision": if approve { "approved" } else { "denied" } });
+    }
+    let wanted = if approve { "accept" } else { "decline" };
+    let available = params
+        .get("availableDecisions")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    let decision = available
+        .iter()
+        .find(|decision| decision.as_str() == Some(wanted))
+        .cloned()
+        .or_else(|| {
+            (!approve)
+                .then(|| {
+                    available
+                        .iter()
+                        .find(|decision| decision.as_str() == Some("cancel"))
+                        .cloned()
+                })
+                .flatten()
+        })
+        .unwrap_or_else(|| Value::String(wanted.to_string()));
+    json!({ "decision": decision })
+}
+
+pub fn append_request_policy_audit(entry: &RequestPolicyAuditEntry) -> anyhow::Result<()> {
+    let path = audit_log_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create '{}'", parent.display()))?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("failed to open policy audit log '{}'", path.display()))?;
+    let line = serde_json::to_string(entry).context("failed to encode policy audit entry")?;
+    writeln!(file, "{}", line)
+        .with_context(|| format!("failed to append policy audit log '{}'", path.display()))
+}
+
+fn describe_rule(rule: &RequestPolicyRule) -> String {
+    let mut parts = Vec::new();
+    if !rule.commands.is_empty() {
+        parts.push(rule.commands.join(", "));
+    }
+    if let Some(pattern) = rule.pattern.as_deref() {
+        parts.push(format!("/{}/", pattern));
+    }
+    if rule.network == Some(true) {
+        parts.push("network".to_string());
+    }
+    if rule.in_repo == Some(true) {
+        parts.push("in repo".to_string());
+    }
+    if rule.outside_writable_roots == Some(true) {
+        parts.push("outside writable roots".to_string());
+    }
+    if parts.is_empty() {
+        parts.push("any request".to_string());
+    }
+    format!(
+        "{} {}",
+        serde_json::to_value(rule.action)
+            .ok()
+            .and_then(|value| value.as_str().map(ToOwned::to_owned))
+            .unwrap_or_default(),
+        parts.join(" ")
+    )
+}
+
+fn unwrap_shell_wrapper(command: &str) -> String {
+    let Ok(parts) = shell_words::split(command) else {
+        return command.trim().to_string();
+    };
+    match parts.as_slice() {
+        [shell, flag, script]
+            if matches!(
+                Path::new(shell).file_name().and_then(|name| name.to_str()),
+                Some("bash" | "sh" | "zsh")
+            ) && matches!(flag.as_str(), "-c" | "-lc") =>
+        {
+            script.trim().to_string()
+        }
+        _ => command.trim().to_string(),
+    }
+}
+
+fn is_simple(command: &str) -> bool {
+    !SHELL_CONTROL_TOKENS
+        .iter()
+        .any(|token| command.contains(token))
+}
+
+fn command_has_prefix(command: &str, prefix: &str) -> bool {
+    let (Ok(words), Ok(prefix)) = (shell_words::split(command), shell_words::split(prefix)) else {
+        return false;
+    };
+    !prefix.is_empty() && words.starts_with(&prefix)
+}
+
+fn command_uses_network(command: &str) -> bool {
+    command
+        .split(['&', '|', ';', '\n', '(', ')', '`'])
+        .filter_map(|segment| shell_words::split(segment.trim()).ok())
+        .any(|words| {
+            let program = words
+                .first()
+                .and_then(|word| Path::new(word).file_name())
+                .and_then(|name| name.to_str())
+                .unwrap_or_default();
+            NETWORK_COMMANDS.contains(&program)
+                || program == "git"
+                    && words
+                        .iter()
+                        .skip(1)
+                        .find(|word| !word.starts_with('-'))
+                        .is_some_and(|word| NETWORK_GIT_SUBCOMMANDS.contains(&word.as_str()))
+        })
+}
+
+fn within(path: &Path, root: &Path) -> bool {
+    normalize(path).starts_with(normalize(root))
+}
+
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            Component::CurDir => {}
+            other => normalized.push(other.as_os_str()),
+        }
+    }
+    normalized
+}
+
+fn audit_log_path() -> anyhow::Result<PathBuf> {
+    Ok(crate::mission::jarvis_codex_dir()?.join(AUDIT_LOG_FILE_NAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn policy_approves_simple_repo_commands_and_denies_network() {
+        let rules: Vec<RequestPolicyRule> = serde_yaml::from_str(
+            r#"
+- action: deny
+  network: true
+- action: escalate
+  kind: file-change
+  outside_writable_roots: true
+- action: approve
+  kind: command
+  commands: ["git status", "cargo test"]
+  in_repo: true
+- action: approve
+  kind: command
+  pattern: "^cargo (build|check)"
+"#,
+        )
+        .unwrap();
+        let rule_sets = vec![("ticket".to_string(), rules)];
+        let roots = vec![PathBuf::from("/tmp/notes")];
+        let scope = RequestPolicyScope {
+            repo: Some(Path::new("/work/repo")),
+            writable_roots: &roots,
+        };
+        let decide = |method: &str, params: Value| {
+            evaluate_request_policy(&rule_sets, &server_request_facts(method, &params), &scope)
+                .map(|decision| decision.action)
+        };
+        let command = "item/commandExecution/requestApproval";
+
+        assert_eq!(
+            decide(
+                command,
+                json!({"command": "bash -lc 'cargo test -p core'", "cwd": "/work/repo/core"})
+            ),
+            Some(RequestPolicyAction::Approve)
+        );
+        assert_eq!(
+            decide(
+                command,
+                json!({"command": "cargo test && rm -rf ~", "cwd": "/work/repo"})
+            ),
+            None
+        );
+        assert_eq!(
+            decide(
+                command,
+                json!({"command": "cargo build --release", "cwd": "/work/repo"})
+            ),
+            Some(RequestPolicyAction::Approve)
+        );
+        assert_eq!(
+            decide(
+                command,
+                json!({"command": "cargo build && rm -rf ~", "cwd": "/work/repo"})
+            ),
+            None
+        );
+        assert_eq!(
+            decide(
+                command,
+                json!({"command": "git status", "cwd": "/work/repo/../other"})
+            ),
+            None
+        );
+        assert_eq!(
+            decide(
+                command,
+                json!({"command": "cargo test; git push origin main", "cwd": "/work/repo"})
+            ),
+            Some(RequestPolicyAction::Deny)
+        );
+        assert_eq!(
+            decide(
+                "item/fileChange/requestApproval",
+                json!({"grantRoot": "/etc"})
+            ),
+            Some(RequestPolicyAction::Escalate)
+        );
+        assert_eq!(decide("item/tool/requestUserInput", json!({})), None);
+
+        let facts = server_request_facts(command, &json!({"command": "curl x"}));
+        assert_eq!(
+            policy_response(
+                RequestPolicyAction::Deny,
+                &facts,
+                &json!({"availableDecisions": ["accept", "cancel"]})
+            ),
+            json!({"decision": "cancel"})
+        );
+    }
+}
@@ -2,12 +2,14 @@ use crate::codex::enrich_native_sessions;
 use crate::codex_app::{
     CodexAppInputMode, attach_codex_app, cleanup_stale_session, codex_app_session_dir_exists,
     codex_app_session_metadata, collect_codex_app_sessions, delete_codex_app_session,
-    interrupt_codex_app, respond_codex_app_server_request, tell_codex_app,
+    interrupt_codex_app, respond_codex_app_server_request, stream_codex_app_events, tell_codex_app,
     tell_codex_app_with_mode,
 };
+use crate::events::{RuntimeEvent, RuntimeEventKind};
 use crate::native::{
-    NativeSessionMetadata, attach_native, collect_native_sessions, delete_native_session,
-    interrupt_native, native_session_metadata, tell_native,
+    AgentRestartPolicy, NativeSessionMetadata, RuntimeContextMetadata, attach_native,
+    collect_native_sessions, delete_native_session, interrupt_native, kill_native_agent,
+    native_session_metadata, spawn_native_agent, stream_native_events, tell_native,
 };
 use anyhow::{Result, anyhow};
 use std::thread;
@@ -36,6 +38,11 @@ pub fn session_metadata_for_namespace(namespace: &str) -> Result<NativeSessionMe
     }
 }
 
+/// Context metadata (turn status, last error) for a runtime, or `None` once it is gone.
+pub fn runtime_context_for_namespace(namespace: &str) -> Result<Option<RuntimeContextMetadata>> {
+    Ok(maybe_session_metadata_for_namespace(namespace)?.and_then(|session| session.context))
+}
+
 pub fn probe_runtime_session_state(namespace: &str) -> Result<RuntimeSessionState> {
     let Some(metadata) = maybe_session_metadata_for_namespace(namespace)? else {
         return Ok(RuntimeSessionState::Missing);
@@ -58,6 +65,7 @@ pub fn probe_runtime_session_state(namespace: &str) -> Result<RuntimeSessionStat
     system.refresh_all();
     let has_active_codex = metadata.agents.iter().any(|agent| {
         agent.running
+            && !agent.idle
             && process_tree_has_codex(
                 &system,
                 usize::try_from(agent.pid)
@@ -77,11 +85,11 @@ pub fn probe_runtime_session_state(namespace: &str) -> Result<RuntimeSessionStat
     Ok(RuntimeSessionState::Idle)
 }
 
-pub fn attach_runtime_session(namespace: &str, agent: &str) -> Result<()> {
+pub fn attach_runtime_session(namespace: &str, agent: &str, read_only: bool) -> Result<()> {
     let session = session_metadata_for_namespace(namespace)?;
     match session.backend.as_str() {
-        "codex-app" => attach_codex_app(namespace),
-        _ => attach_native(namespace, agent),
+        "codex-app" => attach_codex_app(namespace, read_only),
+        _ => attach_native(namespace, agent, read_only),
     }
 }
 
@@ -126,6 +134,53 @@ pub fn interrupt_runtime_session(namespace: &str, agent: &str) -> Result<()> {
     }
 }
 
+pub fn add_runtime_agent(
+    namespace: &str,
+    agent: Option<&str>,
+    working_dir: Option<&str>,
+    shell_command: &str,
+    restart_policy: Option<AgentRestartPolicy>,
+) -> Result<(String, u32)> {
+    let session = session_metadata_for_namespace(namespace)?;
+    if session.backend == "codex-app" {
+        return Err(anyhow!(
+            "codex app sessions expose a single logical agent; '{}' cannot take extra agents",
+            namespace
+        ));
+    }
+    spawn_native_agent(
+        namespace,
+        agent,
+        working_dir,
+        Some(shell_command),
+        restart_policy,
+    )
+}
+
+pub fn remove_runtime_agent(namespace: &str, agent: &str) -> Result<()> {
+    let session = session_metadata_for_namespace(namespace)?;
+    if session.backend == "codex-app" {
+        return Err(anyhow!(
+            "codex app sessions expose a single logical agent; use `jarvisctl delete --namespace {}` instead",
+            namespace
+        ));
+    }
+    kill_native_agent(namespace, agent)
+}
+
+pub fn stream_runtime_events(
+    namespace: &str,
+    kinds: &[RuntimeEventKind],
+    follow: bool,
+    sink: impl FnMut(RuntimeEvent) -> Result<()>,
+) -> Result<()> {
+    let session = session_metadata_for_namespace(namespace)?;
+    match session.backend.as_str() {
+        "codex-app" => stream_codex_app_events(namespace, kinds, follow, sink),
+        _ => stream_native_events(namespace, kinds, follow, sink),
+    }
+}
+
 pub fn respond_runtime_server_request(
     namespace: &str,
     request_id: &str,
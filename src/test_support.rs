@@ -2,6 +2,15 @@ use std::fs;
 use std::path::PathBuf;
 use std::sync::{Mutex, MutexGuard, OnceLock};
 
+/// A fresh path under the system temp dir; the caller creates and removes it.
+pub fn unique_temp_dir(prefix: &str) -> PathBuf {
+    let nonce = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .expect("system clock should be after UNIX_EPOCH")
+        .as_nanos();
+    std::env::temp_dir().join(format!("{prefix}-{nonce}"))
+}
+
 static JARVIS_CODEX_ENV_LOCK: OnceLock<Mutex<()>> = OnceLock::new();
 
 pub struct TempJarvisCodexGuard {
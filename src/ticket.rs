@@ -1,9 +1,13 @@
+use crate::request_policy::{RequestPolicyRule, validate_request_policy};
 use anyhow::{Context, anyhow, bail, ensure};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const DONE_STATUSES: &[&str] = &["done", "complete", "completed", "closed", "merged"];
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct TicketFrontmatter {
@@ -67,6 +71,8 @@ pub struct TicketFrontmatter {
     pub codex_goal: Option<String>,
     #[serde(default)]
     pub codex_goal_token_budget: Option<u64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub codex_request_policy: Vec<RequestPolicyRule>,
     #[serde(default)]
     pub codex_memory_mode: Option<String>,
     #[serde(default)]
@@ -83,6 +89,22 @@ pub struct TicketFrontmatter {
     pub codex_completion_status: Option<String>,
     #[serde(default)]
     pub codex_completion_column: Option<String>,
+    #[serde(default)]
+    pub codex_blocked_column: Option<String>,
+    #[serde(default)]
+    pub codex_failure_column: Option<String>,
+    #[serde(default)]
+    pub codex_max_retries: Option<u32>,
+    #[serde(default)]
+    pub codex_retry_backoff_seconds: Option<u64>,
+    #[serde(default)]
+    pub schedule: Option<String>,
+    #[serde(default)]
+    pub codex_timeout_minutes: Option<u64>,
+    #[serde(default)]
+    pub codex_idle_timeout_minutes: Option<u64>,
+    #[serde(default)]
+    pub codex_timeout_column: Option<String>,
     #[serde(default, alias = "codex_finish_tmux")]
     pub codex_finish_mode: Option<String>,
     #[serde(default)]
@@ -104,6 +126,10 @@ pub struct TicketFrontmatter {
     #[serde(default)]
     pub jarvis_mission: Option<String>,
     #[serde(default)]
+    pub depends_on: Vec<String>,
+    #[serde(default)]
+    pub blocks: Vec<String>,
+    #[serde(default)]
     pub created: Option<String>,
     #[serde(default)]
     pub updated: Option<String>,
@@ -169,6 +195,12 @@ impl TicketNote {
 
         self.codex_cli_args()?;
         self.validate_codex_app_protocol_fields()?;
+        validate_request_policy(&self.frontmatter.codex_request_policy).with_context(|| {
+            format!(
+                "ticket '{}' has an invalid codex_request_policy",
+                self.path.display()
+            )
+        })?;
         self.finish_session_policy()?;
 
         Ok(())
@@ -257,6 +289,17 @@ impl TicketNote {
                     .to_string(),
             );
         }
+        for section_name in ["Request", "Definition Of Done"] {
+            if self
+                .section(section_name)
+                .is_none_or(|section| section.trim().is_empty())
+            {
+                warnings.push(format!(
+                    "ticket has no '## {}' section; the Codex prompt will not include it",
+                    section_name
+                ));
+            }
+        }
 
         warnings
     }
@@ -487,6 +530,126 @@ impl TicketNote {
             .to_string()
     }
 
+    pub fn blocked_column(&self) -> String {
+        self.frontmatter
+            .codex_blocked_column
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .unwrap_or("Blocked")
+            .to_string()
+    }
+
+    pub fn failure_column(&self) -> String {
+        self.frontmatter
+            .codex_failure_column
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .unwrap_or("Codex Failed")
+            .to_string()
+    }
+
+    pub fn max_retries(&self) -> u32 {
+        self.frontmatter.codex_max_retries.unwrap_or(0)
+    }
+
+    /// Parses `schedule:`; standard five-field cron expressions are accepted with
+    /// `0`-`7` weekdays, as well as the six/seven-field form with seconds.
+    pub fn schedule(&self) -> anyhow::Result<Option<cron::Schedule>> {
+        let Some(expression) = self
+            .frontmatter
+            .schedule
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+        else {
+            return Ok(None);
+        };
+        let fields = expression.split_whitespace().collect::<Vec<_>>();
+        let normalized = if fields.len() == 5 {
+            format!(
+                "0 {} {} {} {} {}",
+                fields[0],
+                fields[1],
+                fields[2],
+                fields[3],
+                cron_weekdays(fields[4])
+            )
+        } else {
+            expression.to_string()
+        };
+        normalized
+            .parse::<cron::Schedule>()
+            .map(Some)
+            .with_context(|| {
+                format!(
+                    "ticket '{}' has an invalid schedule '{}'",
+                    self.path.display(),
+                    expression
+                )
+            })
+    }
+
+    /// Wall-clock limit for one dispatched run; unset or `0` means no limit.
+    pub fn run_timeout(&self) -> Option<Duration> {
+        minutes(self.frontmatter.codex_timeout_minutes)
+    }
+
+    /// Longest a dispatched run may go without runtime activity; unset or `0` means no limit.
+    pub fn idle_timeout(&self) -> Option<Duration> {
+        minutes(self.frontmatter.codex_idle_timeout_minutes)
+    }
+
+    /// Column for runs stopped by a timeout, defaulting to the failure column.
+    pub fn timeout_column(&self) -> String {
+        self.frontmatter
+            .codex_timeout_column
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(ToOwned::to_owned)
+            .unwrap_or_else(|| self.failure_column())
+    }
+
+    /// Delay before retry `attempt` (1-based): the configured backoff, doubling per attempt.
+    pub fn retry_backoff_seconds(&self, attempt: u32) -> u64 {
+        self.frontmatter
+            .codex_retry_backoff_seconds
+            .unwrap_or(60)
+            .saturating_mul(1 << attempt.saturating_sub(1).min(16))
+    }
+
+    /// Sort key for `priority:`, lowest first: `critical`/`urgent`, `high`, `medium`, `low`,
+    /// or `p0`..`pN` and bare numbers. Missing or unrecognized priorities rank as `medium`.
+    pub fn priority_rank(&self) -> u32 {
+        let Some(priority) = self.frontmatter.priority.as_deref() else {
+            return 2;
+        };
+        let priority = priority.trim().to_ascii_lowercase();
+        match priority.as_str() {
+            "critical" | "urgent" | "highest" => 0,
+            "high" => 1,
+            "medium" | "normal" => 2,
+            "low" => 3,
+            "lowest" => 4,
+            other => other
+                .strip_prefix('p')
+                .unwrap_or(other)
+                .parse()
+                .unwrap_or(2),
+        }
+    }
+
+    /// Whether the ticket's status counts as finished for tickets that depend on it.
+    pub fn is_done(&self) -> bool {
+        self.frontmatter.status.as_deref().is_some_and(|status| {
+            DONE_STATUSES
+                .iter()
+                .any(|done| status.trim().eq_ignore_ascii_case(done))
+        })
+    }
+
     pub fn finish_session_policy(&self) -> anyhow::Result<&str> {
         let policy = self
             .frontmatter
@@ -504,7 +667,7 @@ impl TicketNote {
         Ok(policy)
     }
 
-    fn validate_codex_app_protocol_fields(&self) -> anyhow::Result<()> {
+    pub fn validate_codex_app_protocol_fields(&self) -> anyhow::Result<()> {
         if let Some(personality) = self.frontmatter.codex_personality.as_deref() {
             ensure!(
                 matches!(personality.trim(), "none" | "friendly" | "pragmatic"),
@@ -617,7 +780,7 @@ fn truncate_summary(raw: &str, limit: usize) -> String {
     rendered
 }
 
-fn split_frontmatter(raw: &str) -> anyhow::Result<(String, String)> {
+pub fn split_frontmatter(raw: &str) -> anyhow::Result<(String, String)> {
     let mut lines = raw.lines();
     if lines.next().map(str::trim) != Some("---") {
         bail!("ticket note must start with YAML frontmatter");
@@ -702,19 +865,46 @@ pub fn slugify(input: &str) -> String {
     slug.trim_matches('-').to_string()
 }
 
+/// Shifts standard cron weekdays (`0`/`7` = Sunday) to the `cron` crate's `1` = Sunday.
+fn cron_weekdays(field: &str) -> String {
+    let shift = |value: &str| match value.parse::<u8>() {
+        Ok(0 | 7) => "1".to_string(),
+        Ok(day @ 1..=6) => (day + 1).to_string(),
+        _ => value.to_string(),
+    };
+    field
+        .split(',')
+        .map(|part| {
+            let (range, step) = match part.split_once('/') {
+                Some((range, step)) => (range, Some(step)),
+                None => (part, None),
+            };
+            let range = match range.split_once('-') {
+                // A range ending on Sunday (`5-7`) would wrap past Saturday, so split it.
+                Some((start, "7")) if step.is_none() => format!("{}-7,1", shift(start)),
+                Some((start, end)) => format!("{}-{}", shift(start), shift(end)),
+                None => shift(range),
+            };
+            match step {
+                Some(step) => format!("{range}/{step}"),
+                None => range,
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn minutes(value: Option<u64>) -> Option<Duration> {
+    value
+        .filter(|minutes| *minutes > 0)
+        .map(|minutes| Duration::from_secs(minutes.saturating_mul(60)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::TicketNote;
+    use crate::test_support::unique_temp_dir;
     use std::fs;
-    use std::time::{SystemTime, UNIX_EPOCH};
-
-    fn unique_temp_dir(prefix: &str) -> std::path::PathBuf {
-        let nonce = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("system clock should be after UNIX_EPOCH")
-            .as_nanos();
-        std::env::temp_dir().join(format!("{prefix}-{nonce}"))
-    }
 
     #[test]
     fn load_parses_markdown_ticket_sections_and_prompt() {
@@ -734,6 +924,7 @@ autostart: true
 project: Projects/jarvisctl/Project.md
 repo_path: {}
 codex_finish_mode: close
+codex_idle_timeout_minutes: 20
 ---
 
 # Markdown Launch
@@ -768,11 +959,53 @@ codex_finish_mode: close
         assert!(ticket.render_codex_prompt().contains("Execution Handoff:"));
         assert_eq!(ticket.completion_column(), "Review");
         assert_eq!(ticket.finish_session_policy().unwrap(), "close");
+        assert_eq!(ticket.failure_column(), "Codex Failed");
+        assert_eq!(ticket.max_retries(), 0);
+        assert_eq!(ticket.retry_backoff_seconds(1), 60);
+        assert_eq!(ticket.retry_backoff_seconds(3), 240);
+        assert_eq!(ticket.run_timeout(), None);
+        assert_eq!(
+            ticket.idle_timeout(),
+            Some(std::time::Duration::from_secs(20 * 60))
+        );
+        assert_eq!(ticket.timeout_column(), "Codex Failed");
         ticket.validate_codex_minimum().unwrap();
 
         let _ = fs::remove_dir_all(root);
     }
 
+    #[test]
+    fn schedule_accepts_standard_five_field_cron() {
+        let root = unique_temp_dir("jarvisctl-ticket-schedule");
+        fs::create_dir_all(&root).unwrap();
+        let ticket_path = root.join("ticket.md");
+        fs::write(
+            &ticket_path,
+            "---\ntitle: Weekly Report\nschedule: \"30 9 * * 1-5\"\n---\n",
+        )
+        .unwrap();
+
+        let schedule = TicketNote::load(&ticket_path)
+            .unwrap()
+            .schedule()
+            .unwrap()
+            .unwrap();
+        let monday = chrono::DateTime::parse_from_rfc3339("2026-03-02T00:00:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let runs = schedule
+            .after(&monday)
+            .take(6)
+            .map(|at| at.to_rfc3339())
+            .collect::<Vec<_>>();
+        assert_eq!(runs[0], "2026-03-02T09:30:00+00:00");
+        assert_eq!(runs[4], "2026-03-06T09:30:00+00:00");
+        assert_eq!(runs[5], "2026-03-09T09:30:00+00:00");
+        assert_eq!(super::cron_weekdays("0,5-7"), "1,6-7,1");
+
+        let _ = fs::remove_dir_all(root);
+    }
+
     #[test]
     fn load_uses_markdown_heading_when_frontmatter_title_is_missing() {
         let root = unique_temp_dir("jarvisctl-ticket-heading");
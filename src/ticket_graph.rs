@@ -0,0 +1,463 @@
+use crate::board::{BoardFile, discover_default_boards, resolve_wiki_link};
+use crate::control_plane::ControlPlaneOutput;
+use crate::ticket::TicketNote;
+use anyhow::Context;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+/// Tickets reachable from board cards through `depends_on:` and `blocks:` links.
+#[derive(Debug, Clone, Default)]
+pub struct TicketGraph {
+    vault_path: PathBuf,
+    nodes: BTreeMap<PathBuf, TicketGraphNode>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TicketGraphNode {
+    pub link: String,
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<String>,
+    pub done: bool,
+    pub missing: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+    #[serde(skip)]
+    dependencies: BTreeSet<PathBuf>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TicketGraphReport {
+    pub tickets: Vec<TicketGraphNode>,
+    pub order: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub cycles: Vec<Vec<String>>,
+}
+
+/// A dependency that keeps a ticket from being dispatched.
+#[derive(Debug, Clone)]
+pub struct BlockingDependency {
+    pub link: String,
+    pub reason: String,
+}
+
+impl TicketGraph {
+    /// Loads the graph from board cards (`(link, column)` pairs), following links transitively.
+    pub fn load(vault_path: &Path, cards: &[(String, String)]) -> Self {
+        let mut graph = Self {
+            vault_path: vault_path.to_path_buf(),
+            nodes: BTreeMap::new(),
+        };
+        let mut stems = BTreeMap::new();
+        let mut columns = BTreeMap::new();
+        let mut queue = VecDeque::new();
+        for (link, column) in cards {
+            let path = resolve_wiki_link(vault_path, &clean_link(link));
+            if let Some(stem) = path.file_stem() {
+                stems.insert(stem.to_string_lossy().to_string(), path.clone());
+            }
+            columns.insert(path.clone(), column.clone());
+            queue.push_back(path);
+        }
+
+        let mut blocked_by = Vec::new();
+        while let Some(path) = queue.pop_front() {
+            if graph.nodes.contains_key(&path) {
+                continue;
+            }
+            let ticket = match path.exists().then(|| TicketNote::load(&path)) {
+                Some(Ok(ticket)) => Some(ticket),
+                Some(Err(error)) => {
+                    warn!("Treating '{}' as missing: {error:#}", path.display());
+                    None
+                }
+                None => None,
+            };
+            let mut node = TicketGraphNode {
+                link: graph.link_for(&path),
+                path: path.display().to_string(),
+                title: ticket.as_ref().map(|ticket| ticket.title.clone()),
+                status: ticket
+                    .as_ref()
+                    .and_then(|ticket| ticket.frontmatter.status.clone()),
+                column: columns.get(&path).cloned(),
+                done: ticket.as_ref().is_some_and(TicketNote::is_done),
+                missing: ticket.is_none(),
+                depends_on: Vec::new(),
+                dependencies: BTreeSet::new(),
+            };
+            if let Some(ticket) = ticket {
+                for link in &ticket.frontmatter.depends_on {
+                    let dependency = resolve_dependency_link(vault_path, link, &stems);
+                    queue.push_back(dependency.clone());
+                    node.dependencies.insert(dependency);
+                }
+                for link in &ticket.frontmatter.blocks {
+                    let dependent = resolve_dependency_link(vault_path, link, &stems);
+                    queue.push_back(dependent.clone());
+                    blocked_by.push((dependent, path.clone()));
+                }
+            }
+            graph.nodes.insert(path, node);
+        }
+
+        for (dependent, dependency) in blocked_by {
+            if let Some(node) = graph.nodes.get_mut(&dependent) {
+                node.dependencies.insert(dependency);
+            }
+        }
+        let links = graph
+            .nodes
+            .iter()
+            .map(|(path, node)| (path.clone(), node.link.clone()))
+            .collect::<BTreeMap<_, _>>();
+        for node in graph.nodes.values_mut() {
+            node.depends_on = node
+                .dependencies
+                .iter()
+                .filter_map(|path| links.get(path).cloned())
+                .collect();
+        }
+        graph
+    }
+
+    /// Dependencies of `ticket` that are not done yet, or the cycle it belongs to.
+    pub fn blocking_dependencies(&self, ticket: &Path) -> Vec<BlockingDependency> {
+        let Some(node) = self.nodes.get(ticket) else {
+            return Vec::new();
+        };
+        if let Some(cycle) = self
+            .cycles()
+            .into_iter()
+            .find(|cycle| cycle.iter().any(|path| path == ticket))
+        {
+            return vec![BlockingDependency {
+                link: node.link.clone(),
+                reason: format!("dependency cycle {}", self.render_cycle(&cycle)),
+            }];
+        }
+        node.dependencies
+            .iter()
+            .filter_map(|path| self.nodes.get(path))
+            .filter(|dependency| !dependency.done)
+            .map(|dependency| BlockingDependency {
+                link: dependency.link.clone(),
+                reason: if dependency.missing {
+                    "missing".to_string()
+                } else {
+                    dependency
+                        .status
+                        .clone()
+                        .unwrap_or_else(|| "no status".to_string())
+                },
+            })
+            .collect()
+    }
+
+    /// Strongly connected components with more than one ticket, plus self-dependencies.
+    pub fn cycles(&self) -> Vec<Vec<PathBuf>> {
+        let mut tarjan = Tarjan::default();
+        for path in self.nodes.keys() {
+            if !tarjan.index.contains_key(path) {
+                tarjan.visit(self, path);
+            }
+        }
+        tarjan
+            .components
+            .into_iter()
+            .filter(|component| {
+                component.len() > 1
+                    || self
+                        .nodes
+                        .get(&component[0])
+                        .is_some_and(|node| node.dependencies.contains(&component[0]))
+            })
+            .map(|component| self.walk_cycle(component))
+            .collect()
+    }
+
+    /// Orders a cyclic component by following dependency edges from its first ticket.
+    fn walk_cycle(&self, mut component: Vec<PathBuf>) -> Vec<PathBuf> {
+        component.sort();
+        let mut ordered = vec![component.remove(0)];
+        while let Some(position) = component.iter().position(|candidate| {
+            self.nodes[ordered.last().expect("cycle walk starts non-empty")]
+                .dependencies
+                .contains(candidate)
+        }) {
+            ordered.push(component.remove(position));
+        }
+        ordered.extend(component);
+        ordered
+    }
+
+    pub fn report(&self) -> TicketGraphReport {
+        let cycles = self.cycles();
+        let cyclic = cycles.iter().flatten().collect::<BTreeSet<_>>();
+        let mut remaining = self
+            .nodes
+            .iter()
+            .filter(|(path, _)| !cyclic.contains(path))
+            .map(|(path, node)| (path.clone(), node.dependencies.clone()))
+            .collect::<BTreeMap<_, _>>();
+        let mut order = Vec::new();
+        loop {
+            let ready = remaining
+                .iter()
+                .filter(|(_, dependencies)| {
+                    dependencies
+                        .iter()
+                        .all(|dependency| !remaining.contains_key(dependency))
+                })
+                .map(|(path, _)| path.clone())
+                .collect::<Vec<_>>();
+            if ready.is_empty() {
+                break;
+            }
+            for path in ready {
+                remaining.remove(&path);
+                order.push(self.nodes[&path].link.clone());
+            }
+        }
+        TicketGraphReport {
+            tickets: self.nodes.values().cloned().collect(),
+            order,
+            cycles: cycles
+                .iter()
+                .map(|cycle| cycle.iter().map(|path| self.link_for(path)).collect())
+                .collect(),
+        }
+    }
+
+    fn render_cycle(&self, cycle: &[PathBuf]) -> String {
+        let mut links = cycle
+            .iter()
+            .map(|path| self.link_for(path))
+            .collect::<Vec<_>>();
+        links.push(links[0].clone());
+        links.join(" -> ")
+    }
+
+    fn link_for(&self, path: &Path) -> String {
+        path.strip_prefix(&self.vault_path)
+            .unwrap_or(path)
+            .with_extension("")
+            .display()
+            .to_string()
+    }
+}
+
+#[derive(Default)]
+struct Tarjan {
+    next: usize,
+    index: BTreeMap<PathBuf, usize>,
+    low: BTreeMap<PathBuf, usize>,
+    stack: Vec<PathBuf>,
+    on_stack: BTreeSet<PathBuf>,
+    components: Vec<Vec<PathBuf>>,
+}
+
+impl Tarjan {
+    fn visit(&mut self, graph: &TicketGraph, path: &Path) {
+        self.index.insert(path.to_path_buf(), self.next);
+        self.low.insert(path.to_path_buf(), self.next);
+        self.next += 1;
+        self.stack.push(path.to_path_buf());
+        self.on_stack.insert(path.to_path_buf());
+
+        let dependencies = graph
+            .nodes
+            .get(path)
+            .map(|node| node.dependencies.clone())
+            .unwrap_or_default();
+        for dependency in dependencies {
+            if !self.index.contains_key(&dependency) {
+                self.visit(graph, &dependency);
+                let low = self.low[path].min(self.low[&dependency]);
+                self.low.insert(path.to_path_buf(), low);
+            } else if self.on_stack.contains(&dependency) {
+                let low = self.low[path].min(self.index[&dependency]);
+                self.low.insert(path.to_path_buf(), low);
+            }
+        }
+
+        if self.low[path] == self.index[path] {
+            let mut component = Vec::new();
+            while let Some(member) = self.stack.pop() {
+                self.on_stack.remove(&member);
+                let done = member == path;
+                component.push(member);
+                if done {
+                    break;
+                }
+            }
+            self.components.push(component);
+        }
+    }
+}
+
+/// Loads the graph for the vault's default boards, or for the given boards when any are passed.
+pub fn load_vault_ticket_graph(
+    vault_path: &Path,
+    boards: &[PathBuf],
+) -> anyhow::Result<TicketGraph> {
+    let board_paths = if boards.is_empty() {
+        discover_default_boards(vault_path)?
+    } else {
+        boards
+            .iter()
+            .map(|path| vault_path.join(path))
+            .collect::<Vec<_>>()
+    };
+    let mut cards = Vec::new();
+    for board_path in board_paths {
+        cards.extend(BoardFile::load(&board_path)?.card_positions());
+    }
+    Ok(TicketGraph::load(vault_path, &cards))
+}
+
+pub fn render_ticket_graph_output(
+    report: &TicketGraphReport,
+    output: ControlPlaneOutput,
+) -> anyhow::Result<String> {
+    match output {
+        ControlPlaneOutput::Json => {
+            serde_json::to_string_pretty(report).context("failed to encode ticket graph")
+        }
+        ControlPlaneOutput::Yaml => {
+            serde_yaml::to_string(report).context("failed to encode ticket graph")
+        }
+        ControlPlaneOutput::Table => Ok(render_ticket_graph_table(report)),
+    }
+}
+
+fn render_ticket_graph_table(report: &TicketGraphReport) -> String {
+    let mut lines = vec!["TICKET\tSTATUS\tCOLUMN\tDEPENDS_ON".to_string()];
+    let by_link = report
+        .tickets
+        .iter()
+        .map(|ticket| (ticket.link.as_str(), ticket))
+        .collect::<BTreeMap<_, _>>();
+    let ordered = report
+        .order
+        .iter()
+        .filter_map(|link| by_link.get(link.as_str()).copied())
+        .chain(
+            report
+                .tickets
+                .iter()
+                .filter(|ticket| !report.order.contains(&ticket.link)),
+        );
+    for ticket in ordered {
+        lines.push(format!(
+            "{}\t{}\t{}\t{}",
+            ticket.link,
+            if ticket.missing {
+                "missing"
+            } else {
+                ticket.status.as_deref().unwrap_or("-")
+            },
+            ticket.column.as_deref().unwrap_or("-"),
+            if ticket.depends_on.is_empty() {
+                "-".to_string()
+            } else {
+                ticket.depends_on.join(", ")
+            }
+        ));
+    }
+    for cycle in &report.cycles {
+        let mut links = cycle.clone();
+        links.push(cycle[0].clone());
+        lines.push(format!("cycle: {}", links.join(" -> ")));
+    }
+    lines.join("\n")
+}
+
+pub fn clean_link(raw: &str) -> String {
+    let trimmed = raw
+        .trim()
+        .trim_start_matches("[[")
+        .trim_end_matches("]]")
+        .trim();
+    let without_alias = trimmed.split('|').next().unwrap_or(trimmed);
+    without_alias
+        .split('#')
+        .next()
+        .unwrap_or(without_alias)
+        .trim()
+        .to_string()
+}
+
+/// Resolves a vault-rooted wiki link, falling back to a board ticket with the same note name.
+fn resolve_dependency_link(
+    vault_path: &Path,
+    raw: &str,
+    stems: &BTreeMap<String, PathBuf>,
+) -> PathBuf {
+    let link = clean_link(raw);
+    let resolved = resolve_wiki_link(vault_path, &link);
+    if resolved.exists() || link.contains('/') {
+        return resolved;
+    }
+    stems.get(&link).cloned().unwrap_or(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::unique_temp_dir;
+    use std::fs;
+
+    fn write_ticket(root: &Path, name: &str, frontmatter: &str) {
+        fs::write(
+            root.join(format!("{name}.md")),
+            format!("---\ntype: ticket\n{frontmatter}\n---\n\n# {name}\n"),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn graph_follows_depends_on_and_blocks_and_detects_cycles() {
+        let root = unique_temp_dir("jarvisctl-ticket-graph");
+        fs::create_dir_all(&root).unwrap();
+        write_ticket(&root, "schema", "status: done");
+        write_ticket(&root, "api", "status: active\ndepends_on: [\"[[schema]]\"]");
+        write_ticket(&root, "ui", "status: ready\ndepends_on: [\"[[api|API]]\"]");
+        write_ticket(&root, "docs", "status: ready\nblocks: [\"[[ui]]\"]");
+        write_ticket(&root, "left", "depends_on: [\"[[right]]\"]");
+        write_ticket(&root, "right", "depends_on: [\"[[left]]\"]");
+
+        let cards = ["ui", "docs", "left"]
+            .iter()
+            .map(|link| (link.to_string(), "Ready for Codex".to_string()))
+            .collect::<Vec<_>>();
+        let graph = TicketGraph::load(&root, &cards);
+
+        let blocking = graph
+            .blocking_dependencies(&root.join("ui.md"))
+            .into_iter()
+            .map(|dependency| format!("{} {}", dependency.link, dependency.reason))
+            .collect::<Vec<_>>();
+        assert_eq!(blocking, vec!["api active", "docs ready"]);
+        assert!(graph.blocking_dependencies(&root.join("api.md")).is_empty());
+        assert_eq!(
+            graph.blocking_dependencies(&root.join("left.md"))[0].reason,
+            "dependency cycle left -> right -> left"
+        );
+
+        let report = graph.report();
+        assert_eq!(report.cycles, vec![vec!["left", "right"]]);
+        let position = |link: &str| report.order.iter().position(|entry| entry == link);
+        assert!(position("schema") < position("api"));
+        assert!(position("api") < position("ui"));
+        assert_eq!(position("left"), None);
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -0,0 +1,495 @@
+use crate::board::resolve_wiki_link;
+use crate::control_plane::ControlPlaneOutput;
+use crate::dispatch::parse_ticket_labels;
+use crate::mission::mission_exists;
+use crate::request_policy::validate_request_policy;
+use crate::ticket::{TicketFrontmatter, TicketNote, split_frontmatter};
+use crate::ticket_graph::clean_link;
+use anyhow::Context;
+use serde::Serialize;
+use serde_yaml::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const SKIPPED_DIRS: &[&str] = &[".obsidian", ".git", ".trash"];
+const CODEX_DRIVERS: &[&str] = &["app-server", "app_server", "cli-pty", "cli_pty"];
+const PRIORITY_NAMES: &[&str] = &[
+    "critical", "urgent", "highest", "high", "medium", "normal", "low", "lowest",
+];
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LintSeverity {
+    Error,
+    Warning,
+}
+
+impl LintSeverity {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LintIssue {
+    pub severity: LintSeverity,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TicketLintResult {
+    pub path: String,
+    pub issues: Vec<LintIssue>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TicketLintReport {
+    pub tickets: Vec<TicketLintResult>,
+    pub errors: usize,
+    pub warnings: usize,
+}
+
+/// Lints the given ticket notes, or every `type: ticket` note in the vault when none are given.
+/// Without a vault, depends_on/blocks links are not checked.
+pub fn lint_tickets(
+    vault_path: Option<&Path>,
+    paths: &[PathBuf],
+) -> anyhow::Result<TicketLintReport> {
+    let vault_tickets = match vault_path {
+        Some(vault_path) => discover_vault_tickets(vault_path)?,
+        None => Vec::new(),
+    };
+    let targets = if paths.is_empty() {
+        vault_tickets.clone()
+    } else {
+        paths.to_vec()
+    };
+    let stems = vault_tickets
+        .iter()
+        .chain(&targets)
+        .filter_map(|path| path.file_stem())
+        .map(|stem| stem.to_string_lossy().to_string())
+        .collect::<BTreeSet<_>>();
+
+    let tickets = targets
+        .iter()
+        .map(|path| TicketLintResult {
+            path: path.display().to_string(),
+            issues: lint_ticket(path, vault_path, &stems),
+        })
+        .collect::<Vec<_>>();
+    let count = |severity| {
+        tickets
+            .iter()
+            .flat_map(|ticket| &ticket.issues)
+            .filter(|issue| issue.severity == severity)
+            .count()
+    };
+    Ok(TicketLintReport {
+        errors: count(LintSeverity::Error),
+        warnings: count(LintSeverity::Warning),
+        tickets,
+    })
+}
+
+fn lint_ticket(path: &Path, vault_path: Option<&Path>, stems: &BTreeSet<String>) -> Vec<LintIssue> {
+    let mut issues = Vec::new();
+    let raw = match fs::read_to_string(path) {
+        Ok(raw) => raw,
+        Err(error) => {
+            issues.push(error_issue(None, format!("failed to read note: {error}")));
+            return issues;
+        }
+    };
+    let mapping = match split_frontmatter(&raw).and_then(|(frontmatter, _)| {
+        serde_yaml::from_str::<Value>(&frontmatter).context("frontmatter is not valid YAML")
+    }) {
+        Ok(Value::Mapping(mapping)) => mapping,
+        Ok(Value::Null) => Default::default(),
+        Ok(_) => {
+            issues.push(error_issue(
+                None,
+                "frontmatter is not a YAML mapping".to_string(),
+            ));
+            return issues;
+        }
+        Err(error) => {
+            issues.push(error_issue(None, format!("{error:#}")));
+            return issues;
+        }
+    };
+
+    let known = known_frontmatter_keys();
+    for key in mapping.keys().filter_map(Value::as_str) {
+        if known.contains(key) {
+            continue;
+        }
+        match did_you_mean(key, &known) {
+            Some(suggestion) => issues.push(LintIssue {
+                severity: LintSeverity::Error,
+                field: Some(key.to_string()),
+                message: format!("unknown frontmatter key '{key}' is ignored"),
+                suggestion: Some(suggestion.to_string()),
+            }),
+            None => issues.push(warning_issue(
+                Some(key),
+                format!("unknown frontmatter key '{key}' is ignored by jarvisctl"),
+            )),
+        }
+    }
+
+    let ticket = match TicketNote::load(path) {
+        Ok(ticket) => ticket,
+        Err(error) => {
+            issues.push(error_issue(None, format!("{error:#}")));
+            return issues;
+        }
+    };
+    lint_fields(&ticket, vault_path, stems, &mut issues);
+    issues.extend(
+        ticket
+            .readiness_warnings()
+            .into_iter()
+            .map(|warning| warning_issue(None, warning)),
+    );
+    issues
+}
+
+fn lint_fields(
+    ticket: &TicketNote,
+    vault_path: Option<&Path>,
+    stems: &BTreeSet<String>,
+    issues: &mut Vec<LintIssue>,
+) {
+    let frontmatter = &ticket.frontmatter;
+    let mut check = |field: Option<&str>, result: anyhow::Result<()>| {
+        if let Err(error) = result {
+            issues.push(error_issue(field, format!("{error:#}")));
+        }
+    };
+
+    if let Some(kind) = frontmatter.kind.as_deref()
+        && !kind.eq_ignore_ascii_case("ticket")
+    {
+        check(
+            Some("type"),
+            Err(anyhow::anyhow!(
+                "unsupported type '{kind}'; expected 'ticket'"
+            )),
+        );
+    }
+    match frontmatter.repo_path.as_deref() {
+        None => check(
+            Some("repo_path"),
+            Err(anyhow::anyhow!("repo_path is missing")),
+        ),
+        Some(repo_path) if !Path::new(repo_path).exists() => check(
+            Some("repo_path"),
+            Err(anyhow::anyhow!("repo_path '{repo_path}' does not exist")),
+        ),
+        Some(_) => {}
+    }
+    if let Some(driver) = frontmatter.codex_driver.as_deref()
+        && !CODEX_DRIVERS.contains(&driver.trim())
+    {
+        check(
+            Some("codex_driver"),
+            Err(anyhow::anyhow!(
+                "unsupported codex_driver '{driver}'; expected app-server or cli-pty"
+            )),
+        );
+    }
+    check(None, ticket.codex_cli_args().map(drop));
+    check(None, ticket.validate_codex_app_protocol_fields());
+    check(
+        Some("codex_request_policy"),
+        validate_request_policy(&frontmatter.codex_request_policy),
+    );
+    check(
+        Some("codex_finish_mode"),
+        ticket.finish_session_policy().map(drop),
+    );
+    check(Some("schedule"), ticket.schedule().map(drop));
+    check(
+        Some("jarvis_node_labels"),
+        parse_ticket_labels(&frontmatter.jarvis_node_labels).map(drop),
+    );
+    if frontmatter
+        .jarvis_node_tolerations
+        .iter()
+        .any(|toleration| toleration.trim().is_empty())
+    {
+        check(
+            Some("jarvis_node_tolerations"),
+            Err(anyhow::anyhow!(
+                "jarvis_node_tolerations entries must be non-empty"
+            )),
+        );
+    }
+    if let Some(mission) = frontmatter
+        .jarvis_mission
+        .as_deref()
+        .map(str::trim)
+        .filter(|mission| !mission.is_empty())
+    {
+        match mission_exists(mission) {
+            Ok(true) => {}
+            Ok(false) => check(
+                Some("jarvis_mission"),
+                Err(anyhow::anyhow!("mission '{mission}' does not exist")),
+            ),
+            Err(error) => check(Some("jarvis_mission"), Err(error)),
+        }
+    }
+
+    if let Some(priority) = frontmatter.priority.as_deref() {
+        let normalized = priority.trim().to_ascii_lowercase();
+        let numeric = normalized.strip_prefix('p').unwrap_or(&normalized);
+        if !PRIORITY_NAMES.contains(&normalized.as_str()) && numeric.parse::<u32>().is_err() {
+            issues.push(warning_issue(
+                Some("priority"),
+                format!("unrecognized priority '{priority}' is treated as medium"),
+            ));
+        }
+    }
+    let Some(vault_path) = vault_path else {
+        return;
+    };
+    for (field, links) in [
+        ("depends_on", &frontmatter.depends_on),
+        ("blocks", &frontmatter.blocks),
+    ] {
+        for link in links {
+            let link = clean_link(link);
+            if !resolve_wiki_link(vault_path, &link).exists() && !stems.contains(&link) {
+                issues.push(warning_issue(
+                    Some(field),
+                    format!("{field} entry [[{link}]] does not resolve to a note in the vault"),
+                ));
+            }
+        }
+    }
+}
+
+fn error_issue(field: Option<&str>, message: String) -> LintIssue {
+    LintIssue {
+        severity: LintSeverity::Error,
+        field: field.map(ToOwned::to_owned),
+        message,
+        suggestion: None,
+    }
+}
+
+fn warning_issue(field: Option<&str>, message: String) -> LintIssue {
+    LintIssue {
+        severity: LintSeverity::Warning,
+        ..error_issue(field, message)
+    }
+}
+
+/// Every key `TicketFrontmatter` understands, read off its serialized form so new fields
+/// are picked up without a separate list.
+fn known_frontmatter_keys() -> BTreeSet<String> {
+    let mut keys = match serde_json::to_value(TicketFrontmatter::default()) {
+        Ok(serde_json::Value::Object(object)) => object.keys().cloned().collect(),
+        _ => BTreeSet::new(),
+    };
+    keys.extend(["codex_request_policy", "codex_finish_tmux"].map(ToOwned::to_owned));
+    keys
+}
+
+/// The closest known key when it is near enough to be a typo rather than a custom property.
+fn did_you_mean<'a>(key: &str, known: &'a BTreeSet<String>) -> Option<&'a str> {
+    let limit = if key.chars().count() < 5 { 1 } else { 2 };
+    known
+        .iter()
+        .map(|candidate| (edit_distance(key, candidate), candidate))
+        .filter(|(distance, _)| *distance <= limit)
+        .min()
+        .map(|(_, candidate)| candidate.as_str())
+}
+
+fn edit_distance(left: &str, right: &str) -> usize {
+    let right = right.chars().collect::<Vec<_>>();
+    let mut previous = (0..=right.len()).collect::<Vec<_>>();
+    for (row, left_char) in left.chars().enumerate() {
+        let mut current = vec![row + 1];
+        for (column, right_char) in right.iter().enumerate() {
+            let substitution = previous[column] + usize::from(left_char != *right_char);
+            current.push(
+                substitution
+                    .min(previous[column + 1] + 1)
+                    .min(current[column] + 1),
+            );
+        }
+        previous = current;
+    }
+    previous[right.len()]
+}
+
+fn discover_vault_tickets(vault_path: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let mut tickets = Vec::new();
+    let mut pending = vec![vault_path.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let entries =
+            fs::read_dir(&dir).with_context(|| format!("failed to read '{}'", dir.display()))?;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let skipped = entry
+                .file_name()
+                .to_str()
+                .is_some_and(|name| SKIPPED_DIRS.contains(&name));
+            if entry.file_type().is_ok_and(|kind| kind.is_dir()) {
+                if !skipped {
+                    pending.push(path);
+                }
+            } else if path.extension().and_then(|value| value.to_str()) == Some("md")
+                && is_ticket_note(&path)
+            {
+                tickets.push(path);
+            }
+        }
+    }
+    tickets.sort();
+    Ok(tickets)
+}
+
+fn is_ticket_note(path: &Path) -> bool {
+    let Ok(raw) = fs::read_to_string(path) else {
+        return false;
+    };
+    let Ok((frontmatter, _)) = split_frontmatter(&raw) else {
+        return false;
+    };
+    serde_yaml::from_str::<BTreeMap<String, Value>>(&frontmatter)
+        .ok()
+        .and_then(|mapping| {
+            mapping
+                .get("type")
+                .and_then(Value::as_str)
+                .map(str::to_owned)
+        })
+        .is_some_and(|kind| kind.eq_ignore_ascii_case("ticket"))
+}
+
+pub fn render_ticket_lint_output(
+    report: &TicketLintReport,
+    output: ControlPlaneOutput,
+) -> anyhow::Result<String> {
+    match output {
+        ControlPlaneOutput::Json => {
+            serde_json::to_string_pretty(report).context("failed to encode ticket lint report")
+        }
+        ControlPlaneOutput::Yaml => {
+            serde_yaml::to_string(report).context("failed to encode ticket lint report")
+        }
+        ControlPlaneOutput::Table => {
+            let mut lines = vec!["TICKET\tSEVERITY\tFIELD\tMESSAGE".to_string()];
+            for ticket in &report.tickets {
+                for issue in &ticket.issues {
+                    let mut message = issue.message.clone();
+                    if let Some(suggestion) = &issue.suggestion {
+                        message.push_str(&format!("; did you mean '{suggestion}'?"));
+                    }
+                    lines.push(format!(
+                        "{}\t{}\t{}\t{}",
+                        ticket.path,
+                        issue.severity.as_str(),
+                        issue.field.as_deref().unwrap_or("-"),
+                        message
+                    ));
+                }
+            }
+            lines.push(format!(
+                "{} ticket(s), {} error(s), {} warning(s)",
+                report.tickets.len(),
+                report.errors,
+                report.warnings
+            ));
+            Ok(lines.join("\n"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::unique_temp_dir;
+
+    #[test]
+    fn lint_reports_typos_missing_fields_and_sections() {
+        let vault = unique_temp_dir("jarvisctl-ticket-lint");
+        fs::create_dir_all(vault.join("Tickets")).unwrap();
+        fs::write(vault.join("Notes.md"), "# Not a ticket\n").unwrap();
+        fs::write(
+            vault.join("Tickets").join("typo.md"),
+            "---\ntype: ticket\nowner: codex\ncodex_reasoning_efort: high\narea: infra\npriority: soon\ncodex_finish_mode: linger\ndepends_on:\n  - \"[[Tickets/missing]]\"\ncodex_request_policy:\n  - action: approve\n    pattern: \"cargo (test\"\n---\n\n## Request\n- Fix it.\n",
+        )
+        .unwrap();
+
+        let report = lint_tickets(Some(&vault), &[]).unwrap();
+        assert_eq!(report.tickets.len(), 1);
+        let issues = &report.tickets[0].issues;
+        let find = |field: &str| {
+            issues
+                .iter()
+                .find(|issue| issue.field.as_deref() == Some(field))
+                .unwrap()
+        };
+        assert_eq!(
+            find("codex_reasoning_efort").suggestion.as_deref(),
+            Some("codex_reasoning_effort")
+        );
+        assert_eq!(find("codex_reasoning_efort").severity, LintSeverity::Error);
+        assert_eq!(find("area").severity, LintSeverity::Warning);
+        assert_eq!(find("repo_path").severity, LintSeverity::Error);
+        assert_eq!(find("codex_finish_mode").severity, LintSeverity::Error);
+        assert_eq!(find("priority").severity, LintSeverity::Warning);
+        assert_eq!(find("depends_on").severity, LintSeverity::Warning);
+        assert!(
+            find("codex_request_policy")
+                .message
+                .contains("rule 1 has an invalid pattern")
+        );
+        assert!(
+            issues
+                .iter()
+                .any(|issue| issue.message.contains("Definition Of Done"))
+        );
+        assert_eq!(report.errors, 4);
+
+        let _ = fs::remove_dir_all(vault);
+    }
+
+    #[test]
+    fn lint_explicit_paths_without_vault_skips_link_checks() {
+        let dir = unique_temp_dir("jarvisctl-ticket-lint-paths");
+        fs::create_dir_all(&dir).unwrap();
+        let note = dir.join("standalone.md");
+        fs::write(
+            &note,
+            "---\ntype: ticket\nowner: codex\ndepends_on:\n  - \"[[Tickets/elsewhere]]\"\n---\n\n## Request\n- Fix it.\n",
+        )
+        .unwrap();
+
+        let report = lint_tickets(None, std::slice::from_ref(&note)).unwrap();
+        assert_eq!(report.tickets.len(), 1);
+        assert!(
+            report.tickets[0]
+                .issues
+                .iter()
+                .all(|issue| issue.field.as_deref() != Some("depends_on"))
+        );
+        assert!(lint_tickets(Some(&dir.join("missing-vault")), &[note]).is_err());
+
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,569 @@
+use crate::board::{BoardFile, resolve_wiki_link};
+use crate::control_plane::{ControlPlaneOutput, atomic_write_string};
+use crate::ticket::{TicketFrontmatter, slugify, split_frontmatter};
+use crate::ticket_graph::clean_link;
+use crate::ticket_lint::{LintIssue, lint_tickets};
+use anyhow::{Context, anyhow, bail, ensure};
+use serde::Serialize;
+use serde_yaml::{Mapping, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Keys written first, in this order, so generated notes read the same regardless of template.
+const LEADING_KEYS: &[&str] = &[
+    "type",
+    "id",
+    "title",
+    "status",
+    "owner",
+    "autostart",
+    "priority",
+    "project",
+    "repo_path",
+];
+const DISPATCH_BOARD: &str = "Ops/Codex Dispatch Board.md";
+
+const FEATURE_TEMPLATE: &str = "---
+status: backlog
+owner: codex
+autostart: true
+priority: medium
+---
+
+# {{title}}
+
+## Request
+
+- Describe the capability to add and who it is for.
+
+## Definition Of Done
+
+- The feature works end to end for the described use.
+- Tests cover the new behaviour.
+- User-facing docs mention the change.
+
+## Context
+
+- Link related notes, prior tickets, and relevant code paths.
+
+## Constraints
+
+- Keep existing behaviour and public interfaces unchanged unless the request says otherwise.
+
+## Execution Handoff
+
+- Summarize what changed, how it was validated, and any follow-ups.
+
+## Progress
+
+";
+
+const BUGFIX_TEMPLATE: &str = "---
+status: backlog
+owner: codex
+autostart: true
+priority: high
+---
+
+# {{title}}
+
+## Request
+
+- What happens:
+- What should happen:
+- Steps to reproduce:
+
+## Definition Of Done
+
+- The bug no longer reproduces.
+- A regression test covers the failing case.
+- Existing tests still pass.
+
+## Context
+
+- Link logs, error output, and the code paths involved.
+
+## Constraints
+
+- Keep the fix minimal; no unrelated refactors.
+
+## Execution Handoff
+
+- Reproduce the bug before fixing it and report the root cause.
+
+## Progress
+
+";
+
+const CHORE_TEMPLATE: &str = "---
+status: backlog
+owner: codex
+autostart: true
+priority: low
+---
+
+# {{title}}
+
+## Request
+
+- Describe the maintenance work: dependency bumps, cleanup, tooling, or docs.
+
+## Definition Of Done
+
+- The build, lints, and tests pass.
+- No behaviour changes.
+
+## Context
+
+- Link the motivating notes or upstream changes.
+
+## Constraints
+
+- Keep the change mechanical and easy to review.
+
+## Execution Handoff
+
+- List anything deferred for a follow-up ticket.
+
+## Progress
+
+";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TicketTemplate {
+    pub id: String,
+    /// `builtin`, or the path of a user template under `~/.jarvis/ticket-templates/`.
+    pub source: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip)]
+    pub raw: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct TicketNewOptions {
+    pub vault_path: PathBuf,
+    pub template: String,
+    pub title: String,
+    pub project: Option<String>,
+    pub repo_path: Option<String>,
+    pub priority: Option<String>,
+    pub column: Option<String>,
+    pub board: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TicketNewResult {
+    pub path: String,
+    pub link: String,
+    pub template: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub board: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<String>,
+    /// Lint findings for the new note, such as a missing `repo_path`.
+    pub issues: Vec<LintIssue>,
+}
+
+/// Built-in templates followed by user templates; a user template replaces a built-in
+/// with the same id.
+pub fn ticket_templates() -> anyhow::Result<Vec<TicketTemplate>> {
+    load_ticket_templates(&ticket_templates_dir()?)
+}
+
+fn load_ticket_templates(dir: &Path) -> anyhow::Result<Vec<TicketTemplate>> {
+    let mut templates = vec![
+        builtin_template(
+            "bugfix",
+            "Reproduce and fix a defect with a regression test",
+            BUGFIX_TEMPLATE,
+        ),
+        builtin_template(
+            "chore",
+            "Maintenance work with no behaviour change",
+            CHORE_TEMPLATE,
+        ),
+        builtin_template(
+            "feature",
+            "Add a new capability end to end",
+            FEATURE_TEMPLATE,
+        ),
+    ];
+    if !dir.exists() {
+        return Ok(templates);
+    }
+
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(dir).with_context(|| format!("failed to read '{}'", dir.display()))? {
+        let path = entry?.path();
+        if path.is_file() && path.extension().and_then(|value| value.to_str()) == Some("md") {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    for path in paths {
+        let Some(id) = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+        else {
+            continue;
+        };
+        let raw = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read ticket template '{}'", path.display()))?;
+        let template = TicketTemplate {
+            id,
+            source: path.display().to_string(),
+            description: None,
+            raw,
+        };
+        match templates
+            .iter_mut()
+            .find(|existing| existing.id == template.id)
+        {
+            Some(existing) => *existing = template,
+            None => templates.push(template),
+        }
+    }
+    Ok(templates)
+}
+
+fn builtin_template(id: &str, description: &str, raw: &str) -> TicketTemplate {
+    TicketTemplate {
+        id: id.to_string(),
+        source: "builtin".to_string(),
+        description: Some(description.to_string()),
+        raw: raw.to_string(),
+    }
+}
+
+fn ticket_templates_dir() -> anyhow::Result<PathBuf> {
+    let home = std::env::var_os("HOME").context("HOME is not set")?;
+    Ok(PathBuf::from(home).join(".jarvis").join("ticket-templates"))
+}
+
+/// Writes a new ticket note from a template and optionally adds its card to a board lane.
+pub fn create_ticket(options: TicketNewOptions) -> anyhow::Result<TicketNewResult> {
+    create_ticket_from(&ticket_templates()?, options)
+}
+
+fn create_ticket_from(
+    templates: &[TicketTemplate],
+    options: TicketNewOptions,
+) -> anyhow::Result<TicketNewResult> {
+    let title = options.title.trim();
+    ensure!(!title.is_empty(), "ticket title must not be empty");
+    let id = slugify(title);
+    ensure!(
+        !id.is_empty(),
+        "ticket title '{}' has no usable characters",
+        title
+    );
+    let template = templates
+        .iter()
+        .find(|template| template.id == options.template)
+        .ok_or_else(|| {
+            anyhow!(
+                "ticket template '{}' does not exist; available: {}",
+                options.template,
+                templates
+                    .iter()
+                    .map(|template| template.id.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        })?;
+
+    let project = clean_optional(options.project.as_deref()).map(project_paths);
+    let tickets_dir = match &project {
+        Some((_, dir)) if !dir.is_empty() => format!("{}/Tickets", dir),
+        _ => "Tickets".to_string(),
+    };
+    let link = format!("{}/{}", tickets_dir, id);
+    let path = resolve_wiki_link(&options.vault_path, &link);
+    if path.exists() {
+        bail!("ticket note '{}' already exists", path.display());
+    }
+
+    // Load the board before writing so a bad board path leaves nothing behind.
+    let column = clean_optional(options.column.as_deref()).map(ToOwned::to_owned);
+    let mut board = match &column {
+        Some(_) => {
+            let board_path = match &options.board {
+                Some(board) if board.is_absolute() => board.clone(),
+                Some(board) => options.vault_path.join(board),
+                None => options.vault_path.join(match &project {
+                    Some((_, dir)) if !dir.is_empty() => format!("{}/Board.md", dir),
+                    _ => DISPATCH_BOARD.to_string(),
+                }),
+            };
+            Some(BoardFile::load(&board_path)?)
+        }
+        None => None,
+    };
+
+    let values = TemplateValues {
+        id: &id,
+        title,
+        project: project.as_ref().map(|(note, _)| note.as_str()),
+        repo_path: clean_optional(options.repo_path.as_deref()),
+        priority: clean_optional(options.priority.as_deref()),
+    };
+    let rendered = render_template(template, &values)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create '{}'", parent.display()))?;
+    }
+    atomic_write_string(&path, &rendered)
+        .with_context(|| format!("failed to write ticket note '{}'", path.display()))?;
+
+    if let (Some(board), Some(column)) = (board.as_mut(), column.as_deref()) {
+        board.add_card(&link, column)?;
+        board.save()?;
+    }
+
+    let issues = lint_tickets(Some(&options.vault_path), std::slice::from_ref(&path))?
+        .tickets
+        .into_iter()
+        .flat_map(|ticket| ticket.issues)
+        .collect();
+    Ok(TicketNewResult {
+        path: path.display().to_string(),
+        link,
+        template: template.id.clone(),
+        board: board.map(|board| board.path.display().to_string()),
+        column,
+        issues,
+    })
+}
+
+struct TemplateValues<'a> {
+    id: &'a str,
+    title: &'a str,
+    project: Option<&'a str>,
+    repo_path: Option<&'a str>,
+    priority: Option<&'a str>,
+}
+
+/// Fills `{{title}}`, `{{id}}`, `{{project}}`, `{{repo_path}}`, and `{{date}}` in the
+/// template body and sets the identifying frontmatter keys, which override the template's.
+fn render_template(template: &TicketTemplate, values: &TemplateValues) -> anyhow::Result<String> {
+    let (frontmatter_raw, body) = split_frontmatter(&template.raw)
+        .with_context(|| format!("ticket template '{}' is invalid", template.source))?;
+    let mut fields = if frontmatter_raw.trim().is_empty() {
+        Mapping::new()
+    } else {
+        serde_yaml::from_str::<Mapping>(&frontmatter_raw).with_context(|| {
+            format!(
+                "ticket template '{}' frontmatter is not a YAML mapping",
+                template.source
+            )
+        })?
+    };
+
+    fields.insert("type".into(), "ticket".into());
+    fields.insert("id".into(), values.id.into());
+    fields.insert("title".into(), values.title.into());
+    for (key, value) in [
+        ("project", values.project),
+        ("repo_path", values.repo_path),
+        ("priority", values.priority),
+    ] {
+        if let Some(value) = value {
+            fields.insert(key.into(), value.into());
+        }
+    }
+
+    let mut ordered = Mapping::new();
+    for key in LEADING_KEYS {
+        if let Some(value) = fields.remove(*key) {
+            ordered.insert((*key).into(), value);
+        }
+    }
+    ordered.extend(fields);
+    serde_yaml::from_value::<TicketFrontmatter>(Value::Mapping(ordered.clone())).with_context(
+        || {
+            format!(
+                "ticket template '{}' has invalid frontmatter",
+                template.source
+            )
+        },
+    )?;
+
+    let date = chrono::Local::now().format("%Y-%m-%d").to_string();
+    let body = body
+        .replace("{{title}}", values.title)
+        .replace("{{id}}", values.id)
+        .replace("{{project}}", values.project.unwrap_or_default())
+        .replace("{{repo_path}}", values.repo_path.unwrap_or_default())
+        .replace("{{date}}", &date);
+    let frontmatter =
+        serde_yaml::to_string(&ordered).context("failed to encode ticket frontmatter")?;
+    Ok(format!(
+        "---\n{}---\n\n{}\n",
+        frontmatter,
+        body.trim_matches('\n')
+    ))
+}
+
+/// Accepts `Projects/X`, `Projects/X/Project.md`, or `[[Projects/X/Project]]` and returns
+/// the project note path and the project directory, both vault-relative.
+fn project_paths(project: &str) -> (String, String) {
+    let project = clean_link(project);
+    let project = project.trim_end_matches('/');
+    if project.ends_with(".md") {
+        let dir = project.rsplit_once('/').map(|(dir, _)| dir).unwrap_or("");
+        return (project.to_string(), dir.to_string());
+    }
+    let leaf = project.rsplit('/').next().unwrap_or(project);
+    if leaf == "Project" {
+        let dir = project.rsplit_once('/').map(|(dir, _)| dir).unwrap_or("");
+        return (format!("{}.md", project), dir.to_string());
+    }
+    (format!("{}/Project.md", project), project.to_string())
+}
+
+fn clean_optional(value: Option<&str>) -> Option<&str> {
+    value.map(str::trim).filter(|value| !value.is_empty())
+}
+
+pub fn render_ticket_templates_output(
+    templates: &[TicketTemplate],
+    output: ControlPlaneOutput,
+) -> anyhow::Result<String> {
+    match output {
+        ControlPlaneOutput::Json => {
+            serde_json::to_string_pretty(templates).context("failed to encode ticket templates")
+        }
+        ControlPlaneOutput::Yaml => {
+            serde_yaml::to_string(templates).context("failed to encode ticket templates")
+        }
+        ControlPlaneOutput::Table => {
+            let mut lines = vec!["ID\tSOURCE\tDESCRIPTION".to_string()];
+            for template in templates {
+                lines.push(format!(
+                    "{}\t{}\t{}",
+                    template.id,
+                    template.source,
+                    template.description.as_deref().unwrap_or("-")
+                ));
+            }
+            Ok(lines.join("\n"))
+        }
+    }
+}
+
+pub fn render_ticket_new_output(
+    result: &TicketNewResult,
+    output: ControlPlaneOutput,
+) -> anyhow::Result<String> {
+    match output {
+        ControlPlaneOutput::Json => {
+            serde_json::to_string_pretty(result).context("failed to encode new ticket")
+        }
+        ControlPlaneOutput::Yaml => {
+            serde_yaml::to_string(result).context("failed to encode new ticket")
+        }
+        ControlPlaneOutput::Table => {
+            let mut lines = vec![format!(
+                "created {} from template '{}'",
+                result.path, result.template
+            )];
+            if let (Some(board), Some(column)) = (&result.board, &result.column) {
+                lines.push(format!(
+                    "added [[{}]] to '{}' on {}",
+                    result.link, column, board
+                ));
+            }
+            for issue in &result.issues {
+                lines.push(format!(
+                    "{}: {}",
+                    issue.field.as_deref().unwrap_or("ticket"),
+                    issue.message
+                ));
+            }
+            Ok(lines.join("\n"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::unique_temp_dir;
+    use crate::ticket::TicketNote;
+
+    #[test]
+    fn create_ticket_renders_template_and_adds_board_card() {
+        let root = unique_temp_dir("jarvisctl-ticket-new");
+        let vault = root.join("vault");
+        let templates_dir = root.join("templates");
+        fs::create_dir_all(vault.join("Projects").join("X")).unwrap();
+        fs::create_dir_all(&templates_dir).unwrap();
+        fs::write(
+            vault.join("Projects").join("X").join("Board.md"),
+            "## Backlog\n\n## Ready for Codex\n\n",
+        )
+        .unwrap();
+        fs::write(
+            templates_dir.join("spike.md"),
+            "---\nowner: codex\ncodex_reasoning_effort: high\n---\n# {{title}}\n\n## Request\n- Investigate in {{repo_path}}.\n\n## Definition Of Done\n- Write up findings.\n",
+        )
+        .unwrap();
+
+        let templates = load_ticket_templates(&templates_dir).unwrap();
+        assert_eq!(
+            templates
+                .iter()
+                .map(|template| template.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["bugfix", "chore", "feature", "spike"]
+        );
+
+        let options = |template: &str, title: &str| TicketNewOptions {
+            vault_path: vault.clone(),
+            template: template.to_string(),
+            title: title.to_string(),
+            project: Some("Projects/X".to_string()),
+            repo_path: Some(root.display().to_string()),
+            priority: None,
+            column: Some("Backlog".to_string()),
+            board: None,
+        };
+        let result =
+            create_ticket_from(&templates, options("bugfix", "Parser: crash on tabs")).unwrap();
+        assert_eq!(result.link, "Projects/X/Tickets/parser-crash-on-tabs");
+        assert!(result.issues.is_empty(), "{:?}", result.issues);
+
+        let ticket = TicketNote::load(&result.path).unwrap();
+        assert_eq!(ticket.title, "Parser: crash on tabs");
+        assert_eq!(
+            ticket.frontmatter.project.as_deref(),
+            Some("Projects/X/Project.md")
+        );
+        assert_eq!(
+            ticket.frontmatter.repo_path.as_deref(),
+            Some(root.display().to_string().as_str())
+        );
+        assert_eq!(ticket.frontmatter.priority.as_deref(), Some("high"));
+        assert!(ticket.render_codex_prompt().contains("Definition Of Done:"));
+
+        let board = BoardFile::load(vault.join("Projects").join("X").join("Board.md")).unwrap();
+        assert_eq!(
+            board.card_positions(),
+            vec![(result.link.clone(), "Backlog".to_string())]
+        );
+
+        let spike = create_ticket_from(&templates, options("spike", "Try tabs")).unwrap();
+        let raw = fs::read_to_string(&spike.path).unwrap();
+        assert!(
+            raw.starts_with("---\ntype: ticket\nid: try-tabs\ntitle: Try tabs\nowner: codex\n")
+        );
+        assert!(raw.contains(&format!("- Investigate in {}.", root.display())));
+
+        assert!(create_ticket_from(&templates, options("spike", "Try tabs")).is_err());
+        assert!(create_ticket_from(&templates, options("missing", "Other")).is_err());
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
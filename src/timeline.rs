@@ -0,0 +1,761 @@
+use crate::native::RuntimeServerRequest;
+use anyhow::Context;
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt::Write as _;
+
+const SUMMARY_TURN_LIMIT: usize = 12;
+const SUMMARY_OUTPUT_LINES: usize = 40;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, ValueEnum, Default)]
+pub enum TimelineFormat {
+    #[default]
+    Text,
+    Markdown,
+    Json,
+    Html,
+}
+
+/// Turn-by-turn view of an app-server thread, joined with the server requests answered during it.
+#[derive(Debug, Clone, Serialize)]
+pub struct Timeline {
+    pub namespace: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+    /// Turns left out because the timeline was rendered without `--full`.
+    #[serde(skip_serializing_if = "is_zero")]
+    pub omitted_turns: usize,
+    pub turns: Vec<TimelineTurn>,
+    /// Approvals whose params did not name a turn from this thread.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub unmatched_approvals: Vec<TimelineApproval>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TimelineTurn {
+    pub id: String,
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub entries: Vec<TimelineEntry>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub approvals: Vec<TimelineApproval>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum TimelineEntry {
+    UserInput {
+        text: String,
+    },
+    AgentMessage {
+        text: String,
+    },
+    Reasoning {
+        summary: Vec<String>,
+    },
+    Plan {
+        text: String,
+    },
+    Command {
+        command: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        cwd: Option<String>,
+        status: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        exit_code: Option<i64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        duration_ms: Option<u64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        output: Option<String>,
+    },
+    FileChange {
+        status: String,
+        changes: Vec<TimelineFileChange>,
+    },
+    ToolCall {
+        name: String,
+        status: String,
+    },
+    Other {
+        item_type: String,
+    },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TimelineFileChange {
+    pub path: String,
+    pub change: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diff: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TimelineApproval {
+    pub id: String,
+    pub method: String,
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decision: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+}
+
+/// Builds a timeline from a `thread/read` response; `full` keeps every turn and untruncated output.
+pub fn build_timeline(
+    namespace: &str,
+    thread_response: &Value,
+    server_requests: &[RuntimeServerRequest],
+    full: bool,
+) -> Timeline {
+    let thread = thread_response.get("thread").unwrap_or(thread_response);
+    let mut approvals = server_requests
+        .iter()
+        .map(|request| {
+            let turn_id = request
+                .params
+                .as_ref()
+                .and_then(|params| params.get("turnId"))
+                .and_then(Value::as_str)
+                .map(ToOwned::to_owned);
+            (turn_id, timeline_approval(request))
+        })
+        .collect::<Vec<_>>();
+
+    let raw_turns = thread
+        .get("turns")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    let omitted_turns = if full {
+        0
+    } else {
+        raw_turns.len().saturating_sub(SUMMARY_TURN_LIMIT)
+    };
+    let turns = raw_turns
+        .iter()
+        .skip(omitted_turns)
+        .map(|turn| {
+            let id = string_field(turn, "id").unwrap_or_else(|| "-".to_string());
+            let (matched, rest) = approvals
+                .drain(..)
+                .partition::<Vec<_>, _>(|(turn_id, _)| turn_id.as_deref() == Some(id.as_str()));
+            approvals = rest;
+            TimelineTurn {
+                status: turn
+                    .get("status")
+                    .and_then(status_text)
+                    .unwrap_or_else(|| "-".to_string()),
+                error: turn
+                    .get("error")
+                    .and_then(|error| error.get("message").or(Some(error)))
+                    .and_then(Value::as_str)
+                    .map(ToOwned::to_owned),
+                entries: turn
+                    .get("items")
+                    .and_then(Value::as_array)
+                    .map(|items| {
+                        items
+                            .iter()
+                            .filter_map(|item| timeline_entry(item, full))
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+                approvals: matched.into_iter().map(|(_, approval)| approval).collect(),
+                id,
+            }
+        })
+        .collect();
+
+    Timeline {
+        namespace: namespace.to_string(),
+        thread_id: string_field(thread, "id"),
+        status: thread.get("status").and_then(status_text),
+        cwd: string_field(thread, "cwd"),
+        omitted_turns,
+        turns,
+        unmatched_approvals: approvals
+            .into_iter()
+            .map(|(_, approval)| approval)
+            .collect(),
+    }
+}
+
+pub fn render_timeline(timeline: &Timeline, format: TimelineFormat) -> anyhow::Result<String> {
+    match format {
+        TimelineFormat::Json => {
+            serde_json::to_string_pretty(timeline).context("failed to encode timeline")
+        }
+        TimelineFormat::Html => Ok(render_html(timeline)),
+        TimelineFormat::Markdown | TimelineFormat::Text => Ok(render_markdown(timeline)),
+    }
+}
+
+fn timeline_entry(item: &Value, full: bool) -> Option<TimelineEntry> {
+    let item_type = item.get("type").and_then(Value::as_str).unwrap_or_default();
+    let entry = match item_type {
+        "userMessage" => TimelineEntry::UserInput {
+            text: item
+                .get("content")
+                .and_then(Value::as_array)
+                .map(|content| {
+                    content
+                        .iter()
+                        .filter_map(user_input_text)
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                })
+                .unwrap_or_default(),
+        },
+        "agentMessage" => TimelineEntry::AgentMessage {
+            text: string_field(item, "text").unwrap_or_default(),
+        },
+        "reasoning" => {
+            let summary = string_list(item.get("summary"));
+            if summary.is_empty() {
+                return None;
+            }
+            TimelineEntry::Reasoning { summary }
+        }
+        "plan" => TimelineEntry::Plan {
+            text: string_field(item, "text").unwrap_or_default(),
+        },
+        "commandExecution" => TimelineEntry::Command {
+            command: string_field(item, "command").unwrap_or_default(),
+            cwd: string_field(item, "cwd"),
+            status: string_field(item, "status").unwrap_or_else(|| "-".to_string()),
+            exit_code: item.get("exitCode").and_then(Value::as_i64),
+            duration_ms: item.get("durationMs").and_then(Value::as_u64),
+            output: string_field(item, "aggregatedOutput")
+                .filter(|output| !output.trim().is_empty())
+                .map(|output| if full { output } else { tail_lines(&output) }),
+        },
+        "fileChange" => TimelineEntry::FileChange {
+            status: string_field(item, "status").unwrap_or_else(|| "-".to_string()),
+            changes: item
+                .get("changes")
+                .and_then(Value::as_array)
+                .map(|changes| changes.iter().map(file_change).collect())
+                .unwrap_or_default(),
+        },
+        "mcpToolCall" | "dynamicToolCall" | "collabAgentToolCall" | "webSearch" => {
+            TimelineEntry::ToolCall {
+                name: tool_name(item, item_type),
+                status: string_field(item, "status").unwrap_or_else(|| "-".to_string()),
+            }
+        }
+        "" => return None,
+        other => TimelineEntry::Other {
+            item_type: other.to_string(),
+        },
+    };
+    Some(entry)
+}
+
+fn user_input_text(input: &Value) -> Option<String> {
+    match input.get("type").and_then(Value::as_str) {
+        Some("text") => string_field(input, "text"),
+        Some("image") => string_field(input, "url").map(|url| format!("[image] {url}")),
+        Some("localImage") => string_field(input, "path").map(|path| format!("[image] {path}")),
+        Some("skill") | Some("mention") => {
+            string_field(input, "name").map(|name| format!("[{name}]"))
+        }
+        _ => None,
+    }
+}
+
+fn file_change(change: &Value) -> TimelineFileChange {
+    let kind = change.get("kind");
+    let label = kind
+        .and_then(|kind| kind.get("type").or(Some(kind)))
+        .and_then(Value::as_str)
+        .unwrap_or("update");
+    let path = string_field(change, "path").unwrap_or_else(|| "-".to_string());
+    let change_label = match kind
+        .and_then(|kind| kind.get("move_path").or_else(|| kind.get("movePath")))
+        .and_then(Value::as_str)
+    {
+        Some(destination) => format!("{label} -> {destination}"),
+        None => label.to_string(),
+    };
+    TimelineFileChange {
+        path,
+        change: change_label,
+        diff: string_field(change, "diff").filter(|diff| !diff.trim().is_empty()),
+    }
+}
+
+fn tool_name(item: &Value, item_type: &str) -> String {
+    match item_type {
+        "mcpToolCall" => format!(
+            "{}/{}",
+            string_field(item, "server").unwrap_or_else(|| "-".to_string()),
+            string_field(item, "tool").unwrap_or_else(|| "-".to_string())
+        ),
+        "webSearch" => format!(
+            "web search: {}",
+            string_field(item, "query").unwrap_or_default()
+        ),
+        _ => string_field(item, "tool").unwrap_or_else(|| item_type.to_string()),
+    }
+}
+
+fn timeline_approval(request: &RuntimeServerRequest) -> TimelineApproval {
+    let params = request.params.as_ref();
+    let subject = params.and_then(|params| {
+        match params.get("command") {
+            Some(Value::String(command)) => Some(command.clone()),
+            Some(Value::Array(parts)) => Some(
+                parts
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            ),
+            _ => None,
+        }
+        .or_else(|| string_field(params, "grantRoot"))
+        .or_else(|| string_field(params, "reason"))
+    });
+    TimelineApproval {
+        id: request.id.clone(),
+        method: request.method.clone(),
+        status: request.status.clone(),
+        decision: request
+            .response
+            .as_ref()
+            .and_then(|response| response.get("decision"))
+            .and_then(|decision| {
+                decision
+                    .as_str()
+                    .map(ToOwned::to_owned)
+                    .or_else(|| serde_json::to_string(decision).ok())
+            }),
+        detail: request.detail.clone().or_else(|| request.error.clone()),
+        subject,
+    }
+}
+
+fn render_markdown(timeline: &Timeline) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "# Codex timeline: {}\n\n- thread: `{}`\n- status: {}",
+        timeline.namespace,
+        timeline.thread_id.as_deref().unwrap_or("-"),
+        timeline.status.as_deref().unwrap_or("-")
+    );
+    if let Some(cwd) = timeline.cwd.as_deref() {
+        let _ = writeln!(out, "- cwd: `{cwd}`");
+    }
+    if timeline.omitted_turns > 0 {
+        let _ = writeln!(
+            out,
+            "- {} earlier turns omitted (use --full)",
+            timeline.omitted_turns
+        );
+    }
+    for (index, turn) in timeline.turns.iter().enumerate() {
+        let _ = writeln!(
+            out,
+            "\n## Turn {} `{}` ({})",
+            timeline.omitted_turns + index + 1,
+            turn.id,
+            turn.status
+        );
+        if let Some(error) = turn.error.as_deref() {
+            let _ = writeln!(out, "\n> error: {error}");
+        }
+        for entry in &turn.entries {
+            out.push('\n');
+            render_markdown_entry(&mut out, entry);
+        }
+        if !turn.approvals.is_empty() {
+            out.push_str("\n**Approvals**\n\n");
+            render_markdown_approvals(&mut out, &turn.approvals);
+        }
+    }
+    if !timeline.unmatched_approvals.is_empty() {
+        out.push_str("\n## Other approvals\n\n");
+        render_markdown_approvals(&mut out, &timeline.unmatched_approvals);
+    }
+    out.trim_end().to_string()
+}
+
+fn render_markdown_entry(out: &mut String, entry: &TimelineEntry) {
+    match entry {
+        TimelineEntry::UserInput { text } => {
+            let _ = writeln!(out, "**User**\n\n{}", quote(text));
+        }
+        TimelineEntry::AgentMessage { text } => {
+            let _ = writeln!(out, "**Agent**\n\n{}", text.trim());
+        }
+        TimelineEntry::Reasoning { summary } => {
+            let _ = writeln!(out, "**Reasoning**\n");
+            for line in summary {
+                let _ = writeln!(out, "- {}", line.trim());
+            }
+        }
+        TimelineEntry::Plan { text } => {
+            let _ = writeln!(out, "**Plan**\n\n{}", text.trim());
+        }
+        TimelineEntry::Command {
+            command,
+            cwd,
+            status,
+            exit_code,
+            duration_ms,
+            output,
+        } => {
+            let _ = writeln!(
+                out,
+                "**Command** `{}` — {}{}{}",
+                command.replace('`', "'"),
+                status,
+                exit_code
+                    .map(|code| format!(", exit {code}"))
+                    .unwrap_or_default(),
+                duration_ms
+                    .map(|ms| format!(", {ms} ms"))
+                    .unwrap_or_default()
+            );
+            if let Some(cwd) = cwd {
+                let _ = writeln!(out, "\ncwd: `{cwd}`");
+            }
+            if let Some(output) = output {
+                let _ = writeln!(out, "\n{}", fenced(output, "text"));
+            }
+        }
+        TimelineEntry::FileChange { status, changes } => {
+            let _ = writeln!(out, "**File changes** — {status}");
+            for change in changes {
+                let _ = writeln!(out, "\n- `{}` ({})", change.path, change.change);
+                if let Some(diff) = change.diff.as_deref() {
+                    let _ = writeln!(out, "\n{}", fenced(diff, "diff"));
+                }
+            }
+        }
+        TimelineEntry::ToolCall { name, status } => {
+            let _ = writeln!(out, "**Tool** {name} — {status}");
+        }
+        TimelineEntry::Other { item_type } => {
+            let _ = writeln!(out, "_{item_type}_");
+        }
+    }
+}
+
+fn render_markdown_approvals(out: &mut String, approvals: &[TimelineApproval]) {
+    for approval in approvals {
+        let _ = writeln!(
+            out,
+            "- `{}` {} — {}{}{}",
+            approval.method,
+            approval
+                .subject
+                .as_deref()
+                .map(|subject| format!("`{}`", subject.replace('`', "'")))
+                .unwrap_or_default(),
+            approval.status,
+            approval
+                .decision
+                .as_deref()
+                .map(|decision| format!(" ({decision})"))
+                .unwrap_or_default(),
+            approval
+                .detail
+                .as_deref()
+                .map(|detail| format!(": {detail}"))
+                .unwrap_or_default()
+        );
+    }
+}
+
+fn render_html(timeline: &Timeline) -> String {
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        "<!doctype html>\n<html><head><meta charset=\"utf-8\"><title>Codex timeline: {0}</title>\
+         <style>body{{font-family:sans-serif;max-width:960px;margin:auto}}\
+         pre{{background:#f4f4f4;padding:8px;overflow:auto}}\
+         .turn{{border-top:1px solid #ccc;margin-top:16px}}</style></head><body>\n\
+         <h1>Codex timeline: {0}</h1>\n<p>thread <code>{1}</code> &middot; {2}</p>\n",
+        escape_html(&timeline.namespace),
+        escape_html(timeline.thread_id.as_deref().unwrap_or("-")),
+        escape_html(timeline.status.as_deref().unwrap_or("-"))
+    );
+    if timeline.omitted_turns > 0 {
+        let _ = writeln!(
+            out,
+            "<p>{} earlier turns omitted (use --full)</p>",
+            timeline.omitted_turns
+        );
+    }
+    for (index, turn) in timeline.turns.iter().enumerate() {
+        let _ = writeln!(
+            out,
+            "<section class=\"turn\"><h2>Turn {} <code>{}</code> ({})</h2>",
+            timeline.omitted_turns + index + 1,
+            escape_html(&turn.id),
+            escape_html(&turn.status)
+        );
+        if let Some(error) = turn.error.as_deref() {
+            let _ = writeln!(out, "<p><strong>error:</strong> {}</p>", escape_html(error));
+        }
+        for entry in &turn.entries {
+            render_html_entry(&mut out, entry);
+        }
+        if !turn.approvals.is_empty() {
+            out.push_str("<h3>Approvals</h3>\n");
+            render_html_approvals(&mut out, &turn.approvals);
+        }
+        out.push_str("</section>\n");
+    }
+    if !timeline.unmatched_approvals.is_empty() {
+        out.push_str("<h2>Other approvals</h2>\n");
+        render_html_approvals(&mut out, &timeline.unmatched_approvals);
+    }
+    out.push_str("</body></html>");
+    out
+}
+
+fn render_html_entry(out: &mut String, entry: &TimelineEntry) {
+    match entry {
+        TimelineEntry::UserInput { text } => {
+            let _ = writeln!(
+                out,
+                "<h3>User</h3><blockquote><pre>{}</pre></blockquote>",
+                escape_html(text)
+            );
+        }
+        TimelineEntry::AgentMessage { text } => {
+            let _ = writeln!(out, "<h3>Agent</h3><pre>{}</pre>", escape_html(text));
+        }
+        TimelineEntry::Reasoning { summary } => {
+            out.push_str("<h3>Reasoning</h3><ul>");
+            for line in summary {
+                let _ = write!(out, "<li>{}</li>", escape_html(line));
+            }
+            out.push_str("</ul>\n");
+        }
+        TimelineEntry::Plan { text } => {
+            let _ = writeln!(out, "<h3>Plan</h3><pre>{}</pre>", escape_html(text));
+        }
+        TimelineEntry::Command {
+            command,
+            cwd,
+            status,
+            exit_code,
+            duration_ms,
+            output,
+        } => {
+            let _ = writeln!(
+                out,
+                "<h3>Command</h3><p><code>{}</code> &mdash; {}{}{}{}</p>",
+                escape_html(command),
+                escape_html(status),
+                exit_code
+                    .map(|code| format!(", exit {code}"))
+                    .unwrap_or_default(),
+                duration_ms
+                    .map(|ms| format!(", {ms} ms"))
+                    .unwrap_or_default(),
+                cwd.as_deref()
+                    .map(|cwd| format!(" in <code>{}</code>", escape_html(cwd)))
+                    .unwrap_or_default()
+            );
+            if let Some(output) = output {
+                let _ = writeln!(out, "<pre>{}</pre>", escape_html(output));
+            }
+        }
+        TimelineEntry::FileChange { status, changes } => {
+            let _ = writeln!(out, "<h3>File changes &mdash; {}</h3>", escape_html(status));
+            for change in changes {
+                let _ = writeln!(
+                    out,
+                    "<p><code>{}</code> ({})</p>",
+                    escape_html(&change.path),
+                    escape_html(&change.change)
+                );
+                if let Some(diff) = change.diff.as_deref() {
+                    let _ = writeln!(out, "<pre>{}</pre>", escape_html(diff));
+                }
+            }
+        }
+        TimelineEntry::ToolCall { name, status } => {
+            let _ = writeln!(
+                out,
+                "<p><strong>Tool</strong> {} &mdash; {}</p>",
+                escape_html(name),
+                escape_html(status)
+            );
+        }
+        TimelineEntry::Other { item_type } => {
+            let _ = writeln!(out, "<p><em>{}</em></p>", escape_html(item_type));
+        }
+    }
+}
+
+fn render_html_approvals(out: &mut String, approvals: &[TimelineApproval]) {
+    out.push_str("<ul>");
+    for approval in approvals {
+        let _ = write!(
+            out,
+            "<li><code>{}</code> {} &mdash; {}{}{}</li>",
+            escape_html(&approval.method),
+            approval
+                .subject
+                .as_deref()
+                .map(|subject| format!("<code>{}</code>", escape_html(subject)))
+                .unwrap_or_default(),
+            escape_html(&approval.status),
+            approval
+                .decision
+                .as_deref()
+                .map(|decision| format!(" ({})", escape_html(decision)))
+                .unwrap_or_default(),
+            approval
+                .detail
+                .as_deref()
+                .map(|detail| format!(": {}", escape_html(detail)))
+                .unwrap_or_default()
+        );
+    }
+    out.push_str("</ul>\n");
+}
+
+fn fenced(body: &str, language: &str) -> String {
+    let mut fence = "```".to_string();
+    while body.contains(&fence) {
+        fence.push('`');
+    }
+    format!("{fence}{language}\n{}\n{fence}", body.trim_end())
+}
+
+fn quote(text: &str) -> String {
+    text.trim()
+        .lines()
+        .map(|line| format!("> {line}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn escape_html(raw: &str) -> String {
+    raw.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn tail_lines(output: &str) -> String {
+    let lines = output.trim_end().lines().collect::<Vec<_>>();
+    if lines.len() <= SUMMARY_OUTPUT_LINES {
+        return lines.join("\n");
+    }
+    format!(
+        "... {} lines omitted\n{}",
+        lines.len() - SUMMARY_OUTPUT_LINES,
+        lines[lines.len() - SUMMARY_OUTPUT_LINES..].join("\n")
+    )
+}
+
+fn status_text(value: &Value) -> Option<String> {
+    value
+        .as_str()
+        .or_else(|| value.get("type").and_then(Value::as_str))
+        .map(ToOwned::to_owned)
+}
+
+fn string_field(value: &Value, key: &str) -> Option<String> {
+    value
+        .get(key)
+        .and_then(Value::as_str)
+        .map(ToOwned::to_owned)
+}
+
+fn string_list(value: Option<&Value>) -> Vec<String> {
+    value
+        .and_then(Value::as_array)
+        .map(|values| {
+            values
+                .iter()
+                .filter_map(|value| value.as_str().or_else(|| value.get("text")?.as_str()))
+                .filter(|value| !value.trim().is_empty())
+                .map(ToOwned::to_owned)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn is_zero(value: &usize) -> bool {
+    *value == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn timeline_renders_commands_diffs_and_turn_approvals() {
+        let thread = json!({
+            "thread": {
+                "id": "thr-1",
+                "status": {"type": "idle"},
+                "turns": [{
+                    "id": "turn-1",
+                    "status": "completed",
+                    "items": [
+                        {"type": "userMessage", "content": [{"type": "text", "text": "fix the test"}]},
+                        {"type": "reasoning", "summary": ["look at the failing assert"]},
+                        {"type": "commandExecution", "command": "cargo test", "status": "completed",
+                         "exitCode": 101, "aggregatedOutput": "test failed\n"},
+                        {"type": "fileChange", "status": "completed", "changes": [
+                            {"path": "src/lib.rs", "kind": {"type": "update"}, "diff": "-a\n+b"}
+                        ]},
+                        {"type": "agentMessage", "text": "Fixed."}
+                    ]
+                }]
+            }
+        });
+        let requests = vec![
+            RuntimeServerRequest {
+                id: "7".to_string(),
+                method: "item/commandExecution/requestApproval".to_string(),
+                status: "resolved".to_string(),
+                params: Some(json!({"turnId": "turn-1", "command": "cargo test"})),
+                response: Some(json!({"decision": "accept"})),
+                ..Default::default()
+            },
+            RuntimeServerRequest {
+                id: "8".to_string(),
+                method: "item/tool/requestUserInput".to_string(),
+                status: "pending".to_string(),
+                ..Default::default()
+            },
+        ];
+
+        let timeline = build_timeline("ns", &thread, &requests, true);
+        assert_eq!(timeline.status.as_deref(), Some("idle"));
+        assert_eq!(timeline.turns[0].entries.len(), 5);
+        assert_eq!(timeline.turns[0].approvals.len(), 1);
+        assert_eq!(timeline.unmatched_approvals.len(), 1);
+
+        let markdown = render_timeline(&timeline, TimelineFormat::Markdown).unwrap();
+        assert!(markdown.contains("**Command** `cargo test` — completed, exit 101"));
+        assert!(markdown.contains("```diff\n-a\n+b\n```"));
+        assert!(markdown.contains("`cargo test` — resolved (accept)"));
+
+        let html = render_timeline(&timeline, TimelineFormat::Html).unwrap();
+        assert!(html.contains("<code>src/lib.rs</code> (update)"));
+
+        let json = render_timeline(&timeline, TimelineFormat::Json).unwrap();
+        assert!(json.contains("\"kind\": \"file-change\""));
+    }
+}
@@ -0,0 +1,276 @@
+use anyhow::{Context, anyhow, bail, ensure};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+const TRIGGER_WINDOW_BYTES: usize = 8 * 1024;
+const DEFAULT_TRIGGER_COOLDOWN_MS: u64 = 30_000;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum OutputTriggerAction {
+    Notify,
+    Interrupt,
+    SendText,
+    OperatorRequest,
+    MarkIdle,
+}
+
+/// A regex evaluated against an agent's ANSI-stripped PTY output.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutputTriggerRule {
+    pub pattern: String,
+    pub action: OutputTriggerAction,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<String>,
+    #[serde(default = "default_trigger_cooldown_ms", rename = "cooldownMs")]
+    pub cooldown_ms: u64,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct OutputTriggerFile {
+    #[serde(default)]
+    triggers: Vec<OutputTriggerRule>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TriggerHit {
+    pub namespace: String,
+    pub agent: String,
+    pub pattern: String,
+    pub action: OutputTriggerAction,
+    pub text: Option<String>,
+    pub matched: String,
+}
+
+fn default_trigger_cooldown_ms() -> u64 {
+    DEFAULT_TRIGGER_COOLDOWN_MS
+}
+
+impl OutputTriggerRule {
+    /// Parses `PATTERN=action`, where action is `notify`, `interrupt`, `send-text:TEXT`,
+    /// `operator-request`, or `mark-idle`. The last `=` that starts a valid action wins.
+    pub fn parse_spec(spec: &str) -> anyhow::Result<Self> {
+        for (index, _) in spec.rmatch_indices('=') {
+            let (pattern, action) = (&spec[..index], &spec[index + 1..]);
+            let Some((action, text)) = parse_action(action) else {
+                continue;
+            };
+            let rule = Self {
+                pattern: pattern.to_string(),
+                action,
+                text,
+                agent: None,
+                cooldown_ms: DEFAULT_TRIGGER_COOLDOWN_MS,
+            };
+            rule.validate()?;
+            return Ok(rule);
+        }
+        bail!(
+            "invalid --on-output '{}': expected PATTERN=notify|interrupt|send-text:TEXT|operator-request|mark-idle",
+            spec
+        )
+    }
+
+    fn validate(&self) -> anyhow::Result<()> {
+        ensure!(!self.pattern.is_empty(), "output trigger pattern is empty");
+        Regex::new(&self.pattern)
+            .with_context(|| format!("invalid output trigger pattern '{}'", self.pattern))?;
+        if self.action == OutputTriggerAction::SendText {
+            ensure!(
+                self.text.as_deref().is_some_and(|text| !text.is_empty()),
+                "send-text trigger '{}' needs text",
+                self.pattern
+            );
+        }
+        Ok(())
+    }
+}
+
+fn parse_action(raw: &str) -> Option<(OutputTriggerAction, Option<String>)> {
+    if let Some(text) = raw.strip_prefix("send-text:") {
+        return Some((OutputTriggerAction::SendText, Some(text.to_string())));
+    }
+    let action = match raw {
+        "notify" => OutputTriggerAction::Notify,
+        "interrupt" => OutputTriggerAction::Interrupt,
+        "operator-request" => OutputTriggerAction::OperatorRequest,
+        "mark-idle" => OutputTriggerAction::MarkIdle,
+        _ => return None,
+    };
+    Some((action, None))
+}
+
+/// Loads `triggers:` rules from a YAML file.
+pub fn load_trigger_file(path: &Path) -> anyhow::Result<Vec<OutputTriggerRule>> {
+    let raw = fs::read_to_string(path)
+        .with_context(|| format!("failed to read trigger file '{}'", path.display()))?;
+    let file: OutputTriggerFile = serde_yaml::from_str(&raw)
+        .with_context(|| format!("failed to parse trigger file '{}'", path.display()))?;
+    for rule in &file.triggers {
+        rule.validate()
+            .with_context(|| format!("invalid trigger in '{}'", path.display()))?;
+    }
+    Ok(file.triggers)
+}
+
+#[derive(Clone, Default)]
+struct StrippedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl Write for StrippedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+struct CompiledTrigger {
+    rule: OutputTriggerRule,
+    regex: Regex,
+    scan_from: usize,
+    last_fired: Option<Instant>,
+}
+
+/// Per-agent trigger state: strips escape sequences across chunk boundaries and reports
+/// each new match once, subject to the rule cooldown.
+pub struct OutputTriggerSet {
+    namespace: String,
+    agent: String,
+    triggers: Vec<CompiledTrigger>,
+    stripper: strip_ansi_escapes::Writer<StrippedBuffer>,
+    stripped: StrippedBuffer,
+    window: String,
+}
+
+impl OutputTriggerSet {
+    pub fn for_agent(
+        rules: &[OutputTriggerRule],
+        namespace: &str,
+        agent: &str,
+    ) -> anyhow::Result<Option<Self>> {
+        let mut triggers = Vec::new();
+        for rule in rules {
+            if rule.agent.as_deref().is_some_and(|target| target != agent) {
+                continue;
+            }
+            let regex = Regex::new(&rule.pattern)
+                .map_err(|error| anyhow!("invalid output trigger '{}': {}", rule.pattern, error))?;
+            triggers.push(CompiledTrigger {
+                rule: rule.clone(),
+                regex,
+                scan_from: 0,
+                last_fired: None,
+            });
+        }
+        if triggers.is_empty() {
+            return Ok(None);
+        }
+        let stripped = StrippedBuffer::default();
+        Ok(Some(Self {
+            namespace: namespace.to_string(),
+            agent: agent.to_string(),
+            triggers,
+            stripper: strip_ansi_escapes::Writer::new(stripped.clone()),
+            stripped,
+            window: String::new(),
+        }))
+    }
+
+    pub fn observe(&mut self, chunk: &[u8]) -> Vec<TriggerHit> {
+        if self.stripper.write_all(chunk).is_err() || self.stripper.flush().is_err() {
+            return Vec::new();
+        }
+        let text = std::mem::take(&mut *self.stripped.0.lock().unwrap());
+        if text.is_empty() {
+            return Vec::new();
+        }
+        self.window.push_str(&String::from_utf8_lossy(&text));
+        self.trim_window();
+
+        let now = Instant::now();
+        let mut hits = Vec::new();
+        for trigger in &mut self.triggers {
+            let Some(found) = trigger.regex.find_at(&self.window, trigger.scan_from) else {
+                continue;
+            };
+            trigger.scan_from = found.end();
+            let cooling = trigger.last_fired.is_some_and(|fired| {
+                now.duration_since(fired) < Duration::from_millis(trigger.rule.cooldown_ms)
+            });
+            if cooling {
+                continue;
+            }
+            trigger.last_fired = Some(now);
+            hits.push(TriggerHit {
+                namespace: self.namespace.clone(),
+                agent: self.agent.clone(),
+                pattern: trigger.rule.pattern.clone(),
+                action: trigger.rule.action,
+                text: trigger.rule.text.clone(),
+                matched: found.as_str().trim().to_string(),
+            });
+        }
+        hits
+    }
+
+    fn trim_window(&mut self) {
+        if self.window.len() <= TRIGGER_WINDOW_BYTES {
+            return;
+        }
+        let mut cut = self.window.len() - TRIGGER_WINDOW_BYTES;
+        while !self.window.is_char_boundary(cut) {
+            cut += 1;
+        }
+        self.window.drain(..cut);
+        for trigger in &mut self.triggers {
+            trigger.scan_from = trigger.scan_from.saturating_sub(cut);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trigger_spec_parses_actions_and_matches_stripped_output_once() {
+        let rule = OutputTriggerRule::parse_spec("a=b=send-text:y=1").unwrap();
+        assert_eq!(
+            (rule.pattern.as_str(), rule.action, rule.text.as_deref()),
+            ("a=b", OutputTriggerAction::SendText, Some("y=1"))
+        );
+        assert!(OutputTriggerRule::parse_spec("oops=reboot").is_err());
+        assert!(OutputTriggerRule::parse_spec("(=notify").is_err());
+
+        let rules = vec![
+            OutputTriggerRule::parse_spec("Press any key=send-text: ").unwrap(),
+            OutputTriggerRule {
+                cooldown_ms: 0,
+                ..OutputTriggerRule::parse_spec(r"Error: rate limited=notify").unwrap()
+            },
+        ];
+        let mut set = OutputTriggerSet::for_agent(&rules, "ops", "agent0")
+            .unwrap()
+            .expect("rules apply to agent0");
+        assert!(set.observe(b"\x1b[31mError: rate").is_empty());
+        let hits = set.observe(b" limited\x1b[0m\r\n");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].matched, "Error: rate limited");
+        assert!(set.observe(b"still waiting\r\n").is_empty());
+        assert_eq!(set.observe(b"Error: rate limited\r\n").len(), 1);
+        assert_eq!(
+            set.observe(b"Press \x1b[1many\x1b[0m key")[0].action,
+            OutputTriggerAction::SendText
+        );
+    }
+}
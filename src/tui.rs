@@ -24,8 +24,9 @@ use crate::codex::enrich_native_sessions;
 use crate::codex_app::collect_codex_app_sessions;
 use crate::native::{
     NativeSessionMetadata, RuntimeContextMetadata, RuntimeFeedEntry, RuntimeSubagentMetadata,
-    collect_native_sessions,
+    collect_native_sessions, compact_token_count,
 };
+use crate::usage::budget_label;
 use crate::{SessionBackend, delete_session, exec_agent, interrupt_agent};
 
 const BG: Color = Color::Reset;
@@ -237,13 +238,23 @@ pub fn run_dashboard(backend: SessionBackend, refresh_ms: u64) -> anyhow::Result
                 KeyCode::Enter => {
                     if let Some(row) = app.selected_row().cloned() {
                         suspend_terminal(&mut terminal)?;
-                        let action = exec_agent(app.backend, &row.namespace, &row.agent);
+                        let action = exec_agent(app.backend, &row.namespace, &row.agent, false);
                         resume_terminal(&mut terminal)?;
                         action.map_err(anyhow::Error::from)?;
                         app.refresh()?;
                         app.status = format!("returned from {}:{}", row.namespace, row.agent);
                     }
                 }
+                KeyCode::Char('v') => {
+                    if let Some(row) = app.selected_row().cloned() {
+                        suspend_terminal(&mut terminal)?;
+                        let action = exec_agent(app.backend, &row.namespace, &row.agent, true);
+                        resume_terminal(&mut terminal)?;
+                        action.map_err(anyhow::Error::from)?;
+                        app.refresh()?;
+                        app.status = format!("stopped watching {}:{}", row.namespace, row.agent);
+                    }
+                }
                 KeyCode::Char('t') => {
                     if let Some(row) = app.selected_row().cloned() {
                         if let Some(transcript_path) = row
@@ -476,8 +487,9 @@ fn render_dashboard_footer(frame: &mut Frame, area: ratatui::layout::Rect, app:
         " enter attach ",
         Color::Black,
         Color::Cyan,
-        PL_A,
+        PL_C,
     );
+    push_powerline_segment(&mut spans, " v watch ", Color::White, PL_C, PL_A);
     push_powerline_segment(&mut spans, " i interrupt ", Color::White, PL_A, PL_B);
     push_powerline_segment(&mut spans, " x close ", Color::White, PL_B, PL_D);
     push_powerline_segment(&mut spans, " t transcript ", Color::White, PL_D, PL_C);
@@ -694,6 +706,28 @@ fn render_runtime_overview_card(
                 Span::styled(truncate_for_cell(goal, 160), Style::default().fg(TEXT)),
             ]));
         }
+        if let Some(usage) = context.token_usage {
+            let turn = context
+                .turn_token_usage
+                .map(|turn| format!("  turn {}", compact_token_count(turn.total_tokens)))
+                .unwrap_or_default();
+            let budget = budget_label(usage.total_tokens, context.token_budget);
+            let over_budget = context
+                .token_budget
+                .is_some_and(|limit| usage.total_tokens > limit);
+            lines.push(Line::from(vec![
+                Span::styled("Tokens: ", Style::default().fg(SUBTLE_TEXT)),
+                Span::styled(
+                    format!("{}{}", compact_token_count(usage.total_tokens), turn),
+                    Style::default().fg(TEXT),
+                ),
+                Span::styled("  budget ", Style::default().fg(SUBTLE_TEXT)),
+                Span::styled(
+                    budget,
+                    status_style(if over_budget { "failed" } else { "active" }),
+                ),
+            ]));
+        }
         if let Some(memory_mode) = context.memory_mode.as_deref() {
             let remote = context
                 .remote_control_status
@@ -0,0 +1,264 @@
+use crate::control_plane::ControlPlaneOutput;
+use crate::mission::{MissionRecord, jarvis_codex_dir};
+use crate::native::{RuntimeTokenUsage, compact_token_count};
+use anyhow::Context;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+const USAGE_LEDGER_FILE_NAME: &str = "usage.jsonl";
+
+/// One completed Codex turn, appended to the usage ledger so totals survive the session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenUsageRecord {
+    pub timestamp_epoch_ms: u128,
+    pub namespace: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ticket: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thread_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub turn_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub turn_status: Option<String>,
+    pub usage: RuntimeTokenUsage,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budget: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TokenUsageRollup {
+    pub key: String,
+    pub turns: usize,
+    pub usage: RuntimeTokenUsage,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub budget: Option<u64>,
+    pub over_budget: bool,
+    pub namespaces: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, ValueEnum)]
+pub enum TokenUsageGrouping {
+    Ticket,
+    Namespace,
+}
+
+pub fn append_usage_record(record: &TokenUsageRecord) -> anyhow::Result<()> {
+    let path = usage_ledger_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create '{}'", parent.display()))?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("failed to open usage ledger '{}'", path.display()))?;
+    let line = serde_json::to_string(record).context("failed to encode usage record")?;
+    writeln!(file, "{}", line)
+        .with_context(|| format!("failed to append usage ledger '{}'", path.display()))
+}
+
+pub fn load_usage_records() -> anyhow::Result<Vec<TokenUsageRecord>> {
+    let path = usage_ledger_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let raw = fs::read_to_string(&path)
+        .with_context(|| format!("failed to read usage ledger '{}'", path.display()))?;
+    Ok(raw
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Keeps the records that belong to a ticket note, matching by path or ticket id.
+pub fn records_for_ticket(records: Vec<TokenUsageRecord>, ticket: &str) -> Vec<TokenUsageRecord> {
+    let wanted = canonical_ticket(ticket);
+    records
+        .into_iter()
+        .filter(|record| {
+            record.task_id.as_deref() == Some(ticket)
+                || record
+                    .ticket
+                    .as_deref()
+                    .is_some_and(|path| canonical_ticket(path) == wanted)
+        })
+        .collect()
+}
+
+/// Keeps the records linked to a mission through its tickets or namespaces.
+pub fn records_for_mission(
+    records: Vec<TokenUsageRecord>,
+    mission: &MissionRecord,
+) -> Vec<TokenUsageRecord> {
+    let tickets = mission
+        .tickets
+        .iter()
+        .map(|ticket| canonical_ticket(ticket))
+        .collect::<Vec<_>>();
+    records
+        .into_iter()
+        .filter(|record| {
+            mission.namespaces.contains(&record.namespace)
+                || record
+                    .ticket
+                    .as_deref()
+                    .is_some_and(|path| tickets.contains(&canonical_ticket(path)))
+        })
+        .collect()
+}
+
+pub fn rollup_usage(
+    records: &[TokenUsageRecord],
+    grouping: TokenUsageGrouping,
+) -> Vec<TokenUsageRollup> {
+    let mut rollups = BTreeMap::<String, TokenUsageRollup>::new();
+    for record in records {
+        let key = match grouping {
+            TokenUsageGrouping::Ticket => record
+                .ticket
+                .clone()
+                .unwrap_or_else(|| record.namespace.clone()),
+            TokenUsageGrouping::Namespace => record.namespace.clone(),
+        };
+        let rollup = rollups
+            .entry(key.clone())
+            .or_insert_with(|| TokenUsageRollup {
+                key,
+                ..TokenUsageRollup::default()
+            });
+        rollup.turns += 1;
+        rollup.usage.add(&record.usage);
+        if record.budget.is_some() {
+            rollup.budget = record.budget;
+        }
+        if !rollup.namespaces.contains(&record.namespace) {
+            rollup.namespaces.push(record.namespace.clone());
+        }
+    }
+    rollups
+        .into_values()
+        .map(|rollup| TokenUsageRollup {
+            over_budget: rollup
+                .budget
+                .is_some_and(|budget| rollup.usage.total_tokens > budget),
+            ..rollup
+        })
+        .collect()
+}
+
+pub fn total_usage(records: &[TokenUsageRecord]) -> RuntimeTokenUsage {
+    let mut total = RuntimeTokenUsage::default();
+    for record in records {
+        total.add(&record.usage);
+    }
+    total
+}
+
+pub fn render_usage_output(
+    rollups: &[TokenUsageRollup],
+    output: ControlPlaneOutput,
+) -> anyhow::Result<String> {
+    match output {
+        ControlPlaneOutput::Json => {
+            serde_json::to_string_pretty(rollups).context("failed to encode token usage")
+        }
+        ControlPlaneOutput::Yaml => {
+            serde_yaml::to_string(rollups).context("failed to encode token usage")
+        }
+        ControlPlaneOutput::Table => Ok(render_usage_table(rollups)),
+    }
+}
+
+fn render_usage_table(rollups: &[TokenUsageRollup]) -> String {
+    let mut lines = vec!["KEY\tTURNS\tINPUT\tCACHED\tOUTPUT\tREASONING\tTOTAL\tBUDGET".to_string()];
+    for rollup in rollups {
+        lines.push(format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            rollup.key,
+            rollup.turns,
+            compact_token_count(rollup.usage.input_tokens),
+            compact_token_count(rollup.usage.cached_input_tokens),
+            compact_token_count(rollup.usage.output_tokens),
+            compact_token_count(rollup.usage.reasoning_output_tokens),
+            compact_token_count(rollup.usage.total_tokens),
+            budget_label(rollup.usage.total_tokens, rollup.budget)
+        ));
+    }
+    lines.join("\n")
+}
+
+/// `42% of 200k`, or `over 200k` once the budget is exceeded.
+pub fn budget_label(total_tokens: u64, budget: Option<u64>) -> String {
+    match budget {
+        Some(budget) if total_tokens > budget => {
+            format!("over {}", compact_token_count(budget))
+        }
+        Some(budget) if budget > 0 => format!(
+            "{}% of {}",
+            total_tokens * 100 / budget,
+            compact_token_count(budget)
+        ),
+        _ => "-".to_string(),
+    }
+}
+
+fn canonical_ticket(path: &str) -> PathBuf {
+    fs::canonicalize(Path::new(path)).unwrap_or_else(|_| PathBuf::from(path.trim()))
+}
+
+fn usage_ledger_path() -> anyhow::Result<PathBuf> {
+    Ok(jarvis_codex_dir()?.join(USAGE_LEDGER_FILE_NAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(namespace: &str, ticket: &str, total: u64) -> TokenUsageRecord {
+        TokenUsageRecord {
+            timestamp_epoch_ms: 1,
+            namespace: namespace.to_string(),
+            ticket: Some(ticket.to_string()),
+            task_id: None,
+            thread_id: None,
+            turn_id: None,
+            turn_status: Some("completed".to_string()),
+            usage: RuntimeTokenUsage {
+                input_tokens: total - 10,
+                output_tokens: 10,
+                total_tokens: total,
+                ..RuntimeTokenUsage::default()
+            },
+            budget: Some(1_000),
+        }
+    }
+
+    #[test]
+    fn usage_rolls_up_per_ticket_and_mission_against_budget() {
+        let records = vec![
+            record("codex-a", "/tmp/a.md", 600),
+            record("codex-a-retry", "/tmp/a.md", 700),
+            record("codex-b", "/tmp/b.md", 100),
+        ];
+        let rollups = rollup_usage(&records, TokenUsageGrouping::Ticket);
+        assert_eq!(rollups.len(), 2);
+        assert_eq!(rollups[0].turns, 2);
+        assert_eq!(rollups[0].usage.total_tokens, 1_300);
+        assert!(rollups[0].over_budget);
+        assert_eq!(budget_label(100, Some(1_000)), "10% of 1.0k");
+
+        let mission = MissionRecord {
+            namespaces: vec!["codex-b".to_string()],
+            ..MissionRecord::default()
+        };
+        let linked = records_for_mission(records, &mission);
+        assert_eq!(total_usage(&linked).total_tokens, 100);
+    }
+}
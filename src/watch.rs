@@ -0,0 +1,245 @@
+use anyhow::Context;
+use std::collections::BTreeMap;
+use std::ffi::{CString, OsStr};
+use std::fs;
+use std::io;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+use std::os::unix::ffi::{OsStrExt, OsStringExt};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+const WATCH_MASK: u32 = libc::IN_CLOSE_WRITE
+    | libc::IN_MOVED_TO
+    | libc::IN_MOVED_FROM
+    | libc::IN_CREATE
+    | libc::IN_DELETE
+    | libc::IN_MODIFY;
+const SKIPPED_DIRS: &[&str] = &[".obsidian", ".git", ".trash"];
+const EVENT_HEADER_LEN: usize = std::mem::size_of::<libc::inotify_event>();
+
+/// Recursive inotify watch over a vault, reporting changed Markdown and JSON files.
+pub struct VaultWatcher {
+    fd: OwnedFd,
+    watches: BTreeMap<i32, PathBuf>,
+}
+
+impl VaultWatcher {
+    pub fn new(root: &Path) -> anyhow::Result<Self> {
+        let raw = unsafe { libc::inotify_init1(libc::IN_NONBLOCK | libc::IN_CLOEXEC) };
+        if raw < 0 {
+            return Err(io::Error::last_os_error()).context("inotify is unavailable");
+        }
+        let mut watcher = Self {
+            fd: unsafe { OwnedFd::from_raw_fd(raw) },
+            watches: BTreeMap::new(),
+        };
+        watcher.watch_tree(root)?;
+        Ok(watcher)
+    }
+
+    /// Blocks until a relevant change arrives or `timeout` passes, then keeps collecting
+    /// until the vault has been quiet for `debounce` so one save burst yields one batch.
+    pub fn wait(
+        &mut self,
+        timeout: Duration,
+        debounce: Duration,
+        max_batch: Duration,
+    ) -> anyhow::Result<Vec<PathBuf>> {
+        let deadline = Instant::now() + timeout;
+        let mut changed = Vec::new();
+        while changed.is_empty() {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Ok(changed);
+            }
+            changed = self.poll(remaining)?;
+        }
+        let started = Instant::now();
+        while started.elapsed() < max_batch {
+            let more = self.poll(debounce)?;
+            if more.is_empty() {
+                break;
+            }
+            changed.extend(more);
+        }
+        changed.sort();
+        changed.dedup();
+        Ok(changed)
+    }
+
+    fn poll(&mut self, timeout: Duration) -> anyhow::Result<Vec<PathBuf>> {
+        let mut poll_fd = libc::pollfd {
+            fd: self.fd.as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        let timeout_ms = i32::try_from(timeout.as_millis()).unwrap_or(i32::MAX);
+        let status = unsafe { libc::poll(&mut poll_fd, 1, timeout_ms) };
+        if status < 0 {
+            let error = io::Error::last_os_error();
+            if error.kind() == io::ErrorKind::Interrupted {
+                return Ok(Vec::new());
+            }
+            return Err(error).context("failed to poll inotify");
+        }
+        if status == 0 {
+            return Ok(Vec::new());
+        }
+
+        let mut changed = Vec::new();
+        let mut buffer = vec![0u8; 64 * 1024];
+        loop {
+            let read = unsafe {
+                libc::read(
+                    self.fd.as_raw_fd(),
+                    buffer.as_mut_ptr().cast(),
+                    buffer.len(),
+                )
+            };
+            if read < 0 {
+                let error = io::Error::last_os_error();
+                if error.kind() == io::ErrorKind::WouldBlock {
+                    break;
+                }
+                return Err(error).context("failed to read inotify events");
+            }
+            if read == 0 {
+                break;
+            }
+            self.parse_events(&buffer[..read as usize], &mut changed)?;
+        }
+        Ok(changed)
+    }
+
+    fn parse_events(&mut self, bytes: &[u8], changed: &mut Vec<PathBuf>) -> anyhow::Result<()> {
+        let mut offset = 0;
+        while offset + EVENT_HEADER_LEN <= bytes.len() {
+            let event = unsafe {
+                std::ptr::read_unaligned(bytes[offset..].as_ptr().cast::<libc::inotify_event>())
+            };
+            let name_start = offset + EVENT_HEADER_LEN;
+            let name_end = (name_start + event.len as usize).min(bytes.len());
+            let name = bytes[name_start..name_end]
+                .split(|byte| *byte == 0)
+                .next()
+                .unwrap_or_default();
+            offset = name_end;
+
+            if event.mask & libc::IN_Q_OVERFLOW != 0 {
+                changed.push(PathBuf::new());
+                continue;
+            }
+            if event.mask & libc::IN_IGNORED != 0 {
+                self.watches.remove(&event.wd);
+                continue;
+            }
+            let Some(dir) = self.watches.get(&event.wd) else {
+                continue;
+            };
+            let path = dir.join(OsStr::from_bytes(name));
+            if event.mask & libc::IN_ISDIR != 0 {
+                if event.mask & (libc::IN_CREATE | libc::IN_MOVED_TO) != 0 {
+                    self.watch_new_dir(&path);
+                }
+                continue;
+            }
+            if matches!(
+                path.extension().and_then(OsStr::to_str),
+                Some("md" | "json")
+            ) {
+                changed.push(path);
+            }
+        }
+        Ok(())
+    }
+
+    /// Watches a directory that appeared after startup. It may already be gone again, and a
+    /// full watch table should not take the whole watcher down, so neither is fatal here.
+    fn watch_new_dir(&mut self, dir: &Path) {
+        if let Err(error) = self.watch_tree(dir) {
+            let vanished = error
+                .downcast_ref::<io::Error>()
+                .is_some_and(|error| error.kind() == io::ErrorKind::NotFound);
+            if !vanished {
+                warn!("{error:#}");
+            }
+        }
+    }
+
+    fn watch_tree(&mut self, dir: &Path) -> anyhow::Result<()> {
+        let path = CString::new(dir.as_os_str().to_os_string().into_vec())
+            .with_context(|| format!("invalid watch path '{}'", dir.display()))?;
+        let wd = unsafe { libc::inotify_add_watch(self.fd.as_raw_fd(), path.as_ptr(), WATCH_MASK) };
+        if wd < 0 {
+            return Err(io::Error::last_os_error())
+                .with_context(|| format!("failed to watch '{}'", dir.display()));
+        }
+        self.watches.insert(wd, dir.to_path_buf());
+
+        let Ok(entries) = fs::read_dir(dir) else {
+            return Ok(());
+        };
+        for entry in entries.flatten() {
+            let skipped = entry
+                .file_name()
+                .to_str()
+                .is_some_and(|name| SKIPPED_DIRS.contains(&name));
+            if !skipped && entry.file_type().is_ok_and(|kind| kind.is_dir()) {
+                self.watch_tree(&entry.path())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::unique_temp_dir;
+
+    #[test]
+    fn watcher_reports_markdown_changes_in_new_subdirectories() {
+        let root = unique_temp_dir("jarvisctl-watch");
+        fs::create_dir_all(root.join(".obsidian")).unwrap();
+        let mut watcher = VaultWatcher::new(&root).unwrap();
+
+        fs::create_dir_all(root.join("Projects")).unwrap();
+        assert!(
+            watcher
+                .wait(
+                    Duration::from_millis(500),
+                    Duration::from_millis(50),
+                    Duration::from_secs(1)
+                )
+                .unwrap()
+                .is_empty()
+        );
+        fs::write(root.join("Projects").join("Board.md"), "## Ready\n").unwrap();
+        fs::write(root.join(".obsidian").join("workspace.json"), "{}").unwrap();
+        fs::write(root.join("Projects").join("notes.txt"), "ignored").unwrap();
+
+        let changed = watcher
+            .wait(
+                Duration::from_secs(2),
+                Duration::from_millis(50),
+                Duration::from_secs(1),
+            )
+            .unwrap();
+        assert_eq!(changed, vec![root.join("Projects").join("Board.md")]);
+
+        fs::create_dir_all(root.join("Scratch")).unwrap();
+        fs::remove_dir(root.join("Scratch")).unwrap();
+        fs::write(root.join("Projects").join("Board.md"), "## Done\n").unwrap();
+        let changed = watcher
+            .wait(
+                Duration::from_secs(2),
+                Duration::from_millis(50),
+                Duration::from_secs(1),
+            )
+            .unwrap();
+        assert_eq!(changed, vec![root.join("Projects").join("Board.md")]);
+
+        let _ = fs::remove_dir_all(root);
+    }
+}